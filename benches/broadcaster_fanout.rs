@@ -0,0 +1,52 @@
+//! Benchmarks `SessionBroadcaster::publish` fan-out cost as the number of
+//! subscribers grows, to catch regressions in the one hot path every
+//! websocket/SSE session shares.
+//!
+//! `SpotMatcher::on_trade`, a `ReplayService` "turbo mode", and DuckDB
+//! kline range scans aren't benchmarked here because none of them exist in
+//! this tree yet: there's no matching engine (`crate::orders`'s module doc
+//! explains why), `services::replay_service` only has the one
+//! speed-scaled loop with no accelerated mode, and `store`'s only
+//! `MarketStore` implementations are in-memory (see `store`'s module doc).
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_decimal_macros::dec;
+
+use exchange_simulator_backend::broadcaster::SessionBroadcaster;
+use exchange_simulator_backend::domain::{MarketEvent, MarketEventPayload, Symbol, Trade};
+use uuid::Uuid;
+
+fn event() -> MarketEvent {
+    MarketEvent {
+        seq: 0,
+        event_time: 0,
+        payload: MarketEventPayload::Trade(Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            agg_trade_id: 1,
+            price: dec!(100.0),
+            quantity: dec!(1.0),
+            trade_time: 0,
+            is_buyer_maker: false,
+        }),
+    }
+}
+
+fn bench_fanout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broadcaster_publish_fanout");
+
+    for subscriber_count in [0usize, 1, 10, 100] {
+        group.bench_with_input(BenchmarkId::from_parameter(subscriber_count), &subscriber_count, |b, &subscriber_count| {
+            let broadcaster = SessionBroadcaster::new(Uuid::nil(), None);
+            let _subscribers: Vec<_> = (0..subscriber_count).map(|_| broadcaster.subscribe()).collect();
+
+            b.iter(|| {
+                broadcaster.publish(black_box(event()));
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_fanout);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+//! Compares the row-by-row `insert_trade` path against the bulk
+//! `insert_trades` path on `InMemoryTradeStore`. The backing store doesn't
+//! matter for what this demonstrates: `insert_trades` takes the write lock
+//! once for the whole batch instead of once per row, which is the same
+//! shape of win a DuckDB appender gets over one prepared `execute` per row.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_decimal::Decimal;
+
+use exchange_simulator_backend::domain::{Symbol, Trade};
+use exchange_simulator_backend::store::{InMemoryTradeStore, TradeStore};
+
+fn trades(count: i64) -> Vec<Trade> {
+    let symbol = Symbol::new("BTCUSDT");
+    (0..count)
+        .map(|i| Trade {
+            symbol: symbol.clone(),
+            agg_trade_id: i,
+            price: Decimal::new(1_000_000 + i, 2),
+            quantity: Decimal::new(1, 0),
+            trade_time: i * 1_000,
+            is_buyer_maker: i % 2 == 0,
+        })
+        .collect()
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trade_insert_10k");
+
+    group.bench_function("row_by_row", |b| {
+        b.iter(|| {
+            let store = InMemoryTradeStore::new();
+            for trade in trades(10_000) {
+                store.insert_trade(black_box(trade));
+            }
+        });
+    });
+
+    group.bench_function("bulk", |b| {
+        b.iter(|| {
+            let store = InMemoryTradeStore::new();
+            store.insert_trades(black_box(trades(10_000)));
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert);
+criterion_main!(benches);
@@ -0,0 +1,158 @@
+//! Per-session, per-symbol rolling analytics (VWAP, realized volatility,
+//! volume profile), folded in incrementally as `ReplayService` produces
+//! each kline instead of being recomputed from raw candles per request.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use rust_decimal::MathematicalOps;
+use rust_decimal_macros::dec;
+
+use crate::domain::{Decimal, Kline, Symbol, SymbolAnalytics, VolumeBucket};
+
+#[derive(Debug, Default)]
+struct SymbolState {
+    cumulative_notional: Decimal,
+    cumulative_volume: Decimal,
+    last_close: Option<Decimal>,
+    return_count: i64,
+    return_mean: Decimal,
+    return_m2: Decimal,
+    volume_by_bucket: HashMap<Decimal, Decimal>,
+}
+
+/// Tracks rolling analytics per symbol for one session.
+#[derive(Debug, Default)]
+pub struct AnalyticsTracker {
+    symbols: Mutex<HashMap<Symbol, SymbolState>>,
+}
+
+impl AnalyticsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds one more closed kline into its symbol's rolling analytics:
+    /// updates the cumulative VWAP inputs, the Welford online variance of
+    /// close-to-close returns, and the volume-by-price-bucket histogram.
+    pub fn observe_kline(&self, kline: &Kline) {
+        let mut symbols = self.symbols.lock().unwrap();
+        let state = symbols.entry(kline.symbol.clone()).or_default();
+
+        state.cumulative_notional += kline.close * kline.volume;
+        state.cumulative_volume += kline.volume;
+
+        if let Some(previous) = state.last_close {
+            if !previous.is_zero() {
+                let ret = (kline.close - previous) / previous;
+                state.return_count += 1;
+                let delta = ret - state.return_mean;
+                state.return_mean += delta / Decimal::from(state.return_count);
+                let delta2 = ret - state.return_mean;
+                state.return_m2 += delta * delta2;
+            }
+        }
+        state.last_close = Some(kline.close);
+
+        let bucket = kline.close.round();
+        *state.volume_by_bucket.entry(bucket).or_insert(Decimal::ZERO) += kline.volume;
+    }
+
+    /// A snapshot of `symbol`'s analytics as observed so far. Zero-valued
+    /// (not an error) if no klines have been observed for it yet.
+    pub fn snapshot(&self, symbol: &Symbol) -> SymbolAnalytics {
+        let symbols = self.symbols.lock().unwrap();
+        let Some(state) = symbols.get(symbol) else {
+            return empty_analytics();
+        };
+
+        let vwap = if state.cumulative_volume.is_zero() {
+            Decimal::ZERO
+        } else {
+            state.cumulative_notional / state.cumulative_volume
+        };
+
+        let realized_volatility = if state.return_count >= 2 {
+            let variance = state.return_m2 / Decimal::from(state.return_count - 1);
+            variance.sqrt().unwrap_or(Decimal::ZERO)
+        } else {
+            Decimal::ZERO
+        };
+
+        let mut volume_profile: Vec<VolumeBucket> = state
+            .volume_by_bucket
+            .iter()
+            .map(|(price, volume)| VolumeBucket { price: *price, volume: *volume })
+            .collect();
+        volume_profile.sort_by_key(|bucket| bucket.price);
+
+        SymbolAnalytics { vwap, realized_volatility, volume_profile }
+    }
+}
+
+fn empty_analytics() -> SymbolAnalytics {
+    SymbolAnalytics {
+        vwap: dec!(0),
+        realized_volatility: dec!(0),
+        volume_profile: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(symbol: &Symbol, close: Decimal, volume: Decimal) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+            quote_volume: close * volume,
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn an_unobserved_symbol_has_zero_valued_analytics() {
+        let tracker = AnalyticsTracker::new();
+        let analytics = tracker.snapshot(&Symbol::new("BTCUSDT"));
+        assert_eq!(analytics.vwap, Decimal::ZERO);
+        assert_eq!(analytics.realized_volatility, Decimal::ZERO);
+        assert!(analytics.volume_profile.is_empty());
+    }
+
+    #[test]
+    fn vwap_weights_by_volume_across_observed_klines() {
+        let tracker = AnalyticsTracker::new();
+        let symbol = Symbol::new("BTCUSDT");
+        tracker.observe_kline(&kline(&symbol, dec!(100.0), dec!(1.0)));
+        tracker.observe_kline(&kline(&symbol, dec!(110.0), dec!(3.0)));
+
+        let analytics = tracker.snapshot(&symbol);
+        assert_eq!(analytics.vwap, dec!(107.5));
+    }
+
+    #[test]
+    fn volume_profile_buckets_by_rounded_close_price() {
+        let tracker = AnalyticsTracker::new();
+        let symbol = Symbol::new("BTCUSDT");
+        tracker.observe_kline(&kline(&symbol, dec!(100.2), dec!(1.0)));
+        tracker.observe_kline(&kline(&symbol, dec!(100.4), dec!(2.0)));
+        tracker.observe_kline(&kline(&symbol, dec!(101.0), dec!(1.0)));
+
+        let analytics = tracker.snapshot(&symbol);
+        assert_eq!(
+            analytics.volume_profile,
+            vec![
+                VolumeBucket { price: dec!(100), volume: dec!(3.0) },
+                VolumeBucket { price: dec!(101), volume: dec!(1.0) },
+            ]
+        );
+    }
+}
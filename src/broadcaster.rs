@@ -0,0 +1,204 @@
+//! Per-session fan-out of [`MarketEvent`]s to websocket and SSE subscribers.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::domain::MarketEvent;
+
+/// Number of recently emitted events kept around so a reconnecting client
+/// can replay from a `Last-Event-ID` without the session having to replay
+/// the dataset itself. Older gaps still fall back to a full resubscribe.
+const RESUME_BUFFER: usize = 1024;
+
+/// Wraps a `tokio::sync::broadcast` channel with a bounded replay buffer so
+/// late subscribers (SSE reconnects, slow websocket consumers) can catch up
+/// on recent events instead of just seeing a gap.
+pub struct SessionBroadcaster {
+    session_id: Uuid,
+    tx: broadcast::Sender<MarketEvent>,
+    recent: Mutex<VecDeque<MarketEvent>>,
+    next_seq: Mutex<u64>,
+    /// Every event published for this session, independent of the bounded
+    /// `recent` resume buffer above. Backs `GET
+    /// /api/v1/sessions/:id/events`, which needs to serve arbitrarily old
+    /// gaps rather than just the live-reconnect window. Unbounded unless
+    /// `journal_cap` is set, in which case the oldest entries are trimmed
+    /// once it's exceeded — see [`Self::publish`].
+    journal: Mutex<VecDeque<MarketEvent>>,
+    /// From `Config::max_session_journal_events`. `None` preserves the
+    /// original unbounded behavior.
+    journal_cap: Option<usize>,
+    /// Set the first time `journal_cap` is exceeded, so the warning in
+    /// [`Self::publish`] fires once per session instead of once per event
+    /// for the rest of a long-running replay.
+    journal_capped: AtomicBool,
+}
+
+impl SessionBroadcaster {
+    pub fn new(session_id: Uuid, journal_cap: Option<usize>) -> Self {
+        let (tx, _rx) = broadcast::channel(RESUME_BUFFER);
+        Self {
+            session_id,
+            tx,
+            recent: Mutex::new(VecDeque::with_capacity(RESUME_BUFFER)),
+            next_seq: Mutex::new(0),
+            journal: Mutex::new(VecDeque::new()),
+            journal_cap,
+            journal_capped: AtomicBool::new(false),
+        }
+    }
+
+    /// Assigns the next sequence number and fans the event out to current
+    /// subscribers, keeping a copy in the resume buffer.
+    pub fn publish(&self, mut event: MarketEvent) {
+        let seq = {
+            let mut next_seq = self.next_seq.lock().unwrap();
+            let seq = *next_seq;
+            *next_seq += 1;
+            seq
+        };
+        event.seq = seq;
+
+        {
+            let mut recent = self.recent.lock().unwrap();
+            if recent.len() == RESUME_BUFFER {
+                recent.pop_front();
+            }
+            recent.push_back(event.clone());
+        }
+
+        {
+            let mut journal = self.journal.lock().unwrap();
+            journal.push_back(event.clone());
+            if let Some(cap) = self.journal_cap {
+                if journal.len() > cap {
+                    if !self.journal_capped.swap(true, Ordering::SeqCst) {
+                        tracing::warn!(
+                            session_id = %self.session_id,
+                            cap,
+                            "session event journal exceeded configured cap; trimming oldest events, \
+                             GET /sessions/:id/events will no longer see the full history for this session \
+                             (market/klines and market/trades still hold it, unbounded, from the store)"
+                        );
+                    }
+                    while journal.len() > cap {
+                        journal.pop_front();
+                    }
+                }
+            }
+        }
+
+        // No active subscribers is not an error: replay keeps advancing
+        // even if nobody is currently listening.
+        let _ = self.tx.send(event);
+    }
+
+    /// Journaled events with `seq >= from_seq`, oldest first. Unlike
+    /// [`events_since`](Self::events_since), this never reports a gap from
+    /// the live resume buffer's own size — though if `journal_cap` has
+    /// trimmed the journal, a `from_seq` older than what remains silently
+    /// starts from the oldest still-journaled event rather than erroring,
+    /// the same way a deleted log file doesn't corrupt the rest of it.
+    pub fn events_from(&self, from_seq: u64) -> Vec<MarketEvent> {
+        self.journal
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.seq >= from_seq)
+            .cloned()
+            .collect()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<MarketEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Events with `seq > last_seq` that are still in the resume buffer,
+    /// oldest first. Returns `None` if `last_seq` is older than the buffer
+    /// retains, meaning the caller must fall back to a live subscription
+    /// with a gap.
+    pub fn events_since(&self, last_seq: u64) -> Option<Vec<MarketEvent>> {
+        let recent = self.recent.lock().unwrap();
+        match recent.front() {
+            Some(oldest) if oldest.seq > last_seq + 1 => None,
+            _ => Some(
+                recent
+                    .iter()
+                    .filter(|e| e.seq > last_seq)
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::{MarketEventPayload, Symbol, Trade};
+
+    fn event() -> MarketEvent {
+        MarketEvent {
+            seq: 0,
+            event_time: 0,
+            payload: MarketEventPayload::Trade(Trade {
+                symbol: Symbol::new("BTCUSDT"),
+                agg_trade_id: 1,
+                price: dec!(100.0),
+                quantity: dec!(1.0),
+                trade_time: 0,
+                is_buyer_maker: false,
+            }),
+        }
+    }
+
+    #[test]
+    fn assigns_increasing_sequence_numbers() {
+        let b = SessionBroadcaster::new(Uuid::nil(), None);
+        b.publish(event());
+        b.publish(event());
+        let events = b.events_since(0).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].seq, 1);
+    }
+
+    #[test]
+    fn reports_gap_when_resume_point_is_too_old() {
+        let b = SessionBroadcaster::new(Uuid::nil(), None);
+        for _ in 0..(RESUME_BUFFER + 2) {
+            b.publish(event());
+        }
+        assert!(b.events_since(0).is_none());
+    }
+
+    #[test]
+    fn journal_serves_events_past_the_resume_buffer() {
+        let b = SessionBroadcaster::new(Uuid::nil(), None);
+        for _ in 0..(RESUME_BUFFER + 2) {
+            b.publish(event());
+        }
+        // The live resume buffer has dropped seq 0, but the journal hasn't.
+        assert!(b.events_since(0).is_none());
+        let journaled = b.events_from(0);
+        assert_eq!(journaled.len(), RESUME_BUFFER + 2);
+        assert_eq!(journaled[0].seq, 0);
+    }
+
+    #[test]
+    fn a_configured_journal_cap_trims_the_oldest_events() {
+        let b = SessionBroadcaster::new(Uuid::nil(), Some(3));
+        for _ in 0..5 {
+            b.publish(event());
+        }
+        let journaled = b.events_from(0);
+        assert_eq!(journaled.len(), 3);
+        assert_eq!(journaled[0].seq, 2);
+        assert_eq!(journaled[2].seq, 4);
+    }
+}
@@ -0,0 +1,80 @@
+//! Lockstep coordination for sessions sharing a [`Session::clock_group`]
+//! (`domain::session`). Each group's simulated time is a single shared
+//! high-water mark: whichever session's `ReplayService` loop reaches a
+//! given `open_time` first pushes the group forward, and any other member
+//! still behind jumps straight to that value on its next tick instead of
+//! drifting on its own independently-incremented clock. No dedicated
+//! driver task is needed — every member's own tick already keeps the
+//! group's clock moving as long as at least one session in it is running.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+/// Tracks the shared simulated time for every active clock group.
+#[derive(Debug, Default)]
+pub struct ClockGroupRegistry {
+    groups: Mutex<HashMap<Uuid, i64>>,
+}
+
+impl ClockGroupRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Proposes `open_time` as `group`'s next tick and returns the time the
+    /// caller should actually use: `open_time` if it's at or ahead of the
+    /// group's current clock (advancing the group to match), otherwise the
+    /// group's already-further-ahead clock, so a lagging session resyncs
+    /// rather than emitting a kline at a time another member already
+    /// passed.
+    pub fn advance(&self, group: Uuid, open_time: i64) -> i64 {
+        let mut groups = self.groups.lock().unwrap();
+        let current = groups.entry(group).or_insert(open_time);
+        if open_time > *current {
+            *current = open_time;
+        }
+        *current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_first_proposal_for_a_group_is_accepted_as_is() {
+        let registry = ClockGroupRegistry::new();
+        let group = Uuid::new_v4();
+        assert_eq!(registry.advance(group, 60_000), 60_000);
+    }
+
+    #[test]
+    fn a_lagging_member_resyncs_to_the_groups_further_ahead_clock() {
+        let registry = ClockGroupRegistry::new();
+        let group = Uuid::new_v4();
+        registry.advance(group, 120_000);
+
+        assert_eq!(registry.advance(group, 60_000), 120_000);
+    }
+
+    #[test]
+    fn a_leading_member_pushes_the_group_forward() {
+        let registry = ClockGroupRegistry::new();
+        let group = Uuid::new_v4();
+        registry.advance(group, 60_000);
+
+        assert_eq!(registry.advance(group, 120_000), 120_000);
+    }
+
+    #[test]
+    fn different_groups_are_independent() {
+        let registry = ClockGroupRegistry::new();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        registry.advance(first, 120_000);
+
+        assert_eq!(registry.advance(second, 60_000), 60_000);
+    }
+}
@@ -0,0 +1,248 @@
+//! Process configuration, layered: built-in defaults, then an optional TOML
+//! config file, then environment variables (each layer overriding the one
+//! before it). Per-environment profiles are just separate files pointed to
+//! by `CONFIG_FILE` (e.g. `CONFIG_FILE=config.staging.toml`).
+
+use std::env;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Decimal, LatencyProfile, MarketMode};
+
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct Config {
+    /// Origins allowed to call the API from a browser. Empty means no CORS
+    /// layer is installed (same-origin only).
+    pub cors_origins: Vec<String>,
+    /// Directory to serve the bundled web frontend from, if set.
+    pub ui_dir: Option<String>,
+    /// Path to a backup file (see `services::backup`) restored into the
+    /// in-memory stores at startup, if present. Every store in this tree
+    /// (`store::InMemoryMarketStore`, `InMemoryTradeStore`) is in-memory
+    /// only, so this is a flat snapshot file rather than a real embedded
+    /// database's data directory.
+    pub dataset_path: Option<String>,
+    /// Fee rate new sessions fall back to. Not yet applied at session
+    /// creation (`CreateSessionRequest::commission` stays `None` unless
+    /// the caller sets it) — exposed so operators can see what a default
+    /// would be before anything acts on it.
+    #[serde(default = "default_commission_rate")]
+    pub default_commission_rate: Decimal,
+    /// Desired per-session websocket resume buffer size. `SessionBroadcaster`
+    /// still has its own fixed capacity; threading this through is
+    /// follow-up work for once `AppState` takes a `Config` at construction.
+    #[serde(default = "default_ws_buffer_size")]
+    pub ws_buffer_size: usize,
+    /// Cap on historical chunks `services::run_ingest` will pull before
+    /// stopping, once a real ingest source exists. The seed data in
+    /// `simulator::seed_history` is synthesized locally rather than
+    /// fetched, so there is nothing for this to bound yet.
+    pub max_ingest_chunks: Option<usize>,
+    /// Requests allowed per client per minute, once a rate-limiting layer
+    /// exists. No such layer is installed in `http::router` yet.
+    pub rate_limit_per_minute: Option<u32>,
+    /// Per-route-group artificial delay for the `/api/v3` surface. Applied
+    /// by `latency::LatencyInjector`, installed as middleware in
+    /// `http::router`. Empty means no added latency.
+    pub v3_latency: Vec<LatencyProfile>,
+    /// Seeds the jitter component of `v3_latency` so delays are
+    /// reproducible across runs for the same config.
+    #[serde(default = "default_latency_seed")]
+    pub v3_latency_seed: u64,
+    /// Gzip-compresses REST response bodies for clients that send
+    /// `Accept-Encoding: gzip` (see `http::compression`). On by default: it
+    /// only changes anything for callers that already negotiate the
+    /// encoding, so there's no surprising behavior for the ones that don't.
+    /// The websocket stream isn't covered — there's no permessage-deflate
+    /// support here, since negotiating a per-message extension means
+    /// hand-rolling the upgrade handshake rather than using
+    /// `WebSocketUpgrade` as-is.
+    #[serde(default = "default_enable_compression")]
+    pub enable_compression: bool,
+    /// How long a `Created`/`Paused` session may sit with no API activity
+    /// and no open websocket subscriber before `services::reaper` reclaims
+    /// it. `None` (the default) disables the reaper entirely — an operator
+    /// opts in once they actually have a shared, long-running deployment
+    /// to protect.
+    pub idle_session_ttl_secs: Option<u64>,
+    /// Whether a reaped session is removed outright (freeing its
+    /// broadcaster, ledgers and trackers for reuse of the id) rather than
+    /// just stopped with its per-session trackers cleared. Off by default
+    /// so a session's final status and summary stay inspectable unless an
+    /// operator explicitly wants the memory back.
+    #[serde(default)]
+    pub idle_session_delete: bool,
+    /// Caps how many events `broadcaster::SessionBroadcaster` keeps in a
+    /// session's journal (the unbounded history backing `GET
+    /// /api/v1/sessions/:id/events`). `None` (the default) preserves the
+    /// original unbounded journal; once set, the oldest events are trimmed
+    /// past the cap and a structured warning is logged, since a
+    /// high-frequency aggTrades session left running indefinitely would
+    /// otherwise grow the journal without bound. Trimmed history for
+    /// kline/trade events is still available from the store itself via
+    /// `GET /api/v1/market/klines` and `/market/trades`, which this tree
+    /// already keeps in full rather than windowed.
+    pub max_session_journal_events: Option<usize>,
+    /// Whether startup should re-attempt `IngestionLedger` records left
+    /// `Resumable` by `services::backup::restore_if_present`. Off by
+    /// default since resuming only actually does anything once session
+    /// persistence lands — see `ingestion_ledger`'s module doc for why a
+    /// `Resumable` record's session never exists yet after a real restart.
+    #[serde(default)]
+    pub auto_resume_ingestions: bool,
+    /// What a session publishes when it doesn't set
+    /// [`Session::market_mode`](crate::domain::Session::market_mode)
+    /// itself. Defaults to [`MarketMode::Klines`], the only behavior any
+    /// session had before this field existed.
+    #[serde(default = "default_market_mode")]
+    pub default_market_mode: MarketMode,
+}
+
+fn default_commission_rate() -> Decimal {
+    Decimal::new(10, 4)
+}
+
+fn default_ws_buffer_size() -> usize {
+    1024
+}
+
+fn default_latency_seed() -> u64 {
+    1
+}
+
+fn default_enable_compression() -> bool {
+    true
+}
+
+fn default_market_mode() -> MarketMode {
+    MarketMode::Klines
+}
+
+fn parse_market_mode(raw: &str) -> Option<MarketMode> {
+    match raw {
+        "klines" => Some(MarketMode::Klines),
+        "agg_trades" => Some(MarketMode::AggTrades),
+        "hybrid" => Some(MarketMode::Hybrid),
+        _ => None,
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cors_origins: Vec::new(),
+            ui_dir: None,
+            dataset_path: None,
+            default_commission_rate: default_commission_rate(),
+            ws_buffer_size: default_ws_buffer_size(),
+            max_ingest_chunks: None,
+            rate_limit_per_minute: None,
+            v3_latency: Vec::new(),
+            v3_latency_seed: default_latency_seed(),
+            enable_compression: default_enable_compression(),
+            idle_session_ttl_secs: None,
+            idle_session_delete: false,
+            max_session_journal_events: None,
+            auto_resume_ingestions: false,
+            default_market_mode: default_market_mode(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads defaults, overlays the TOML file at `CONFIG_FILE` (or
+    /// `config.toml` if that var is unset and the file exists), then
+    /// overlays environment variables on top. A missing or unparseable
+    /// config file is not an error — it just leaves the defaults in place.
+    pub fn load() -> Self {
+        let mut config = Self::from_file().unwrap_or_default();
+        config.apply_env();
+        config
+    }
+
+    fn from_file() -> Option<Self> {
+        let path = env::var("CONFIG_FILE").unwrap_or_else(|_| DEFAULT_CONFIG_FILE.to_string());
+        let contents = fs::read_to_string(&path).ok()?;
+        match toml::from_str(&contents) {
+            Ok(config) => Some(config),
+            Err(err) => {
+                tracing::warn!(path, %err, "failed to parse config file, falling back to defaults");
+                None
+            }
+        }
+    }
+
+    fn apply_env(&mut self) {
+        if let Ok(raw) = env::var("CORS_ORIGINS") {
+            self.cors_origins = raw
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Ok(ui_dir) = env::var("UI_DIR") {
+            self.ui_dir = Some(ui_dir);
+        }
+        if let Ok(dataset_path) = env::var("DATASET_PATH") {
+            self.dataset_path = Some(dataset_path);
+        }
+        if let Ok(raw) = env::var("WS_BUFFER_SIZE") {
+            if let Ok(parsed) = raw.parse() {
+                self.ws_buffer_size = parsed;
+            }
+        }
+        if let Ok(raw) = env::var("MAX_INGEST_CHUNKS") {
+            if let Ok(parsed) = raw.parse() {
+                self.max_ingest_chunks = Some(parsed);
+            }
+        }
+        if let Ok(raw) = env::var("RATE_LIMIT_PER_MINUTE") {
+            if let Ok(parsed) = raw.parse() {
+                self.rate_limit_per_minute = Some(parsed);
+            }
+        }
+        if let Ok(raw) = env::var("ENABLE_COMPRESSION") {
+            if let Ok(parsed) = raw.parse() {
+                self.enable_compression = parsed;
+            }
+        }
+        if let Ok(raw) = env::var("IDLE_SESSION_TTL_SECS") {
+            if let Ok(parsed) = raw.parse() {
+                self.idle_session_ttl_secs = Some(parsed);
+            }
+        }
+        if let Ok(raw) = env::var("IDLE_SESSION_DELETE") {
+            if let Ok(parsed) = raw.parse() {
+                self.idle_session_delete = parsed;
+            }
+        }
+        if let Ok(raw) = env::var("MAX_SESSION_JOURNAL_EVENTS") {
+            if let Ok(parsed) = raw.parse() {
+                self.max_session_journal_events = Some(parsed);
+            }
+        }
+        if let Ok(raw) = env::var("AUTO_RESUME_INGESTIONS") {
+            if let Ok(parsed) = raw.parse() {
+                self.auto_resume_ingestions = parsed;
+            }
+        }
+        if let Ok(raw) = env::var("DEFAULT_MARKET_MODE") {
+            if let Some(parsed) = parse_market_mode(&raw) {
+                self.default_market_mode = parsed;
+            }
+        }
+    }
+
+    /// The config as returned by `GET /api/v1/config`. Nothing here is
+    /// secret-shaped today, but routing through this method rather than
+    /// serializing `Config` directly at the call site means a future
+    /// secret-bearing field has one obvious place to get redacted.
+    pub fn sanitized(&self) -> Self {
+        self.clone()
+    }
+}
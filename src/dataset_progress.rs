@@ -0,0 +1,125 @@
+//! Tracks one session's dataset backfill throughput (see
+//! `services::dataset::spawn_backfill`), folded in incrementally as each
+//! ingest chunk lands instead of being derived after the fact.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::domain::{DatasetProgress, ThrottleReason};
+
+/// A rough stand-in for a real network transfer size, since this tree
+/// backfills synthetic candles rather than downloading bytes — see
+/// `DatasetProgress::bytes_downloaded`.
+pub(crate) const ESTIMATED_BYTES_PER_ROW: i64 = 64;
+
+#[derive(Debug, Default)]
+pub struct DatasetProgressTracker {
+    progress: Mutex<DatasetProgress>,
+}
+
+impl DatasetProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `rows_ingested` rows have landed after `elapsed` of
+    /// backfilling, with `remaining_rows` left to go (`None` if the total
+    /// isn't known, e.g. no gaps to fill at all) and `anomalies` rejected
+    /// so far (see [`DatasetProgress::anomalies`]). `rows_per_second` and
+    /// `eta_seconds` are derived from the average rate since the backfill
+    /// started, not an instantaneous rate, so a batch of slow or fast
+    /// chunks doesn't make the ETA swing wildly between updates.
+    pub fn record(&self, rows_ingested: i64, elapsed: Duration, remaining_rows: Option<i64>, anomalies: u64) {
+        let elapsed_secs = Decimal::try_from(elapsed.as_secs_f64()).unwrap_or(Decimal::ZERO);
+        let rows_per_second = if elapsed_secs.is_zero() {
+            Decimal::ZERO
+        } else {
+            Decimal::from(rows_ingested) / elapsed_secs
+        };
+
+        let eta_seconds = match remaining_rows {
+            Some(remaining) if remaining > 0 && rows_per_second > Decimal::ZERO => {
+                (Decimal::from(remaining) / rows_per_second).to_i64()
+            }
+            _ => None,
+        };
+
+
+        let mut progress = self.progress.lock().unwrap();
+        progress.rows_ingested = rows_ingested;
+        progress.rows_per_second = rows_per_second;
+        progress.bytes_downloaded = rows_ingested.saturating_mul(ESTIMATED_BYTES_PER_ROW);
+        progress.eta_seconds = eta_seconds;
+        progress.anomalies = anomalies;
+    }
+
+    pub fn snapshot(&self) -> DatasetProgress {
+        *self.progress.lock().unwrap()
+    }
+
+    /// Folds a `services::rate_limit::RateLimiter::state` snapshot into
+    /// this tracker's progress, independently of [`Self::record`] since
+    /// throttling and row counts can each change without the other.
+    pub fn record_throttle(&self, used_weight: u32, reason: Option<ThrottleReason>, retry_after_ms: Option<u64>) {
+        let mut progress = self.progress.lock().unwrap();
+        progress.used_weight = used_weight;
+        progress.throttle_reason = reason;
+        progress.retry_after_ms = retry_after_ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_untouched_tracker_reports_zero_progress() {
+        let tracker = DatasetProgressTracker::new();
+        let progress = tracker.snapshot();
+        assert_eq!(progress.rows_ingested, 0);
+        assert_eq!(progress.eta_seconds, None);
+    }
+
+    #[test]
+    fn records_rate_and_eta_from_elapsed_time_and_remaining_rows() {
+        let tracker = DatasetProgressTracker::new();
+        tracker.record(100, Duration::from_secs(10), Some(400), 0);
+
+        let progress = tracker.snapshot();
+        assert_eq!(progress.rows_ingested, 100);
+        assert_eq!(progress.rows_per_second, Decimal::from(10));
+        assert_eq!(progress.bytes_downloaded, 100 * ESTIMATED_BYTES_PER_ROW);
+        assert_eq!(progress.eta_seconds, Some(40));
+        assert_eq!(progress.anomalies, 0);
+    }
+
+    #[test]
+    fn eta_is_none_once_nothing_remains() {
+        let tracker = DatasetProgressTracker::new();
+        tracker.record(500, Duration::from_secs(10), Some(0), 0);
+        assert_eq!(tracker.snapshot().eta_seconds, None);
+    }
+
+    #[test]
+    fn records_the_cumulative_anomaly_count() {
+        let tracker = DatasetProgressTracker::new();
+        tracker.record(100, Duration::from_secs(10), Some(400), 3);
+        assert_eq!(tracker.snapshot().anomalies, 3);
+    }
+
+    #[test]
+    fn recording_throttle_state_is_independent_of_row_progress() {
+        let tracker = DatasetProgressTracker::new();
+        tracker.record(100, Duration::from_secs(10), Some(400), 0);
+        tracker.record_throttle(1100, Some(ThrottleReason::RateLimited), Some(30_000));
+
+        let progress = tracker.snapshot();
+        assert_eq!(progress.rows_ingested, 100);
+        assert_eq!(progress.used_weight, 1100);
+        assert_eq!(progress.throttle_reason, Some(ThrottleReason::RateLimited));
+        assert_eq!(progress.retry_after_ms, Some(30_000));
+    }
+}
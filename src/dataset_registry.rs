@@ -0,0 +1,189 @@
+//! Global (not session-scoped) bookkeeping of registered dataset coverage,
+//! so registering the same symbol/interval/range twice can be rejected (or
+//! merged) instead of silently creating a second overlapping copy. This is
+//! separate from `services::dataset::gaps`/`spawn_backfill`'s implicit,
+//! per-session backfill path — a session still fills its own gaps in
+//! `store::MarketStore` the way it always has, whether or not the coverage
+//! was ever registered here. A [`DatasetRecord`] just records the *intent*
+//! "this coverage exists", for callers (`http::datasets::register_dataset`)
+//! that actually want conflict/dedupe semantics around that intent.
+
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use crate::domain::{DatasetRecord, Symbol};
+
+#[derive(Default)]
+pub struct DatasetRegistry {
+    records: Mutex<Vec<DatasetRecord>>,
+}
+
+impl DatasetRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `symbols`/`interval`/`[start, end)` as a new dataset.
+    /// `Err(existing)` if it overlaps an already-registered record (shares
+    /// a symbol, the same interval, and an intersecting range) and
+    /// `dedupe` is `false`. With `dedupe` set, an overlap is folded into
+    /// the existing record instead — its symbol list gains any new
+    /// symbols and its range grows to the union of both — and the merged
+    /// record is returned as `Ok`.
+    pub fn register(&self, symbols: Vec<Symbol>, interval: String, start: i64, end: i64, dedupe: bool) -> Result<DatasetRecord, DatasetRecord> {
+        let mut records = self.records.lock().unwrap();
+        if let Some(idx) = records.iter().position(|r| overlaps(r, &symbols, &interval, start, end)) {
+            if !dedupe {
+                return Err(records[idx].clone());
+            }
+            let existing = &mut records[idx];
+            for symbol in &symbols {
+                if !existing.symbols.contains(symbol) {
+                    existing.symbols.push(symbol.clone());
+                }
+            }
+            existing.start = existing.start.min(start);
+            existing.end = existing.end.max(end);
+            return Ok(existing.clone());
+        }
+
+        let record = DatasetRecord { id: Uuid::new_v4(), symbols, interval, start, end };
+        records.push(record.clone());
+        Ok(record)
+    }
+
+    /// Folds every pair of registered records that share a symbol, an
+    /// interval, and an overlapping range into one record spanning their
+    /// union, obsoleting the ones folded in. Returns the registry's
+    /// records after merging. O(n^2) in record count, which is fine for
+    /// the handful of datasets a single instance accumulates.
+    pub fn merge_overlaps(&self) -> Vec<DatasetRecord> {
+        let mut records = self.records.lock().unwrap();
+        let mut merged: Vec<DatasetRecord> = Vec::new();
+
+        'records: for record in records.drain(..) {
+            for existing in merged.iter_mut() {
+                if existing.interval == record.interval
+                    && existing.start < record.end
+                    && record.start < existing.end
+                    && existing.symbols.iter().any(|s| record.symbols.contains(s))
+                {
+                    for symbol in &record.symbols {
+                        if !existing.symbols.contains(symbol) {
+                            existing.symbols.push(symbol.clone());
+                        }
+                    }
+                    existing.start = existing.start.min(record.start);
+                    existing.end = existing.end.max(record.end);
+                    continue 'records;
+                }
+            }
+            merged.push(record);
+        }
+
+        *records = merged.clone();
+        merged
+    }
+
+    pub fn all(&self) -> Vec<DatasetRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<DatasetRecord> {
+        self.records.lock().unwrap().iter().find(|record| record.id == id).cloned()
+    }
+
+    /// Removes and returns the record registered under `id`, if any. Pure
+    /// bookkeeping — doesn't touch `store::MarketStore`/`TradeStore`, whose
+    /// rows this record merely described the coverage of. See
+    /// `http::admin::delete_dataset` for the referential checks a caller
+    /// should run first.
+    pub fn remove(&self, id: Uuid) -> Option<DatasetRecord> {
+        let mut records = self.records.lock().unwrap();
+        let idx = records.iter().position(|record| record.id == id)?;
+        Some(records.remove(idx))
+    }
+}
+
+fn overlaps(record: &DatasetRecord, symbols: &[Symbol], interval: &str, start: i64, end: i64) -> bool {
+    record.interval == interval
+        && record.start < end
+        && start < record.end
+        && record.symbols.iter().any(|s| symbols.contains(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(names: &[&str]) -> Vec<Symbol> {
+        names.iter().map(|s| Symbol::new(*s)).collect()
+    }
+
+    #[test]
+    fn registering_disjoint_ranges_never_conflicts() {
+        let registry = DatasetRegistry::new();
+        registry.register(symbols(&["BTCUSDT"]), "1m".into(), 0, 1000, false).unwrap();
+        let result = registry.register(symbols(&["BTCUSDT"]), "1m".into(), 1000, 2000, false);
+        assert!(result.is_ok());
+        assert_eq!(registry.all().len(), 2);
+    }
+
+    #[test]
+    fn an_overlapping_registration_without_dedupe_conflicts_with_the_existing_record() {
+        let registry = DatasetRegistry::new();
+        let first = registry.register(symbols(&["BTCUSDT"]), "1m".into(), 0, 1000, false).unwrap();
+        let err = registry.register(symbols(&["BTCUSDT"]), "1m".into(), 500, 1500, false).unwrap_err();
+        assert_eq!(err.id, first.id);
+    }
+
+    #[test]
+    fn a_different_interval_never_conflicts_even_over_the_same_range() {
+        let registry = DatasetRegistry::new();
+        registry.register(symbols(&["BTCUSDT"]), "1m".into(), 0, 1000, false).unwrap();
+        let result = registry.register(symbols(&["BTCUSDT"]), "5m".into(), 0, 1000, false);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn dedupe_merges_the_overlap_into_the_existing_record_instead_of_conflicting() {
+        let registry = DatasetRegistry::new();
+        let first = registry.register(symbols(&["BTCUSDT"]), "1m".into(), 0, 1000, false).unwrap();
+        let merged = registry.register(symbols(&["BTCUSDT", "ETHUSDT"]), "1m".into(), 500, 1500, true).unwrap();
+        assert_eq!(merged.id, first.id);
+        assert_eq!(merged.start, 0);
+        assert_eq!(merged.end, 1500);
+        assert_eq!(merged.symbols, symbols(&["BTCUSDT", "ETHUSDT"]));
+        assert_eq!(registry.all().len(), 1);
+    }
+
+    #[test]
+    fn merge_overlaps_folds_every_overlapping_record_and_leaves_disjoint_ones_separate() {
+        let registry = DatasetRegistry::new();
+        registry.records.lock().unwrap().push(DatasetRecord { id: Uuid::new_v4(), symbols: symbols(&["BTCUSDT"]), interval: "1m".into(), start: 0, end: 1000 });
+        registry.records.lock().unwrap().push(DatasetRecord { id: Uuid::new_v4(), symbols: symbols(&["BTCUSDT"]), interval: "1m".into(), start: 500, end: 1500 });
+        registry.records.lock().unwrap().push(DatasetRecord { id: Uuid::new_v4(), symbols: symbols(&["ETHUSDT"]), interval: "1m".into(), start: 0, end: 1000 });
+
+        let merged = registry.merge_overlaps();
+        assert_eq!(merged.len(), 2);
+        let btc = merged.iter().find(|r| r.symbols.contains(&Symbol::new("BTCUSDT"))).unwrap();
+        assert_eq!(btc.start, 0);
+        assert_eq!(btc.end, 1500);
+    }
+
+    #[test]
+    fn removing_a_registered_record_drops_it_and_returns_it() {
+        let registry = DatasetRegistry::new();
+        let record = registry.register(symbols(&["BTCUSDT"]), "1m".into(), 0, 1000, false).unwrap();
+        let removed = registry.remove(record.id).unwrap();
+        assert_eq!(removed.id, record.id);
+        assert!(registry.all().is_empty());
+    }
+
+    #[test]
+    fn removing_an_unregistered_id_is_a_no_op() {
+        let registry = DatasetRegistry::new();
+        assert!(registry.remove(Uuid::new_v4()).is_none());
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::Decimal;
+
+/// Rolling analytics for one symbol within a session, maintained
+/// incrementally by `ReplayService` as klines are produced (see
+/// `crate::analytics::AnalyticsTracker`) rather than recomputed from raw
+/// candles on every request.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SymbolAnalytics {
+    /// Volume-weighted average close price across every kline observed so far.
+    pub vwap: Decimal,
+    /// Sample standard deviation of consecutive close-to-close returns.
+    /// Zero until at least two klines have been observed.
+    pub realized_volatility: Decimal,
+    /// Traded volume bucketed by close price, rounded to the nearest whole
+    /// unit, ordered by price ascending.
+    pub volume_profile: Vec<VolumeBucket>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct VolumeBucket {
+    pub price: Decimal,
+    pub volume: Decimal,
+}
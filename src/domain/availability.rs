@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::Symbol;
+
+/// One contiguous gap in `[start, end)` with no klines for a symbol/interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MissingSpan {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// `GET /api/v1/market/availability`'s response: the contiguous gaps in
+/// `[start, end)` this tree's local store has no klines for, so a UI can
+/// offer "ingest the missing part" per span instead of per candle. There's
+/// no external fetch source wired into this tree (see
+/// `services::dataset`'s module doc), so unlike the request that asked for
+/// this endpoint, there's no separate "remote Binance range" to merge
+/// against — every span reported here is just local coverage.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketAvailability {
+    pub symbol: Symbol,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+    pub missing_spans: Vec<MissingSpan>,
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossDirection {
+    Above,
+    Below,
+}
+
+/// A condition `ReplayService` checks every tick (see
+/// `services::replay_service::find_triggered_breakpoint`). The first one
+/// that's true pauses the session and publishes a [`BreakpointEvent`] (see
+/// [`super::MarketEventPayload::Breakpoint`]) describing which condition
+/// fired, then is removed from [`super::Session::breakpoints`] so it
+/// doesn't refire on the next tick. A time-based breakpoint is its own,
+/// earlier mechanism — see `Session::pause_at` and
+/// `http::sessions::pause_at`.
+///
+/// Only [`BreakpointCondition::PriceCrosses`] is ever actually evaluated
+/// as true today: `OrderFilled` has no order/fill pipeline to watch (see
+/// `orders`' module doc) and `DrawdownExceeds` has no equity-curve history
+/// to measure a drawdown against (see `http::reports`' module doc). Both
+/// are still accepted so the condition vocabulary is complete and ready to
+/// wire up the moment either pipeline lands, rather than this tree
+/// rejecting a perfectly reasonable breakpoint request today only to need
+/// a breaking API change later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "condition", rename_all = "snake_case")]
+pub enum BreakpointCondition {
+    PriceCrosses {
+        symbol: Symbol,
+        price: Decimal,
+        direction: CrossDirection,
+    },
+    OrderFilled,
+    DrawdownExceeds {
+        percent: Decimal,
+    },
+}
+
+/// Published when a [`BreakpointCondition`] fires.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BreakpointEvent {
+    pub condition: BreakpointCondition,
+}
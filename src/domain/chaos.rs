@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Seeded fault injection for a session's websocket stream: probabilities
+/// (each in `[0, 1]`) for dropping the connection, delaying a frame,
+/// duplicating one, or holding it back to swap order with the next. Bot
+/// authors use this to exercise resilience logic that's otherwise only
+/// reachable by chance against a real exchange.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ChaosConfig {
+    pub seed: u64,
+    pub disconnect_probability: f64,
+    pub delay_probability: f64,
+    pub max_delay_ms: u64,
+    pub duplicate_probability: f64,
+    pub reorder_probability: f64,
+}
+
+/// Deterministic chaos decisions: same seed and call sequence always
+/// produces the same outcomes, using the same xorshift64* generator as
+/// [`super::clock::DriftGenerator`] so replays stay reproducible.
+#[derive(Debug, Clone)]
+pub struct ChaosGenerator {
+    config: ChaosConfig,
+    state: u64,
+}
+
+impl ChaosGenerator {
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            state: config.seed | 1,
+            config,
+        }
+    }
+
+    /// Next pseudo-random value in `[0, 1)`.
+    fn next_unit(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn should_disconnect(&mut self) -> bool {
+        self.next_unit() < self.config.disconnect_probability
+    }
+
+    pub fn should_duplicate(&mut self) -> bool {
+        self.next_unit() < self.config.duplicate_probability
+    }
+
+    pub fn should_reorder(&mut self) -> bool {
+        self.next_unit() < self.config.reorder_probability
+    }
+
+    /// `None` if this frame isn't delayed, otherwise how long to hold it.
+    pub fn delay(&mut self) -> Option<std::time::Duration> {
+        if self.next_unit() >= self.config.delay_probability {
+            return None;
+        }
+        let ms = (self.next_unit() * self.config.max_delay_ms as f64) as u64;
+        Some(std::time::Duration::from_millis(ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ChaosConfig {
+        ChaosConfig {
+            seed: 7,
+            disconnect_probability: 0.3,
+            delay_probability: 0.5,
+            max_delay_ms: 100,
+            duplicate_probability: 0.2,
+            reorder_probability: 0.2,
+        }
+    }
+
+    #[test]
+    fn same_seed_is_reproducible() {
+        let mut a = ChaosGenerator::new(config());
+        let mut b = ChaosGenerator::new(config());
+        for _ in 0..50 {
+            assert_eq!(a.should_disconnect(), b.should_disconnect());
+            assert_eq!(a.delay(), b.delay());
+        }
+    }
+
+    #[test]
+    fn zero_probability_never_fires() {
+        let mut generator = ChaosGenerator::new(ChaosConfig {
+            seed: 42,
+            disconnect_probability: 0.0,
+            delay_probability: 0.0,
+            max_delay_ms: 100,
+            duplicate_probability: 0.0,
+            reorder_probability: 0.0,
+        });
+        for _ in 0..1000 {
+            assert!(!generator.should_disconnect());
+            assert!(generator.delay().is_none());
+            assert!(!generator.should_duplicate());
+            assert!(!generator.should_reorder());
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Bounded, seeded clock skew/jitter applied to a session's emitted event
+/// timestamps, so bots' time-sync logic and `recvWindow` handling can be
+/// exercised against an exchange clock that isn't perfectly in sync.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ClockDriftConfig {
+    pub max_skew_ms: i64,
+    pub seed: u64,
+}
+
+/// Deterministic jitter source: same seed and call sequence always produces
+/// the same skew, so replays stay reproducible.
+#[derive(Debug, Clone)]
+pub struct DriftGenerator {
+    config: ClockDriftConfig,
+    state: u64,
+}
+
+impl DriftGenerator {
+    pub fn new(config: ClockDriftConfig) -> Self {
+        Self {
+            state: config.seed | 1,
+            config,
+        }
+    }
+
+    /// Applies the next jittered skew to `base_ms`, bounded to
+    /// `[-max_skew_ms, max_skew_ms]`.
+    pub fn drift(&mut self, base_ms: i64) -> i64 {
+        // xorshift64*: fast, deterministic, good enough for bounded jitter.
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        let span = (2 * self.config.max_skew_ms + 1).max(1) as u64;
+        let offset = (self.state % span) as i64 - self.config.max_skew_ms;
+        base_ms + offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_within_configured_bounds() {
+        let mut gen = DriftGenerator::new(ClockDriftConfig {
+            max_skew_ms: 50,
+            seed: 42,
+        });
+        for base in 0..1000 {
+            let drifted = gen.drift(base);
+            assert!((drifted - base).abs() <= 50);
+        }
+    }
+
+    #[test]
+    fn same_seed_is_reproducible() {
+        let config = ClockDriftConfig {
+            max_skew_ms: 25,
+            seed: 7,
+        };
+        let mut a = DriftGenerator::new(config);
+        let mut b = DriftGenerator::new(config);
+        for base in 0..10 {
+            assert_eq!(a.drift(base), b.drift(base));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// Maker/taker fee rates a future fill handler would charge per trade, on
+/// top of the BNB-style `asset`/`discount` rebate already modeled below.
+/// `maker_bps`/`taker_bps` may be negative — a negative maker rate models
+/// a maker rebate (the exchange pays the maker instead of charging them),
+/// the way real venues incentivize liquidity provision at some fee tiers.
+/// This tree has no order/fill pipeline yet (see `orders`' module doc and
+/// `crate::matching`'s module doc for why there's no `SpotMatcher` to wire
+/// fee math into), so `fee`/`discounted_fee` are the pieces that handler
+/// would call per trade to compute what actually gets deducted (or
+/// credited, for a rebate).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CommissionConfig {
+    pub asset: String,
+    pub discount: Decimal,
+    #[serde(default)]
+    pub maker_bps: Decimal,
+    #[serde(default)]
+    pub taker_bps: Decimal,
+    /// Per-symbol overrides of `maker_bps`/`taker_bps`, checked before
+    /// falling back to the session-wide rate — e.g. a promotional
+    /// zero-fee pair alongside a standard-rate book for everything else.
+    #[serde(default)]
+    pub symbol_overrides: Vec<SymbolFeeOverride>,
+}
+
+/// `maker_bps`/`taker_bps` for one symbol, overriding
+/// [`CommissionConfig::maker_bps`]/[`CommissionConfig::taker_bps`] for
+/// trades in that symbol only.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SymbolFeeOverride {
+    pub symbol: Symbol,
+    pub maker_bps: Decimal,
+    pub taker_bps: Decimal,
+}
+
+impl CommissionConfig {
+    fn bps(&self, symbol: &Symbol, is_maker: bool) -> Decimal {
+        let fallback = if is_maker { self.maker_bps } else { self.taker_bps };
+        self.symbol_overrides
+            .iter()
+            .find(|override_| &override_.symbol == symbol)
+            .map(|override_| if is_maker { override_.maker_bps } else { override_.taker_bps })
+            .unwrap_or(fallback)
+    }
+
+    /// The fee owed on a fill of `notional` quote value in `symbol` —
+    /// negative when the resolved rate is a maker rebate. `bps` means
+    /// what it always does, hundredths of a percent: `notional *
+    /// bps / 10_000`.
+    pub fn fee(&self, symbol: &Symbol, notional: Decimal, is_maker: bool) -> Decimal {
+        notional * self.bps(symbol, is_maker) / Decimal::from(10_000)
+    }
+
+    /// Applies the configured discount to a standard fee amount. A
+    /// negative `standard_fee` (a maker rebate) is scaled the same way a
+    /// positive one is, so a discounted rebate asset still shrinks the
+    /// rebate's magnitude rather than only ever discounting a charge.
+    pub fn discounted_fee(&self, standard_fee: Decimal) -> Decimal {
+        standard_fee * (Decimal::ONE - self.discount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn config() -> CommissionConfig {
+        CommissionConfig {
+            asset: "BNB".into(),
+            discount: dec!(0.25),
+            maker_bps: dec!(1.0),
+            taker_bps: dec!(4.0),
+            symbol_overrides: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn discounted_fee_reduces_standard_rate() {
+        let config = config();
+        assert_eq!(config.discounted_fee(dec!(10.0)), dec!(7.50));
+    }
+
+    #[test]
+    fn taker_fee_is_computed_from_notional_and_bps() {
+        let config = config();
+        let symbol = Symbol::new("BTCUSDT");
+        assert_eq!(config.fee(&symbol, dec!(10_000), false), dec!(4.0));
+    }
+
+    #[test]
+    fn negative_maker_bps_produces_a_rebate() {
+        let mut config = config();
+        config.maker_bps = dec!(-1.0);
+        let symbol = Symbol::new("BTCUSDT");
+        assert_eq!(config.fee(&symbol, dec!(10_000), true), dec!(-1.0));
+    }
+
+    #[test]
+    fn a_symbol_override_takes_priority_over_the_session_wide_rate() {
+        let mut config = config();
+        config.symbol_overrides.push(SymbolFeeOverride {
+            symbol: Symbol::new("ETHUSDT"),
+            maker_bps: dec!(-2.0),
+            taker_bps: dec!(0.0),
+        });
+        let btc = Symbol::new("BTCUSDT");
+        let eth = Symbol::new("ETHUSDT");
+        assert_eq!(config.fee(&eth, dec!(10_000), true), dec!(-2.0));
+        assert_eq!(config.fee(&btc, dec!(10_000), true), dec!(1.0));
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A dry-run projection for a prospective ingestion, so a caller can see
+/// roughly how long a multi-year, 1m-interval backfill would take before
+/// actually starting one. See
+/// [`services::dataset::estimate`](crate::services::dataset::estimate).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DatasetEstimate {
+    pub candle_count: i64,
+    pub request_count: i64,
+    /// `None` if no `rate_limit_per_minute` is configured — there's
+    /// nothing to pace the estimate against.
+    pub estimated_duration_seconds: Option<i64>,
+    /// Same per-row size estimate `DatasetProgress::bytes_downloaded`
+    /// uses, since neither corresponds to a real on-disk or wire format in
+    /// this tree yet.
+    pub estimated_storage_bytes: i64,
+}
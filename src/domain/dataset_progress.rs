@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, ThrottleReason};
+
+/// A snapshot of how far a dataset backfill (see
+/// [`services::dataset::spawn_backfill`](crate::services::dataset::spawn_backfill))
+/// has gotten, so a UI polling a session can show a realistic ETA for a
+/// multi-month ingestion instead of an indeterminate spinner.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DatasetProgress {
+    pub rows_ingested: i64,
+    pub rows_per_second: Decimal,
+    /// Approximate: this tree backfills synthetic candles rather than
+    /// bytes off the wire, so this is `rows_ingested` times a fixed
+    /// per-row size estimate rather than a real transfer count.
+    pub bytes_downloaded: i64,
+    /// `None` until at least one row has landed, or once every gap has
+    /// been filled — there's nothing left to extrapolate a remaining time
+    /// from either way.
+    pub eta_seconds: Option<i64>,
+    /// Rows `services::validate_klines` rejected during this backfill
+    /// (non-positive price, `high < low`, or an `open_time` outside the
+    /// requested window) — a nonzero count means the upstream chunk had
+    /// bad data that was dropped rather than silently inserted.
+    pub anomalies: u64,
+    /// Last observed `X-MBX-USED-WEIGHT-*` value, via
+    /// `services::rate_limit::RateLimiter`. Always `0` in this tree today
+    /// — see that module's doc for why.
+    pub used_weight: u32,
+    /// `None` while the next fetch isn't being delayed.
+    pub throttle_reason: Option<ThrottleReason>,
+    /// How long the next fetch is being delayed, if `throttle_reason` is set.
+    pub retry_after_ms: Option<u64>,
+}
+
+impl DatasetProgress {
+    /// A coarse classification derived from this snapshot's fields, for
+    /// callers (e.g. `http::datasets::dataset_events`) that want to filter
+    /// or group backfills without reasoning about `eta_seconds`/
+    /// `throttle_reason` themselves. Not a persisted field — recomputed
+    /// fresh from whatever the snapshot already holds.
+    pub fn status(&self) -> DatasetProgressStatus {
+        if self.throttle_reason.is_some() {
+            DatasetProgressStatus::Throttled
+        } else if self.rows_ingested == 0 {
+            DatasetProgressStatus::Idle
+        } else if self.eta_seconds.is_none() {
+            DatasetProgressStatus::Complete
+        } else {
+            DatasetProgressStatus::InProgress
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DatasetProgressStatus {
+    /// No rows have landed yet.
+    Idle,
+    InProgress,
+    /// `throttle_reason` is set — takes priority over `InProgress` since a
+    /// throttled backfill isn't currently making progress.
+    Throttled,
+    /// At least one row has landed and `eta_seconds` is `None`, meaning
+    /// there's nothing left to extrapolate a remaining time from.
+    Complete,
+}
+
+#[cfg(test)]
+mod status_tests {
+    use super::*;
+
+    #[test]
+    fn an_untouched_snapshot_is_idle() {
+        assert_eq!(DatasetProgress::default().status(), DatasetProgressStatus::Idle);
+    }
+
+    #[test]
+    fn throttled_takes_priority_over_in_progress() {
+        let progress = DatasetProgress {
+            rows_ingested: 10,
+            eta_seconds: Some(5),
+            throttle_reason: Some(ThrottleReason::RateLimited),
+            ..Default::default()
+        };
+        assert_eq!(progress.status(), DatasetProgressStatus::Throttled);
+    }
+
+    #[test]
+    fn rows_ingested_with_no_eta_is_complete() {
+        let progress = DatasetProgress {
+            rows_ingested: 10,
+            eta_seconds: None,
+            ..Default::default()
+        };
+        assert_eq!(progress.status(), DatasetProgressStatus::Complete);
+    }
+
+    #[test]
+    fn rows_ingested_with_an_eta_is_in_progress() {
+        let progress = DatasetProgress {
+            rows_ingested: 10,
+            eta_seconds: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(progress.status(), DatasetProgressStatus::InProgress);
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use super::Symbol;
+
+/// One registered symbol/interval/range combination, tracked by
+/// [`crate::dataset_registry::DatasetRegistry`] so a second registration
+/// covering the same coverage can be rejected or merged instead of
+/// silently creating an overlapping copy.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DatasetRecord {
+    pub id: uuid::Uuid,
+    pub symbols: Vec<Symbol>,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+}
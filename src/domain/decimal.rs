@@ -0,0 +1,5 @@
+/// Fixed-point decimal used for all prices and quantities. Plain `f64`
+/// accumulates rounding drift in fees and balances over long sessions, so
+/// every price/quantity value object in the domain goes through this type
+/// instead.
+pub use rust_decimal::Decimal;
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::{BorrowPosition, Decimal, ShortPosition};
+
+/// One point on a session's equity curve, captured periodically during
+/// replay (see `Session::equity_snapshot_interval_ms` and
+/// `crate::equity_curve::EquityCurve`) rather than only once at session end
+/// like [`SessionSummary`](super::SessionSummary). Carries the same
+/// balance-shaped fields `SessionSummary` does and for the same reason —
+/// there's no order/fill pipeline in this tree to snapshot an actual
+/// wallet from, see that type's doc comment.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EquitySnapshot {
+    pub simulated_time: i64,
+    pub open_borrows: Vec<BorrowPosition>,
+    pub open_shorts: Vec<ShortPosition>,
+    pub total_unrealized_pnl: Decimal,
+}
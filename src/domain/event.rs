@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use super::{BalanceEvent, BreakpointEvent, FillEvent, Kline, MarkPrice, OrderAmendmentEvent, OrderEvent, Trade};
+
+/// A single unit of replay output broadcast to websocket/SSE subscribers of
+/// a session. `seq` is monotonically increasing per session and is what
+/// lets a reconnecting client resume from `Last-Event-ID`. It also doubles
+/// as the tie-breaker for ReplayService's deterministic total order over
+/// `(event_time, symbol, seq)`: within a tick, symbols are always
+/// processed in lexicographic order (see
+/// `services::replay_service::ordered_symbols`), so two events sharing an
+/// `event_time` always get the same relative `seq` across runs.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketEvent {
+    pub seq: u64,
+    /// Simulated exchange emission time (Binance's `E` field). Subject to
+    /// a session's configured clock drift, unlike the kline/trade's own
+    /// dataset timestamps.
+    pub event_time: i64,
+    #[serde(flatten)]
+    pub payload: MarketEventPayload,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MarketEventPayload {
+    Kline(Kline),
+    Trade(Trade),
+    MarkPrice(MarkPrice),
+    /// Wire shape for a future order-lifecycle notification. Nothing in
+    /// this tree constructs one today: there's no `OrdersService` or
+    /// `SpotMatcher` to change state in the first place (see `orders`'
+    /// and `matching`'s module docs), so `SessionBroadcaster` never
+    /// publishes this variant. It exists so that once an order pipeline
+    /// lands, it has a ready-made event shape and `ws`'s opt-in
+    /// `include_orders` flag (see `http::ws::WsBatchParams`) to publish
+    /// through, without another breaking change to this enum.
+    Order(OrderEvent),
+    /// See [`MarketEventPayload::Order`] — same "ready but unused" status.
+    Fill(FillEvent),
+    /// See [`MarketEventPayload::Order`] — same "ready but unused" status.
+    Balance(BalanceEvent),
+    /// See [`MarketEventPayload::Order`] — same "ready but unused" status.
+    OrderAmendment(OrderAmendmentEvent),
+    /// Published when one of a session's
+    /// [`Session::breakpoints`](super::Session::breakpoints) fires — see
+    /// `services::replay_service::find_triggered_breakpoint`. Unlike
+    /// `Order`/`Fill`/`Balance` above, this one is actually constructed
+    /// today for `BreakpointCondition::PriceCrosses`.
+    Breakpoint(BreakpointEvent),
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Symbol;
+
+/// Lifecycle of a [`IngestionRecord`] tracked by `IngestionLedger`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestStatus {
+    Ingesting,
+    Completed,
+    Failed,
+    /// Was `Ingesting` in a snapshot restored at startup — the process
+    /// that owned it is gone, so it can't actually still be running. See
+    /// `ingestion_ledger`'s module doc for what resuming one requires.
+    Resumable,
+}
+
+/// What caused `services::dataset::spawn_backfill` to run this ingestion —
+/// the session-creation gap fill (`http::sessions::create_session`) or a
+/// startup resume of a job a prior process left `Resumable`
+/// (`services::dataset::resume_stale_ingestions`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionTrigger {
+    SessionCreate,
+    Resume,
+}
+
+/// One attempt at filling an [`IngestionRecord`]'s gap — a record starts
+/// with one `Ingesting` run and gains another each time it's resumed
+/// (`IngestionLedger::resume`), so a run that later succeeds doesn't erase
+/// why an earlier attempt failed.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IngestionRun {
+    pub started_at: i64,
+    /// `None` while this run is still `Ingesting`.
+    pub finished_at: Option<i64>,
+    pub status: IngestStatus,
+    pub rows_inserted: i64,
+    /// Set when `status` is [`IngestStatus::Failed`]. This tree's
+    /// synthetic backfill has no failure path today (see
+    /// `services::dataset::spawn_backfill_job`), so nothing populates this
+    /// yet — it's here for whenever a real upstream fetch can fail.
+    pub error: Option<String>,
+    pub triggered_by: IngestionTrigger,
+}
+
+/// One `services::dataset::spawn_backfill` job, tracked across process
+/// restarts so a job killed mid-flight doesn't silently stay `Ingesting`
+/// forever. See `ingestion_ledger::IngestionLedger`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IngestionRecord {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub symbols: Vec<Symbol>,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+    pub status: IngestStatus,
+    /// Every attempt at this ingestion, oldest first — see [`IngestionRun`].
+    /// Defaults to empty so a snapshot written before this field existed
+    /// still restores cleanly.
+    #[serde(default)]
+    pub runs: Vec<IngestionRun>,
+    /// Hash of the klines rows this ingestion wrote, set once the first run
+    /// completes successfully (see `services::dataset::content_hash`) so a
+    /// session's results can be traced back to the exact data version they
+    /// replayed against. `None` until then, and never recomputed by a later
+    /// resume — a resume only fills the same `[start, end)` gap again, so
+    /// its hash wouldn't differ.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
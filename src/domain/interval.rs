@@ -0,0 +1,73 @@
+/// Binance-style interval string (`"1m"`, `"15m"`, `"1h"`, `"1d"`, `"1w"`)
+/// in milliseconds, or `None` if it isn't a recognized `<amount><unit>`
+/// pair. Nothing else in this tree needs to know an interval's actual
+/// duration — it's otherwise just an opaque store partition key — so this
+/// only exists for `services::resample`'s divisibility check and bucket
+/// boundaries.
+pub fn interval_ms(interval: &str) -> Option<i64> {
+    let unit = interval.chars().last()?;
+    let amount: i64 = interval[..interval.len() - unit.len_utf8()].parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
+    let unit_ms = match unit {
+        's' => 1_000,
+        'm' => 60_000,
+        'h' => 3_600_000,
+        'd' => 86_400_000,
+        'w' => 604_800_000,
+        _ => return None,
+    };
+    amount.checked_mul(unit_ms)
+}
+
+/// The start of the `bucket_ms`-sized window `timestamp` falls in, shifted
+/// by `align_offset_ms` — e.g. daily (`bucket_ms = 86_400_000`) candles
+/// aligned to UTC+8 day boundaries use `align_offset_ms = 8 * 3_600_000`
+/// rather than the UTC midnight a bare `timestamp.rem_euclid(bucket_ms)`
+/// would anchor to. `0` reproduces the unshifted behavior every bucketing
+/// path (`services::resample`, `services::tape`) had before per-session
+/// alignment existed.
+pub fn aligned_bucket_open(timestamp: i64, bucket_ms: i64, align_offset_ms: i64) -> i64 {
+    let shifted = timestamp - align_offset_ms;
+    shifted - shifted.rem_euclid(bucket_ms) + align_offset_ms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_supported_unit() {
+        assert_eq!(interval_ms("1s"), Some(1_000));
+        assert_eq!(interval_ms("1m"), Some(60_000));
+        assert_eq!(interval_ms("15m"), Some(900_000));
+        assert_eq!(interval_ms("1h"), Some(3_600_000));
+        assert_eq!(interval_ms("1d"), Some(86_400_000));
+        assert_eq!(interval_ms("1w"), Some(604_800_000));
+    }
+
+    #[test]
+    fn rejects_unrecognized_units_and_amounts() {
+        assert_eq!(interval_ms("1y"), None);
+        assert_eq!(interval_ms("0m"), None);
+        assert_eq!(interval_ms("m"), None);
+        assert_eq!(interval_ms(""), None);
+    }
+
+    #[test]
+    fn zero_offset_matches_the_unaligned_bucket() {
+        assert_eq!(aligned_bucket_open(90_000, 86_400_000, 0), 0);
+        assert_eq!(aligned_bucket_open(3_600_000, 86_400_000, 0), 0);
+    }
+
+    #[test]
+    fn shifts_the_boundary_by_the_offset() {
+        let day = 86_400_000;
+        let utc8 = 8 * 3_600_000;
+        // 07:00 UTC is still within the UTC+8 day that opened at 16:00 UTC the day before.
+        assert_eq!(aligned_bucket_open(7 * 3_600_000, day, utc8), utc8 - day);
+        // 09:00 UTC is within the UTC+8 day that opens at 08:00 UTC.
+        assert_eq!(aligned_bucket_open(9 * 3_600_000, day, utc8), utc8);
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// A single OHLCV candle for a symbol/interval pair.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Kline {
+    pub symbol: Symbol,
+    pub interval: String,
+    pub open_time: i64,
+    pub close_time: i64,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    /// Sum of `price * quantity` across the trades the candle was built
+    /// from — Binance's quote asset volume. Zero for candles synthesized
+    /// without trade-level input (e.g. `services::replay_service`'s demo
+    /// stream), same as the base `volume` would be without a trade source.
+    #[serde(default)]
+    pub quote_volume: Decimal,
+    pub is_closed: bool,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Artificial delay applied to requests under `route_prefix` by
+/// [`crate::latency::LatencyInjector`], so a strategy's timeout/retry and
+/// concurrency handling can be exercised against something slower than
+/// this simulator's actual in-process response time.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LatencyProfile {
+    /// Path prefix this profile applies to, e.g. `"/api/v3/exchangeInfo"`.
+    pub route_prefix: String,
+    pub fixed_ms: u64,
+    /// Upper bound on top of `fixed_ms`; actual jitter is uniform in
+    /// `[0, jitter_ms]`.
+    pub jitter_ms: u64,
+}
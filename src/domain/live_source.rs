@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a session's simulated clock should pull klines/trades from,
+/// instead of always replaying the historical dataset a symbol was backfilled
+/// into. See `Session::live_source` for where a session opts in.
+///
+/// Only [`DatasetReplay`](LiveDataSource::DatasetReplay) is wired up —
+/// `ReplayService` has always behaved this way and this variant just names
+/// it. [`BinancePassthrough`](LiveDataSource::BinancePassthrough) is
+/// accepted and stored but inert: forwarding live Binance websocket
+/// klines/aggTrades needs a websocket *client* (this tree only has
+/// `axum`'s server-side `ws` feature — no `tokio-tungstenite` or
+/// equivalent in `Cargo.toml`, and this environment can't add one), so a
+/// session created with it still replays its backfilled dataset exactly
+/// like `DatasetReplay` until that dependency lands. Set here rather than
+/// left unimplementable so `POST /api/v1/sessions` at least round-trips
+/// the intent instead of silently ignoring it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum LiveDataSource {
+    /// Replay the session's backfilled historical dataset — the only
+    /// behavior this tree actually has today.
+    DatasetReplay,
+    /// Forward live Binance websocket klines/aggTrades while still
+    /// matching, tracking balances, and tracking orders locally — paper
+    /// trading against the real market. See this type's doc comment for
+    /// why this doesn't do anything yet.
+    BinancePassthrough,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use super::Decimal;
+
+/// Session-level margin parameters: how far a borrow can push a position
+/// past its balance, and the simple interest rate charged on it. Spot
+/// sessions have no balance/order pipeline in this tree, so enabling this
+/// only unlocks [`BorrowPosition`] bookkeeping via `AppState::margin_ledger`,
+/// not actual negative-balance order execution.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarginConfig {
+    pub max_leverage: Decimal,
+    /// Simple (non-compounding) interest rate charged per millisecond of
+    /// simulated time the borrow is outstanding.
+    pub interest_rate_per_ms: Decimal,
+}
+
+/// Outstanding borrow of one asset within a margin-enabled session.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BorrowPosition {
+    pub asset: String,
+    pub principal: Decimal,
+    pub borrowed_at: i64,
+}
+
+impl BorrowPosition {
+    /// Interest accrued between `borrowed_at` and `as_of`, given `config`.
+    pub fn interest(&self, as_of: i64, config: &MarginConfig) -> Decimal {
+        let elapsed = Decimal::from((as_of - self.borrowed_at).max(0));
+        self.principal * config.interest_rate_per_ms * elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn interest_accrues_linearly_with_elapsed_time() {
+        let position = BorrowPosition {
+            asset: "USDT".into(),
+            principal: dec!(1000.0),
+            borrowed_at: 0,
+        };
+        let config = MarginConfig {
+            max_leverage: dec!(3.0),
+            interest_rate_per_ms: dec!(0.00001),
+        };
+        assert_eq!(position.interest(1000, &config), dec!(10.00000));
+    }
+}
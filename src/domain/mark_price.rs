@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// How a session's mark price is derived, instead of always marking
+/// positions at the raw last trade price. See
+/// [`services::mark_price`](crate::services::mark_price) for the
+/// computation and `Session::mark_price` for where a session opts in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum MarkPriceConfig {
+    /// The close of the most recently emitted kline.
+    LastClose,
+    /// Volume-weighted average trade price over the trailing `window_ms`.
+    TradeVwap { window_ms: i64 },
+}
+
+/// A computed mark price for one symbol, published as a `mark_price`
+/// event alongside klines/trades (see
+/// [`MarketEventPayload::MarkPrice`](super::MarketEventPayload::MarkPrice))
+/// so a subscriber can track it the same way it tracks any other stream —
+/// this tree has no combined-stream subscription syntax like Binance's
+/// `<symbol>@markPrice`, so the symbol travels inside the payload instead
+/// of in a stream name.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarkPrice {
+    pub symbol: Symbol,
+    pub price: Decimal,
+}
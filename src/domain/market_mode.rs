@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// Which event types `ReplayService` publishes for a session's symbols each
+/// tick. Doesn't change what's written to `store::MarketStore`/`TradeStore`
+/// or tracked by `ticker::TickerTracker`/`services::analytics` — those stay
+/// kline-driven regardless, since nothing in this tree (mark price, the
+/// ticker, symbol analytics) has a trade-based equivalent to fall back to
+/// yet. This only controls which [`MarketEventPayload`](super::MarketEventPayload)
+/// variants actually reach subscribers, e.g. a strategy that wants to
+/// drive fills off `aggTrade` ticks without also paying for kline frames
+/// it isn't charting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketMode {
+    /// Klines only — every session's behavior before this type existed.
+    Klines,
+    /// Synthetic `aggTrade` ticks only, no kline frames. See
+    /// [`crate::matching`]'s module doc for why nothing yet consumes these
+    /// to actually drive fills — there's no order pipeline in this tree to
+    /// wire them into.
+    AggTrades,
+    /// Both: klines for charting, aggTrades for whatever downstream
+    /// matching eventually consumes. The two pipelines run independently
+    /// off the same per-tick price, so a subscriber sees both series
+    /// advance together rather than one derived from the other.
+    Hybrid,
+}
+
+impl MarketMode {
+    pub fn includes_klines(self) -> bool {
+        matches!(self, MarketMode::Klines | MarketMode::Hybrid)
+    }
+
+    pub fn includes_trades(self) -> bool {
+        matches!(self, MarketMode::AggTrades | MarketMode::Hybrid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn klines_mode_excludes_trades() {
+        assert!(MarketMode::Klines.includes_klines());
+        assert!(!MarketMode::Klines.includes_trades());
+    }
+
+    #[test]
+    fn hybrid_mode_includes_both() {
+        assert!(MarketMode::Hybrid.includes_klines());
+        assert!(MarketMode::Hybrid.includes_trades());
+    }
+}
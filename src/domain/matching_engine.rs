@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Which fill model a session wants, once a real order/matching pipeline
+/// exists to consult one — see `matching`'s module doc comment for why
+/// only [`LastPrice`](MatchingEngineKind::LastPrice) does anything today.
+/// Named now so a session can declare its intent ahead of the other two
+/// existing, rather than needing a breaking schema change once they do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchingEngineKind {
+    /// Fills at the last trade/kline-close price seen for the order's
+    /// symbol — the only model this tree implements.
+    LastPrice,
+    /// A simulated order book consulted for fill price/size instead of a
+    /// single last price. No order book exists in this tree yet — see
+    /// `services::depth_sequencing`'s module doc.
+    OrderBook,
+    /// Fills probabilistically (e.g. partial fills, slippage, rejected
+    /// orders) rather than deterministically against a price or book.
+    Probabilistic,
+}
@@ -0,0 +1,82 @@
+//! Core domain types shared across the replay engine, trading surface and
+//! control plane. Nothing in here knows about HTTP, DuckDB or websockets.
+
+mod analytics;
+mod availability;
+mod breakpoint;
+mod chaos;
+mod clock;
+mod commission;
+mod dataset_estimate;
+mod dataset_progress;
+mod dataset_record;
+mod decimal;
+mod equity_snapshot;
+mod event;
+mod ingestion_record;
+mod interval;
+mod kline;
+mod latency;
+mod live_source;
+mod margin;
+mod mark_price;
+mod market_mode;
+mod matching_engine;
+mod order_event;
+mod order_limits;
+mod order_rate;
+mod position;
+mod session;
+mod short;
+mod summary;
+mod symbol;
+mod symbol_filters;
+mod symbol_lifecycle;
+mod symbol_price;
+mod tape;
+mod tenancy;
+mod throttle;
+mod ticker;
+mod trade;
+mod webhook;
+mod ws_stats;
+
+pub use analytics::{SymbolAnalytics, VolumeBucket};
+pub use availability::{MarketAvailability, MissingSpan};
+pub use breakpoint::{BreakpointCondition, BreakpointEvent, CrossDirection};
+pub use chaos::{ChaosConfig, ChaosGenerator};
+pub use clock::{ClockDriftConfig, DriftGenerator};
+pub use commission::{CommissionConfig, SymbolFeeOverride};
+pub use dataset_estimate::DatasetEstimate;
+pub use dataset_progress::{DatasetProgress, DatasetProgressStatus};
+pub use dataset_record::DatasetRecord;
+pub use decimal::Decimal;
+pub use equity_snapshot::EquitySnapshot;
+pub use event::{MarketEvent, MarketEventPayload};
+pub use ingestion_record::{IngestStatus, IngestionRecord, IngestionRun, IngestionTrigger};
+pub use interval::{aligned_bucket_open, interval_ms};
+pub use kline::Kline;
+pub use latency::LatencyProfile;
+pub use live_source::LiveDataSource;
+pub use margin::{BorrowPosition, MarginConfig};
+pub use mark_price::{MarkPrice, MarkPriceConfig};
+pub use market_mode::MarketMode;
+pub use matching_engine::MatchingEngineKind;
+pub use order_event::{BalanceEvent, FillEvent, OrderAmendmentEvent, OrderEvent, OrderSide, OrderStatus};
+pub use order_limits::OrderLimitsConfig;
+pub use order_rate::OrderRateBucket;
+pub use position::Position;
+pub use session::{Session, SessionStatus};
+pub use short::ShortPosition;
+pub use summary::SessionSummary;
+pub use symbol::Symbol;
+pub use symbol_filters::SymbolFilters;
+pub use symbol_lifecycle::SymbolLifecycleEvent;
+pub use symbol_price::SymbolPrice;
+pub use tape::TapeBucket;
+pub use tenancy::{Namespace, TokenRole};
+pub use throttle::ThrottleReason;
+pub use ticker::{AvgPrice, Ticker24hr};
+pub use trade::Trade;
+pub use webhook::{DeliveryStatus, WebhookConfig, WebhookDelivery, WebhookEvent};
+pub use ws_stats::WsConnectionRecord;
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Decimal, Symbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected,
+    /// The status a resting order would move to once its symbol is
+    /// delisted (see `symbol_registry::SymbolLifecycleRegistry`), the way
+    /// Binance expires open orders on a delisted pair. Nothing constructs
+    /// this yet — see [`OrderEvent`]'s doc comment for why there's no open
+    /// order to expire in the first place.
+    Expired,
+}
+
+/// An order's lifecycle changing state, the shape Binance's `executionReport`
+/// user-data event would carry. Nothing in this tree constructs one yet —
+/// see [`MarketEventPayload::Order`](super::MarketEventPayload::Order)'s
+/// doc comment for why.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OrderEvent {
+    pub order_id: Uuid,
+    pub client_order_id: String,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    pub status: OrderStatus,
+    pub quantity: Decimal,
+    pub price: Decimal,
+}
+
+/// One fill against an order. See [`OrderEvent`]'s doc comment.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FillEvent {
+    pub order_id: Uuid,
+    pub symbol: Symbol,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub commission: Decimal,
+}
+
+/// A balance changing for one asset. See [`OrderEvent`]'s doc comment.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BalanceEvent {
+    pub asset: String,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+/// A resting order's quantity being reduced in place (Binance's order
+/// amendment), rather than canceled and replaced — the distinction that
+/// matters is `new_quantity` keeps the order's original price-time
+/// priority in the book. See [`OrderEvent`]'s doc comment for why nothing
+/// constructs this yet, and `orders::validate_quantity_amendment` for the
+/// rule a future handler would check before emitting one.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OrderAmendmentEvent {
+    pub order_id: Uuid,
+    pub symbol: Symbol,
+    pub previous_quantity: Decimal,
+    pub new_quantity: Decimal,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-session caps on order activity, modeled on Binance's own
+/// `-1015 TOO_MANY_ORDERS` rate limiting. This tree has no order/fill
+/// pipeline yet (see `orders`), so the config only lives on
+/// [`Session`](super::Session) for now — `orders::OrderCountTracker` is
+/// the piece a future order placement handler would check against before
+/// registering a new order.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OrderLimitsConfig {
+    pub max_open_orders_per_symbol: Option<u32>,
+    pub max_orders_per_day: Option<u32>,
+}
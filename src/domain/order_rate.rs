@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Orders placed/canceled/filled during one simulated hour, from
+/// [`crate::orders::OrderRateTracker`] — lets a caller sanity-check a bot
+/// isn't massively over-trading relative to what it expected before
+/// looking at PnL at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OrderRateBucket {
+    /// Simulated time divided by milliseconds-per-hour — see
+    /// [`crate::orders::hour_bucket`].
+    pub hour: i64,
+    pub placed: u64,
+    pub canceled: u64,
+    pub filled: u64,
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// A per-symbol net position for reporting, derived from whatever
+/// position-shaped bookkeeping a session actually has. There's no spot
+/// fill pipeline in this tree (see `orders` and `matching`'s module docs),
+/// so the only source today is [`ShortLedger`](crate::shorts::ShortLedger)
+/// — `quantity` is always negative, since a long position would need a buy
+/// fill to open one and nothing constructs those yet. Once an order
+/// pipeline lands, this is the shape it would fold buy fills into
+/// alongside the existing short bookkeeping, rather than a new one.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Position {
+    pub symbol: Symbol,
+    /// Negative for a short, positive for a long. Always negative today —
+    /// see the struct doc.
+    pub quantity: Decimal,
+    pub avg_entry_price: Decimal,
+    /// Marked at the session's current mark price, zero if none is
+    /// available yet for `symbol`.
+    pub unrealized_pnl: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn a_short_position_reports_a_negative_quantity() {
+        let position = Position {
+            symbol: Symbol::new("BTCUSDT"),
+            quantity: dec!(-2.0),
+            avg_entry_price: dec!(100.0),
+            unrealized_pnl: dec!(20.0),
+        };
+        assert!(position.quantity < Decimal::ZERO);
+    }
+}
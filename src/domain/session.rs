@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    BreakpointCondition, ChaosConfig, ClockDriftConfig, CommissionConfig, LiveDataSource, MarginConfig, MarkPriceConfig,
+    MarketMode, MatchingEngineKind, OrderLimitsConfig, Symbol,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    Created,
+    Running,
+    Paused,
+    Stopped,
+    Ended,
+}
+
+/// A deterministic replay envelope: the symbols/interval/speed bounds a run
+/// executes against. Sessions are the unit of lifecycle control
+/// (`start -> pause/resume -> stop`) and the scope streams/orders are keyed by.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Session {
+    pub id: Uuid,
+    /// The namespace this session belongs to. The HTTP surface enforces
+    /// that a request's token namespace matches before returning or
+    /// mutating a session — see `crate::http::tenancy`.
+    pub namespace_id: Uuid,
+    pub symbols: Vec<Symbol>,
+    /// Additional symbols streamed read-only alongside `symbols`, e.g. an
+    /// index pair a strategy wants to watch without trading it. There's no
+    /// order/matching pipeline in this tree to distinguish "tradable" from
+    /// "watch-only" against, so the only practical difference today is
+    /// that `ReplayService` includes these when publishing events but they
+    /// aren't implied to be part of the session's trading universe.
+    #[serde(default)]
+    pub watchlist: Vec<Symbol>,
+    pub interval: String,
+    pub speed: f64,
+    pub status: SessionStatus,
+    #[serde(default)]
+    pub clock_drift: Option<ClockDriftConfig>,
+    /// BNB-style fee discount, if the session opted in. Inert until a
+    /// fill/order pipeline lands to actually charge fees against it.
+    #[serde(default)]
+    pub commission: Option<CommissionConfig>,
+    /// Margin parameters, if the session opted into borrow tracking. Spot
+    /// sessions leave this `None`.
+    #[serde(default)]
+    pub margin: Option<MarginConfig>,
+    /// Independent of `margin`: lets a SELL exceed the base balance by
+    /// opening a tracked short instead of requiring full margin.
+    #[serde(default)]
+    pub allow_short: bool,
+    /// How the mark price published alongside klines/trades (and used to
+    /// mark unrealized PnL on open shorts, see
+    /// `AppState::generate_summary`) is derived. Defaults to
+    /// [`MarkPriceConfig::LastClose`] when unset, the same price an
+    /// unmarked session would already be showing.
+    #[serde(default)]
+    pub mark_price: Option<MarkPriceConfig>,
+    /// Fault injection for this session's websocket stream. Only applied
+    /// to the default, unbatched `/ws` path — see `http::ws` for why the
+    /// batched path doesn't chaos-test per frame.
+    #[serde(default)]
+    pub chaos: Option<ChaosConfig>,
+    /// The dataset `open_time` the replay loop starts advancing from.
+    /// Defaults to `0`, the same point every session started at before
+    /// this field existed.
+    #[serde(default)]
+    pub start_time: i64,
+    /// If set, klines in `[warmup_start, start_time)` are readable through
+    /// the session-scoped market endpoints for indicator initialization,
+    /// but the replay loop never emits or trades them — it still starts at
+    /// `start_time`. Must be earlier than `start_time`.
+    #[serde(default)]
+    pub warmup_start: Option<i64>,
+    /// The dataset `open_time` the replay loop has advanced to so far.
+    /// Session-scoped market data endpoints clamp queries to this so a
+    /// strategy can't read candles/trades the session hasn't replayed yet.
+    #[serde(default)]
+    pub current_time: i64,
+    /// Caps on order activity, if the session opted in. Inert until a
+    /// fill/order pipeline lands to actually check against it — see
+    /// `orders::OrderCountTracker`.
+    #[serde(default)]
+    pub order_limits: Option<OrderLimitsConfig>,
+    /// Links this session's replay clock to every other session sharing
+    /// the same id, so a cross-market strategy trading `BTCUSDT` in one
+    /// session and `ETHUSDT` in another sees the same simulated time on
+    /// both. Caller-chosen rather than its own resource with a create
+    /// endpoint — any `Uuid` the caller consistently reuses across the
+    /// sessions it wants coordinated works. See
+    /// `clock_group::ClockGroupRegistry`.
+    #[serde(default)]
+    pub clock_group: Option<Uuid>,
+    /// Where this session's klines/trades come from. `None` behaves like
+    /// [`LiveDataSource::DatasetReplay`], the same dataset-backed replay
+    /// every session has always done. See [`LiveDataSource`] for why
+    /// `BinancePassthrough` doesn't change anything yet.
+    #[serde(default)]
+    pub live_source: Option<LiveDataSource>,
+    /// Which fill model a future order pipeline should consult for this
+    /// session. `None` behaves like [`MatchingEngineKind::LastPrice`] — see
+    /// [`crate::matching`] for why it's the only one implemented.
+    #[serde(default)]
+    pub matching_engine: Option<MatchingEngineKind>,
+    /// A simulated `open_time` at which `ReplayService` should pause this
+    /// session, set via `http::sessions::pause_at` rather than at creation
+    /// time. Cleared the moment it fires — see
+    /// `services::replay_service::ReplayService::spawn`'s breakpoint check.
+    #[serde(default)]
+    pub pause_at: Option<i64>,
+    /// Conditions `ReplayService` checks every tick in addition to
+    /// `pause_at`, e.g. "pause when BTCUSDT crosses 105". The first one
+    /// that's true pauses the session, publishes a breakpoint event, and is
+    /// removed from this list. Set via `http::sessions::add_breakpoint`.
+    /// See [`BreakpointCondition`] for which conditions are actually
+    /// evaluated today.
+    #[serde(default)]
+    pub breakpoints: Vec<BreakpointCondition>,
+    /// Shifts candle/tape bucket boundaries for this session's
+    /// `services::resample` and `services::tape` calls away from UTC, in
+    /// milliseconds — e.g. `8 * 3_600_000` so daily candles close on
+    /// exchange-local UTC+8 day boundaries rather than UTC midnight.
+    /// Defaults to `0` (UTC-aligned), the same boundary every session used
+    /// before this field existed. See
+    /// [`aligned_bucket_open`](super::aligned_bucket_open).
+    #[serde(default)]
+    pub candle_align_offset_ms: i64,
+    /// How often, in simulated milliseconds, `ReplayService` appends a
+    /// point to this session's equity curve (see
+    /// `crate::equity_curve::EquityCurve`) — e.g. `5 * 60_000` for every 5
+    /// simulated minutes. `None` (the default) disables periodic
+    /// snapshotting; the only equity-shaped data a session reports without
+    /// it is the single end-of-session [`SessionSummary`].
+    #[serde(default)]
+    pub equity_snapshot_interval_ms: Option<i64>,
+    /// A simulated `open_time` this session's replay loop should jump
+    /// forward to on its next tick, set via `http::sessions::seek_session`
+    /// rather than at creation time. Cleared the moment it's applied. Only
+    /// ever set to a value `>= current_time` — see
+    /// `AppState::seek` for why rewinding isn't accepted: this tree has no
+    /// order-book/matcher journal (see [`crate::matching`]) to restore
+    /// state from on a seek backward, so only the direction that needs no
+    /// restoration is supported.
+    #[serde(default)]
+    pub seek_to: Option<i64>,
+    /// Whether a future order pipeline should sequence a filled order's
+    /// `executionReport NEW` strictly before its `TRADE` event (Binance's
+    /// own ordering) instead of stamping both with the same simulated
+    /// time. See `orders::sequence_ack_and_fill`. Inert until that
+    /// pipeline exists — same status as `matching_engine`/`order_limits`.
+    #[serde(default)]
+    pub ack_before_trade: bool,
+    /// Which event types `ReplayService` publishes for this session. `None`
+    /// falls back to [`crate::config::Config::default_market_mode`]. See
+    /// [`MarketMode`].
+    #[serde(default)]
+    pub market_mode: Option<MarketMode>,
+    /// A caller-chosen, free-form label, e.g. "BTC mean-reversion v3". Not
+    /// unique and not validated — purely organizational, the same as
+    /// `tags`.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Free-form labels for organizing sessions, e.g. `["backtest",
+    /// "strategy-a"]`. Not validated against any fixed vocabulary; filter
+    /// with `GET /api/v1/sessions?tag=...` (`http::sessions::list_sessions`).
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether a future order pipeline should drive `matching_engine`
+    /// against this session's replay at all. Defaults to `false` — a
+    /// dry, market-data-only replay, the only kind this tree has ever
+    /// actually run (see `crate::matching`'s module doc) — so existing
+    /// sessions stay dry until a caller opts in via `PATCH
+    /// /api/v1/sessions/:id/matching`
+    /// (`http::sessions::patch_matching_config`).
+    #[serde(default)]
+    pub matching_enabled: bool,
+}
+
+impl Session {
+    /// The earliest `open_time` session-scoped market endpoints will serve:
+    /// `warmup_start` if set, otherwise `start_time` itself.
+    pub fn readable_from(&self) -> i64 {
+        self.warmup_start.unwrap_or(self.start_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(start_time: i64, warmup_start: Option<i64>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            namespace_id: Uuid::new_v4(),
+            symbols: vec![Symbol::new("BTCUSDT")],
+            watchlist: Vec::new(),
+            interval: "1m".into(),
+            speed: 1.0,
+            status: SessionStatus::Created,
+            clock_drift: None,
+            commission: None,
+            margin: None,
+            allow_short: false,
+            chaos: None,
+            mark_price: None,
+            start_time,
+            warmup_start,
+            current_time: 0,
+            order_limits: None,
+            clock_group: None,
+            live_source: None,
+            matching_engine: None,
+            pause_at: None,
+            breakpoints: Vec::new(),
+            candle_align_offset_ms: 0,
+            equity_snapshot_interval_ms: None,
+            seek_to: None,
+            ack_before_trade: false,
+            market_mode: None,
+            name: None,
+            tags: Vec::new(),
+            matching_enabled: false,
+        }
+    }
+
+    #[test]
+    fn readable_from_falls_back_to_start_time_without_warmup() {
+        assert_eq!(session(60_000, None).readable_from(), 60_000);
+    }
+
+    #[test]
+    fn readable_from_uses_warmup_start_when_set() {
+        assert_eq!(session(60_000, Some(0)).readable_from(), 0);
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// An open short position opened via synthetic borrow: a SELL that exceeded
+/// the session's base balance. This tree has no order pipeline to place
+/// that SELL against, so `allow_short` on [`Session`](super::Session) and
+/// this type only capture the bookkeeping shape a future order handler
+/// would drive through `ShortLedger`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ShortPosition {
+    pub symbol: Symbol,
+    pub quantity: Decimal,
+    pub avg_entry_price: Decimal,
+}
+
+impl ShortPosition {
+    /// PnL from buying back `quantity` at `price`: positive when `price` is
+    /// below the average entry price, since the position profits as the
+    /// asset falls.
+    pub fn realized_pnl(&self, quantity: Decimal, price: Decimal) -> Decimal {
+        (self.avg_entry_price - price) * quantity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn profits_when_buyback_price_is_lower() {
+        let position = ShortPosition {
+            symbol: Symbol::new("BTCUSDT"),
+            quantity: dec!(2.0),
+            avg_entry_price: dec!(100.0),
+        };
+        assert_eq!(position.realized_pnl(dec!(2.0), dec!(90.0)), dec!(20.0));
+    }
+
+    #[test]
+    fn loses_when_buyback_price_is_higher() {
+        let position = ShortPosition {
+            symbol: Symbol::new("BTCUSDT"),
+            quantity: dec!(2.0),
+            avg_entry_price: dec!(100.0),
+        };
+        assert_eq!(position.realized_pnl(dec!(2.0), dec!(110.0)), dec!(-20.0));
+    }
+}
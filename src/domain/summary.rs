@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{BorrowPosition, Decimal, OrderRateBucket, ShortPosition};
+
+/// Generated once, when a session transitions to
+/// [`SessionStatus::Ended`](super::SessionStatus::Ended), and persisted for
+/// later retrieval rather than recomputed per request.
+///
+/// There's no order/fill pipeline anywhere in this tree (see
+/// `commission`, `margin`, `shorts`), so `total_fees` is always zero and
+/// `open_borrows`/`open_shorts` stand in for the "final balances" a real
+/// exchange statement would show — they're the only balance-shaped state
+/// this simulator actually tracks.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SessionSummary {
+    pub session_id: Uuid,
+    /// The session's simulated clock at the moment the summary was
+    /// generated, not a wall-clock timestamp — consistent with every other
+    /// time field a session reports.
+    pub generated_at: i64,
+    pub event_count: u64,
+    pub trade_count: u64,
+    pub total_volume: Decimal,
+    pub total_fees: Decimal,
+    pub open_borrows: Vec<BorrowPosition>,
+    pub open_shorts: Vec<ShortPosition>,
+    /// Sum of every open short's PnL marked at the session's mark price
+    /// (see `Session::mark_price` and `services::mark_price`) rather than
+    /// its realized buyback price, since none of `open_shorts` has been
+    /// bought back yet. Zero if a short has no mark price available (e.g.
+    /// no klines/trades yet for its symbol).
+    pub total_unrealized_pnl: Decimal,
+    /// Orders placed/canceled/filled per simulated hour, from
+    /// [`crate::orders::OrderRateTracker`] — lets a caller sanity-check a
+    /// bot isn't massively over-trading relative to what it expected
+    /// before looking at PnL at all.
+    pub order_rate: Vec<OrderRateBucket>,
+}
@@ -0,0 +1,37 @@
+use super::Decimal;
+
+/// Per-symbol precision/step-size rules, mirroring Binance's `PRICE_FILTER`
+/// and `LOT_SIZE` exchange filters, plus the base/quote asset pair itself.
+/// `base_asset`/`quote_asset` are always set explicitly (at seeding time or
+/// via `http::admin::set_symbol_metadata`) rather than guessed by splitting
+/// `symbol` against a hardcoded list of known quote assets — that would
+/// mis-parse pairs like `DOGEBTC` (quote is `BTC`, not `BTCUSDT`'s usual
+/// suffix) or any quote this tree hasn't hardcoded yet (`FDUSD`, `TRY`, ...).
+#[derive(Debug, Clone)]
+pub struct SymbolFilters {
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub tick_size: Decimal,
+    pub step_size: Decimal,
+}
+
+impl SymbolFilters {
+    pub fn price_precision(&self) -> u32 {
+        self.tick_size.scale()
+    }
+
+    pub fn qty_precision(&self) -> u32 {
+        self.step_size.scale()
+    }
+}
+
+impl Default for SymbolFilters {
+    fn default() -> Self {
+        Self {
+            base_asset: String::new(),
+            quote_asset: String::new(),
+            tick_size: Decimal::new(1, 8),
+            step_size: Decimal::new(1, 8),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use super::Symbol;
+
+/// One entry in a symbol's lifecycle, tracked globally by
+/// [`crate::symbol_registry::SymbolLifecycleRegistry`]. `Renamed` lets a
+/// query spanning the rename stitch the old and new symbol's stored data
+/// together under the current name; `Delisted` is the simulated `open_time`
+/// after which `services::replay_service::ReplayService` stops producing
+/// data for the symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SymbolLifecycleEvent {
+    Renamed { from: Symbol, to: Symbol, effective_at: i64 },
+    Delisted { symbol: Symbol, effective_at: i64 },
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// One symbol's latest observed prices, for `http::debug::get_session_prices`
+/// — a single place to check "what does the simulator think this symbol's
+/// price is right now" instead of cross-referencing klines, trades, and mark
+/// price across three separate endpoints when an order isn't filling.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SymbolPrice {
+    pub symbol: Symbol,
+    /// Close of the most recent kline at the session's interval, `None` if
+    /// none has landed yet.
+    pub last_kline_close: Option<Decimal>,
+    /// `open_time` of the kline `last_kline_close` came from.
+    pub last_kline_time: Option<i64>,
+    /// Price of the most recent trade, `None` if none has landed yet.
+    pub last_trade_price: Option<Decimal>,
+    /// `trade_time` of the trade `last_trade_price` came from.
+    pub last_trade_time: Option<i64>,
+    /// The session's configured mark price as of `Session::current_time`
+    /// (see `services::mark_price::compute`), `None` if there isn't enough
+    /// data yet to mark against. There's no simulated order book in this
+    /// tree, so this is the closest thing to a bid/ask this endpoint can
+    /// report.
+    pub mark_price: Option<Decimal>,
+}
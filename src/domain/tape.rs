@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// One fixed-size time bucket of aggregated trade prints. See
+/// `services::tape` and `http::sessions::get_session_tape`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TapeBucket {
+    pub symbol: Symbol,
+    pub bucket_open: i64,
+    pub count: u64,
+    pub volume: Decimal,
+    pub vwap: Decimal,
+}
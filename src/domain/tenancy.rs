@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A tenant boundary: sessions and webhooks are created under a namespace
+/// and only visible to API tokens issued for that same namespace. See
+/// `crate::tenancy` for where namespaces and tokens actually live.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Namespace {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// What a token scoped to a namespace is allowed to do. Variants are
+/// ordered least to most privileged and each role includes everything the
+/// ones below it can do — `Operator` can do anything `Viewer` can, `Admin`
+/// anything `Operator` can — so `role >= minimum` is the whole check, done
+/// by [`crate::http::tenancy::require_role`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenRole {
+    /// Read-only: GET endpoints and stream subscriptions (`/ws`, `/stream`).
+    Viewer,
+    /// Everything a `Viewer` can do, plus creating/starting sessions and
+    /// registering webhooks.
+    Operator,
+    /// Everything an `Operator` can do, plus instance-wide control-plane
+    /// mutations (maintenance mode, log level, dataset deletion via
+    /// `DELETE /api/v1/admin/datasets/{id}`).
+    Admin,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Why a dataset backfill's next fetch is being delayed. See
+/// `services::rate_limit::RateLimiter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleReason {
+    /// Used weight has crept past the soft threshold; pacing down before
+    /// an actual 429 is tripped.
+    Weight,
+    /// A 429 ("too many requests") was observed; backing off for its
+    /// `Retry-After`.
+    RateLimited,
+    /// A 418 ("IP auto-banned") was observed — Binance's harshest
+    /// response, issued after ignoring repeated 429s.
+    Banned,
+}
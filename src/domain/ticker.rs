@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// Binance-style 24hr rolling ticker stats for one symbol, maintained
+/// incrementally by `crate::ticker::TickerTracker` as klines are written to
+/// the global `store::MarketStore` rather than recomputed from history on
+/// every request.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Ticker24hr {
+    pub symbol: Symbol,
+    pub price_change: Decimal,
+    pub price_change_percent: Decimal,
+    pub weighted_avg_price: Decimal,
+    pub open_price: Decimal,
+    pub high_price: Decimal,
+    pub low_price: Decimal,
+    pub last_price: Decimal,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+    pub open_time: i64,
+    pub close_time: i64,
+    /// Number of klines the window was computed over, not a trade count —
+    /// this tree has no per-trade ticker feed, only candles.
+    pub count: i64,
+}
+
+/// `GET /api/v3/avgPrice` response: Binance's `{mins, price}` shape.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AvgPrice {
+    pub mins: i64,
+    pub price: Decimal,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Decimal, Symbol};
+
+/// A single aggregated trade print sourced from the dataset.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Trade {
+    pub symbol: Symbol,
+    pub agg_trade_id: i64,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub trade_time: i64,
+    pub is_buyer_maker: bool,
+}
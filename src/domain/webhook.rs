@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Events a webhook can subscribe to. `OrderFill` and `DatasetReady` are
+/// defined ahead of the order pipeline and on-demand dataset fetching
+/// landing in this tree, so neither is ever emitted yet — `SessionEnded`
+/// and `SummaryReady` are the ones actually fired today, both from
+/// `AppState::set_status` when a session ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    OrderFill,
+    SessionEnded,
+    DatasetReady,
+    SummaryReady,
+}
+
+/// A session's webhook subscription: where to POST, a shared secret sent as
+/// a header so the receiver can verify the sender, and which events it
+/// wants delivered.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+/// One attempted (or exhausted) delivery of an event to a webhook, kept so
+/// `GET /api/v1/webhooks/:id/deliveries` gives CI pipelines something to
+/// audit instead of having to trust a fire-and-forget POST.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub event: WebhookEvent,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
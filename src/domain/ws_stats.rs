@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// One websocket connection's lifecycle against a session's stream, in
+/// simulated time (see `Session::current_time`). `disconnected_at` stays
+/// `None` while the socket is still open. `lag_drops` counts how many
+/// broadcast messages this connection missed because it fell behind the
+/// session's publish rate (`tokio::sync::broadcast::error::RecvError::Lagged`).
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WsConnectionRecord {
+    pub connected_at: i64,
+    pub disconnected_at: Option<i64>,
+    pub messages_sent: u64,
+    pub lag_drops: u64,
+}
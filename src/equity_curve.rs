@@ -0,0 +1,59 @@
+//! Per-session history of periodic balance snapshots taken during replay
+//! (see `Session::equity_snapshot_interval_ms` and
+//! `services::replay_service`), so `http::sessions::get_equity_curve` can
+//! serve fine-grained equity-curve data without recomputing it from fills
+//! — there's no fill pipeline to recompute from anyway (see
+//! `domain::SessionSummary`'s doc comment), and no DuckDB in this tree
+//! either (see `store`'s module doc), so this keeps the history in memory
+//! the same way every other per-session tracker
+//! (`analytics::AnalyticsTracker`, `ws_stats::WsStatsTracker`) already does.
+
+use std::sync::Mutex;
+
+use crate::domain::EquitySnapshot;
+
+#[derive(Default)]
+pub struct EquityCurve {
+    snapshots: Mutex<Vec<EquitySnapshot>>,
+}
+
+impl EquityCurve {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, snapshot: EquitySnapshot) {
+        self.snapshots.lock().unwrap().push(snapshot);
+    }
+
+    pub fn all(&self) -> Vec<EquitySnapshot> {
+        self.snapshots.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn snapshot(simulated_time: i64) -> EquitySnapshot {
+        EquitySnapshot {
+            simulated_time,
+            open_borrows: Vec::new(),
+            open_shorts: Vec::new(),
+            total_unrealized_pnl: dec!(0),
+        }
+    }
+
+    #[test]
+    fn snapshots_are_kept_in_recording_order() {
+        let curve = EquityCurve::new();
+        curve.record(snapshot(0));
+        curve.record(snapshot(300_000));
+        let all = curve.all();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].simulated_time, 0);
+        assert_eq!(all[1].simulated_time, 300_000);
+    }
+}
@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde_json::json;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("session {0} not found")]
+    SessionNotFound(uuid::Uuid),
+    #[error("webhook {0} not found")]
+    WebhookNotFound(uuid::Uuid),
+    #[error("namespace {0} not found")]
+    NamespaceNotFound(uuid::Uuid),
+    #[error("missing or invalid api token")]
+    Unauthorized,
+    #[error("token role does not permit this operation")]
+    Forbidden,
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("dataset already registered as {0}")]
+    DatasetConflict(uuid::Uuid),
+    #[error("ingestion record {0} not found")]
+    IngestionRecordNotFound(uuid::Uuid),
+    #[error("dataset record {0} not found")]
+    DatasetRecordNotFound(uuid::Uuid),
+    #[error("dataset is depended on by {} active session(s); pass force=true to stop them and delete anyway", .0.len())]
+    DatasetInUse(Vec<uuid::Uuid>),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ApiError::SessionNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::WebhookNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::NamespaceNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::DatasetConflict(_) => StatusCode::CONFLICT,
+            ApiError::IngestionRecordNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::DatasetRecordNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::DatasetInUse(_) => StatusCode::CONFLICT,
+        };
+        let mut body = json!({
+            "error": self.to_string(),
+            "request_id": crate::http::request_id::current(),
+        });
+        if let ApiError::DatasetConflict(existing_id) = &self {
+            body["dataset_id"] = json!(existing_id);
+        }
+        if let ApiError::DatasetInUse(session_ids) = &self {
+            body["session_ids"] = json!(session_ids);
+        }
+        (status, axum::Json(body)).into_response()
+    }
+}
@@ -0,0 +1,66 @@
+//! Internal pub/sub that decouples producers (`ReplayService` today; a
+//! future order/matching pipeline tomorrow) from needing a direct call
+//! site for every consumer. A producer publishes one [`BusEvent`]; any
+//! number of consumers subscribe independently via [`SessionEventBus::subscribe`]
+//! instead of the producer growing another `if let Ok(consumer) = ...`
+//! branch every time a new one is added.
+//!
+//! [`SessionBroadcaster`] is wired up as the first (and so far only)
+//! subscriber — see `state::AppState::insert_session`'s forwarding task —
+//! so the ws/SSE/webhook surfaces that already depend on it keep working
+//! unchanged. `ReplayService` no longer calls `SessionBroadcaster`
+//! directly; it publishes onto this bus instead.
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::domain::{MarketEvent, SessionStatus};
+
+/// Matches [`crate::broadcaster::RESUME_BUFFER`] in order of magnitude;
+/// this channel only needs to outrun its subscribers' poll latency, not
+/// serve as a resume buffer itself (that's still `SessionBroadcaster`'s
+/// job for `MarketEvent`s specifically).
+const BUS_CAPACITY: usize = 1024;
+
+/// One unit published on a session's [`SessionEventBus`]. Named after the
+/// three producers/consumers this decouples, even though only `Market`
+/// and `Status` have a producer today — `Order` is ready for the order
+/// pipeline described in `orders`' module doc the moment it exists.
+#[derive(Debug, Clone)]
+pub enum BusEvent {
+    Market(MarketEvent),
+    Status(SessionStatus),
+    Order(crate::domain::OrderEvent),
+}
+
+/// Per-session typed event bus. One instance per session, created
+/// alongside its [`SessionBroadcaster`](crate::broadcaster::SessionBroadcaster)
+/// in `AppState::insert_session`.
+pub struct SessionEventBus {
+    session_id: Uuid,
+    tx: broadcast::Sender<BusEvent>,
+}
+
+impl SessionEventBus {
+    pub fn new(session_id: Uuid) -> Self {
+        let (tx, _rx) = broadcast::channel(BUS_CAPACITY);
+        Self { session_id, tx }
+    }
+
+    pub fn session_id(&self) -> Uuid {
+        self.session_id
+    }
+
+    /// Fans `event` out to current subscribers. Silently dropped if no one
+    /// is subscribed — the same "no one asked, so there's nothing to
+    /// forward" behavior `tokio::sync::broadcast` already has, and matches
+    /// how `SessionBroadcaster::publish` behaves when a session has no
+    /// live ws/SSE consumers.
+    pub fn publish(&self, event: BusEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<BusEvent> {
+        self.tx.subscribe()
+    }
+}
@@ -0,0 +1,327 @@
+//! Operator-only control-plane endpoints, distinct from the session and
+//! Binance-compatible surfaces.
+
+use axum::body::Body;
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::domain::{DatasetRecord, SessionStatus, Symbol, SymbolLifecycleEvent, TokenRole};
+use crate::error::ApiError;
+use crate::http::tenancy;
+use crate::services::backup::{self, BackupSummary};
+use crate::services::bundle::{self, ImportSummary};
+use crate::services::dataset;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetMaintenanceRequest {
+    pub enabled: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct MaintenanceStatus {
+    pub enabled: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Candle-cache hit/miss counters, exposed for debugging hot-range cache
+/// behavior without wiring up a metrics scraper.
+#[utoipa::path(get, path = "/api/v1/admin/cache-stats", tag = "admin",
+    responses((status = 200, body = CacheStats)))]
+pub async fn cache_stats(State(state): State<AppState>) -> Json<CacheStats> {
+    Json(CacheStats {
+        hits: state.market_cache_hits(),
+        misses: state.market_cache_misses(),
+    })
+}
+
+/// The effective process configuration (layered file + env), for operators
+/// to confirm what actually took effect without shelling into the host.
+#[utoipa::path(get, path = "/api/v1/config", tag = "admin",
+    responses((status = 200, body = Config)))]
+pub async fn get_config(State(state): State<AppState>) -> Json<Config> {
+    Json(state.config().sanitized())
+}
+
+/// Flips the global maintenance flag: while enabled, order placement is
+/// rejected exchange-wide (Binance `-1016`/`-2011`-style) and session
+/// streams pause instead of advancing, so bots' degraded-mode handling can
+/// be exercised. Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/maintenance", tag = "admin",
+    request_body = SetMaintenanceRequest, responses((status = 200, body = MaintenanceStatus)))]
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Json(req): Json<SetMaintenanceRequest>,
+) -> Result<Json<MaintenanceStatus>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    state.set_maintenance(req.enabled);
+    Ok(Json(MaintenanceStatus {
+        enabled: req.enabled,
+    }))
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkSessionResult {
+    pub session_id: Uuid,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkSessionActionResponse {
+    pub results: Vec<BulkSessionResult>,
+}
+
+/// Pauses every `Running` session's clock and replay task in one call, so
+/// an operator can quiesce the simulator (e.g. before backup or DB
+/// maintenance) instead of pausing sessions one at a time. A paused
+/// session's `ReplayService` task idles in place rather than exiting, so
+/// `resume_all` can pick it back up. Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/pause-all", tag = "admin",
+    responses((status = 200, body = BulkSessionActionResponse)))]
+pub async fn pause_all(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+) -> Result<Json<BulkSessionActionResponse>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    let results = state
+        .sessions_with_status(SessionStatus::Running)
+        .into_iter()
+        .map(|session| apply_status(&state, session.id, SessionStatus::Paused))
+        .collect();
+    Ok(Json(BulkSessionActionResponse { results }))
+}
+
+/// The inverse of [`pause_all`]: resumes every `Paused` session. Requires
+/// an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/resume-all", tag = "admin",
+    responses((status = 200, body = BulkSessionActionResponse)))]
+pub async fn resume_all(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+) -> Result<Json<BulkSessionActionResponse>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    let results = state
+        .sessions_with_status(SessionStatus::Paused)
+        .into_iter()
+        .map(|session| apply_status(&state, session.id, SessionStatus::Running))
+        .collect();
+    Ok(Json(BulkSessionActionResponse { results }))
+}
+
+fn apply_status(state: &AppState, id: Uuid, status: SessionStatus) -> BulkSessionResult {
+    match state.set_status(id, status) {
+        Ok(()) => BulkSessionResult { session_id: id, success: true, error: None },
+        Err(err) => BulkSessionResult { session_id: id, success: false, error: Some(err.to_string()) },
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BackupRequest {
+    pub path: String,
+}
+
+/// Writes every kline and trade to `path` as a single JSON snapshot,
+/// pausing every running session for the duration so the snapshot is
+/// consistent. See [`backup::backup`]. Restored automatically at startup
+/// from `Config::dataset_path`, if set. Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/backup", tag = "admin",
+    request_body = BackupRequest, responses((status = 200, body = BackupSummary)))]
+pub async fn backup_dataset(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Json(req): Json<BackupRequest>,
+) -> Result<Json<BackupSummary>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    Ok(Json(backup::backup(&state, &req.path)?))
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SetSymbolMetadataRequest {
+    pub base_asset: String,
+    pub quote_asset: String,
+}
+
+/// Overrides `symbol`'s base/quote asset (see `domain::SymbolFilters`),
+/// for pairs the seeded defaults don't cover or parse correctly from the
+/// symbol string alone (e.g. `DOGEBTC`'s quote is `BTC`, not a suffix this
+/// tree would otherwise guess). Leaves tick/step size untouched if the
+/// symbol is already registered, otherwise registers it with the default
+/// precision. Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/symbols/{symbol}/metadata", tag = "admin",
+    params(("symbol" = String, Path)),
+    request_body = SetSymbolMetadataRequest, responses((status = 200, body = SetSymbolMetadataRequest)))]
+pub async fn set_symbol_metadata(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Path(symbol): Path<String>,
+    Json(req): Json<SetSymbolMetadataRequest>,
+) -> Result<Json<SetSymbolMetadataRequest>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    let symbol = Symbol::new(symbol);
+    let mut filters = state.symbol_filters(&symbol).unwrap_or_default();
+    filters.base_asset = req.base_asset.clone();
+    filters.quote_asset = req.quote_asset.clone();
+    state.set_symbol_filters(symbol, filters);
+    Ok(Json(req))
+}
+
+/// Folds every pair of registered datasets (see
+/// [`crate::dataset_registry::DatasetRegistry`]) that share a symbol, an
+/// interval, and an overlapping range into one record spanning their
+/// union, obsoleting the ones folded in. Returns the registry's state
+/// after merging. Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/datasets/merge", tag = "admin",
+    responses((status = 200, body = Vec<DatasetRecord>)))]
+pub async fn merge_datasets(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+) -> Result<Json<Vec<DatasetRecord>>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    Ok(Json(state.dataset_registry().merge_overlaps()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteDatasetQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DeleteDatasetResponse {
+    pub deleted: DatasetRecord,
+    /// Sessions that were reading this dataset's symbols/interval and got
+    /// force-stopped (`SessionStatus::Stopped`, the same terminal status
+    /// `services::reaper` uses) to let the deletion through. Empty unless
+    /// `force=true` was actually needed.
+    pub stopped_sessions: Vec<Uuid>,
+}
+
+/// Removes a [`DatasetRecord`] from
+/// [`crate::dataset_registry::DatasetRegistry`] — bookkeeping only; the
+/// klines/trades it described staying in `store::MarketStore`/`TradeStore`
+/// are left untouched, the same way registering one never wrote any rows
+/// either. Without `force`, fails with 409 (`session_ids` in the body)
+/// if any active session still reads the dataset's symbols at its
+/// interval (see [`dataset::dependent_sessions`]); with `force=true`,
+/// those sessions are stopped first. Requires an `Admin` token.
+#[utoipa::path(delete, path = "/api/v1/admin/datasets/{id}", tag = "admin",
+    params(("id" = Uuid, Path), ("force" = Option<bool>, Query)),
+    responses(
+        (status = 200, body = DeleteDatasetResponse),
+        (status = 409, description = "dependent active sessions exist and force wasn't set")
+    ))]
+pub async fn delete_dataset(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DeleteDatasetQuery>,
+) -> Result<Json<DeleteDatasetResponse>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    let record = state.dataset_registry().get(id).ok_or(ApiError::DatasetRecordNotFound(id))?;
+    let dependents = dataset::dependent_sessions(&state, &record);
+
+    if !dependents.is_empty() && !query.force {
+        return Err(ApiError::DatasetInUse(dependents.iter().map(|session| session.id).collect()));
+    }
+
+    let mut stopped_sessions = Vec::new();
+    for session in &dependents {
+        state.set_status(session.id, SessionStatus::Stopped)?;
+        stopped_sessions.push(session.id);
+    }
+
+    let deleted = state.dataset_registry().remove(id).ok_or(ApiError::DatasetRecordNotFound(id))?;
+    Ok(Json(DeleteDatasetResponse { deleted, stopped_sessions }))
+}
+
+/// Registers a rename or delisting in
+/// [`crate::symbol_registry::SymbolLifecycleRegistry`]. A `Renamed` event
+/// is what lets `services::symbol_history::klines_page` stitch the old
+/// symbol's stored klines in under the new name for a query spanning the
+/// rename; a `Delisted` event is what `ReplayService` checks each tick to
+/// stop synthesizing data for the symbol. Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/admin/symbols/lifecycle", tag = "admin",
+    request_body = SymbolLifecycleEvent, responses((status = 200, body = SymbolLifecycleEvent)))]
+pub async fn register_symbol_lifecycle_event(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Json(event): Json<SymbolLifecycleEvent>,
+) -> Result<Json<SymbolLifecycleEvent>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    state.symbol_lifecycle().register(event.clone());
+    Ok(Json(event))
+}
+
+/// Every rename/delisting event registered so far, in registration order.
+#[utoipa::path(get, path = "/api/v1/admin/symbols/lifecycle", tag = "admin",
+    responses((status = 200, body = Vec<SymbolLifecycleEvent>)))]
+pub async fn list_symbol_lifecycle_events(State(state): State<AppState>) -> Json<Vec<SymbolLifecycleEvent>> {
+    Json(state.symbol_lifecycle().all())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportDatasetQuery {
+    /// Comma-separated symbols, e.g. `"BTCUSDT,ETHUSDT"`.
+    pub symbols: String,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Exports `symbols`' klines/trades over `[start, end)` at `interval` as a
+/// single gzip-compressed bundle (`application/gzip`), for handing a
+/// prepared dataset to another simulator instance without it having to
+/// re-fetch from Binance. See [`bundle::export`]. Requires an `Admin`
+/// token.
+#[utoipa::path(get, path = "/api/v1/admin/datasets/export", tag = "admin",
+    params(
+        ("symbols" = String, Query, description = "Comma-separated symbols"),
+        ("interval" = String, Query),
+        ("start" = i64, Query),
+        ("end" = i64, Query)
+    ),
+    responses((status = 200, body = Vec<u8>)))]
+pub async fn export_dataset(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Query(query): Query<ExportDatasetQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    let symbols: Vec<Symbol> = query.symbols.split(',').map(str::trim).filter(|s| !s.is_empty()).map(Symbol::new).collect();
+    if symbols.is_empty() {
+        return Err(ApiError::BadRequest("symbols must not be empty".into()));
+    }
+    let compressed = bundle::export(&state, &symbols, &query.interval, query.start, query.end)?;
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/gzip")
+        .body(Body::from(compressed))
+        .unwrap())
+}
+
+/// Imports a bundle written by [`export_dataset`] (or [`bundle::export`])
+/// into this instance's stores and registers its coverage in
+/// [`crate::dataset_registry::DatasetRegistry`]. Requires an `Admin`
+/// token.
+#[utoipa::path(post, path = "/api/v1/admin/datasets/import", tag = "admin",
+    request_body(content = String, content_type = "application/gzip"),
+    responses((status = 200, body = ImportSummary)))]
+pub async fn import_dataset(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    body: axum::body::Bytes,
+) -> Result<Json<ImportSummary>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    Ok(Json(bundle::import(&state, &body)?))
+}
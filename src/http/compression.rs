@@ -0,0 +1,74 @@
+//! Gzip-compresses REST response bodies when the client negotiates
+//! `Accept-Encoding: gzip` (see `Config::enable_compression`). Installed as
+//! an optional outer layer in `http::router`, the same way `cors_layer` is
+//! conditional on `Config::cors_origins` — an operator who never expects a
+//! compressed reply isn't surprised by one. Covers the REST surface, which
+//! is where a polling consumer's bandwidth actually goes
+//! (`/market/klines`, `/market/trades`); see `http::ws` for why the
+//! websocket stream itself isn't compressed.
+//!
+//! Explicitly skips `text/event-stream` (`http::sse::stream_session`,
+//! `datasets::dataset_events`) and `application/x-ndjson`
+//! (`market::get_klines`'s `stream=true` mode): those responses are
+//! chained onto a live, indefinite stream rather than a body that's ready
+//! in full, and `to_bytes` below buffers a response completely before
+//! this layer can send a single byte of it. Applied to one of those, a
+//! gzip-negotiating client would see `to_bytes` block for as long as the
+//! stream stays open, receiving nothing until it ends — for
+//! `stream_session`/`dataset_events` that's the life of the session, i.e.
+//! never.
+
+use std::io::Write;
+
+use axum::body::{to_bytes, Body};
+use axum::extract::Request;
+use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// `true` for a `Content-Type` this layer must never buffer — see this
+/// module's doc comment.
+fn is_streaming_content_type(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("text/event-stream") || v.starts_with("application/x-ndjson"))
+        .unwrap_or(false)
+}
+
+pub(crate) async fn gzip(req: Request, next: Next) -> Response {
+    let accepts_gzip = req
+        .headers()
+        .get(ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').any(|enc| enc.trim().starts_with("gzip")))
+        .unwrap_or(false);
+
+    let response = next.run(req).await;
+    if !accepts_gzip || response.headers().contains_key(CONTENT_ENCODING) || is_streaming_content_type(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let compressed = match encoder.write_all(&bytes).and_then(|_| encoder.finish()) {
+        Ok(compressed) => compressed,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+
+    parts
+        .headers
+        .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+    parts
+        .headers
+        .insert(CONTENT_LENGTH, HeaderValue::from(compressed.len()));
+    Response::from_parts(parts, Body::from(compressed))
+}
@@ -0,0 +1,199 @@
+//! Dataset-sizing and dataset-registration endpoints, distinct from session
+//! creation's implicit `dataset` gap-filling — these never touch the store.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::Json;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{DatasetEstimate, DatasetProgress, DatasetProgressStatus, DatasetRecord, IngestionRecord, IngestionRun, Kline, Symbol};
+use crate::error::ApiError;
+use crate::services::dataset;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct EstimateDatasetRequest {
+    pub symbols: Vec<String>,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Projects the candle count, request count, estimated duration (under the
+/// configured `rate_limit_per_minute`, if any) and estimated storage size
+/// for an ingestion, without creating a dataset or session. See
+/// [`dataset::estimate`].
+#[utoipa::path(post, path = "/api/v1/datasets/estimate", tag = "datasets",
+    request_body = EstimateDatasetRequest, responses((status = 200, body = DatasetEstimate)))]
+pub async fn estimate_dataset(
+    State(state): State<AppState>,
+    Json(req): Json<EstimateDatasetRequest>,
+) -> Result<Json<DatasetEstimate>, ApiError> {
+    let result = dataset::estimate(&state, req.symbols.len(), &req.interval, req.start, req.end)?;
+    Ok(Json(result))
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RegisterDatasetRequest {
+    pub symbols: Vec<String>,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+    /// When `true`, a registration overlapping an already-registered
+    /// symbol/interval/range is merged into the existing record (its
+    /// symbol list and range both grow to cover the union) instead of
+    /// being rejected. Defaults to `false`.
+    #[serde(default)]
+    pub dedupe: bool,
+}
+
+/// Records `symbols`/`interval`/`[start, end)` as a dataset in
+/// [`crate::dataset_registry::DatasetRegistry`] — bookkeeping only, no
+/// klines are fetched or stored (see [`crate::services::dataset`] for
+/// that). 409 with the existing record's id in `dataset_id` if it overlaps
+/// an already-registered symbol/interval/range and `dedupe` wasn't set.
+#[utoipa::path(post, path = "/api/v1/datasets/register", tag = "datasets",
+    request_body = RegisterDatasetRequest, responses(
+        (status = 200, body = DatasetRecord),
+        (status = 409, description = "overlaps an already-registered dataset")
+    ))]
+pub async fn register_dataset(
+    State(state): State<AppState>,
+    Json(req): Json<RegisterDatasetRequest>,
+) -> Result<Json<DatasetRecord>, ApiError> {
+    let symbols: Vec<Symbol> = req.symbols.into_iter().map(Symbol::new).collect();
+    state
+        .dataset_registry()
+        .register(symbols, req.interval, req.start, req.end, req.dedupe)
+        .map(Json)
+        .map_err(|existing| ApiError::DatasetConflict(existing.id))
+}
+
+/// `GET /api/v1/datasets/:id/runs` - every ingestion attempt
+/// (`IngestionRun`) recorded for ingestion ledger record `id`, oldest
+/// first, so an operator can see why an earlier attempt failed even after
+/// a later resume succeeded. `id` is an `IngestionRecord::id` (the value
+/// `services::dataset::spawn_backfill` registers a gap-fill under), not a
+/// [`DatasetRecord::id`](crate::domain::DatasetRecord) — this tree's
+/// ingestion is session-triggered gap-filling, not keyed by a standalone
+/// dataset resource, so there's no other id to ask this by yet.
+#[utoipa::path(get, path = "/api/v1/datasets/{id}/runs", tag = "datasets",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<IngestionRun>)))]
+pub async fn get_ingestion_runs(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<Vec<IngestionRun>>, ApiError> {
+    state.ingestion_ledger().runs(id).map(Json).ok_or(ApiError::IngestionRecordNotFound(id))
+}
+
+/// `GET /api/v1/datasets/:id` - the ingestion ledger record itself, most
+/// usefully for its [`IngestionRecord::content_hash`]: a fingerprint over
+/// the klines rows the backfill wrote, so a result can be traced back to
+/// the exact data version it replayed against. `None` until the
+/// ingestion's first run completes — see [`dataset::content_hash`].
+#[utoipa::path(get, path = "/api/v1/datasets/{id}", tag = "datasets",
+    params(("id" = Uuid, Path)), responses((status = 200, body = IngestionRecord)))]
+pub async fn get_dataset(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<IngestionRecord>, ApiError> {
+    state.ingestion_ledger().get(id).map(Json).ok_or(ApiError::IngestionRecordNotFound(id))
+}
+
+const DEFAULT_PREVIEW_LIMIT: usize = 100;
+const MAX_PREVIEW_LIMIT: usize = 1_000;
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewDatasetQuery {
+    /// Which of the dataset's symbols to preview. Defaults to the first
+    /// one registered, since a preview chart only ever shows one series at
+    /// a time.
+    pub symbol: Option<String>,
+    /// Simulated time to center the window on. Defaults to the midpoint of
+    /// the dataset's registered range.
+    pub at: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// `GET /api/v1/datasets/:id/preview` - a small window of klines straddling
+/// `at` for one of dataset record `id`'s symbols, so a UI can render a
+/// preview chart before committing to `POST /api/v1/sessions` against it.
+/// `id` is a [`DatasetRecord::id`] (see [`register_dataset`]), not an
+/// [`crate::domain::IngestionRecord::id`] — previewing only needs the
+/// coverage a registration describes, not an ingestion's run history.
+#[utoipa::path(get, path = "/api/v1/datasets/{id}/preview", tag = "datasets",
+    params(
+        ("id" = Uuid, Path),
+        ("symbol" = Option<String>, Query),
+        ("at" = Option<i64>, Query),
+        ("limit" = Option<usize>, Query)
+    ),
+    responses((status = 200, body = Vec<Kline>)))]
+pub async fn preview_dataset(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PreviewDatasetQuery>,
+) -> Result<Json<Vec<Kline>>, ApiError> {
+    let record = state.dataset_registry().get(id).ok_or(ApiError::DatasetRecordNotFound(id))?;
+    let symbol = match query.symbol {
+        Some(symbol) => Symbol::new(symbol),
+        None => record
+            .symbols
+            .first()
+            .cloned()
+            .ok_or_else(|| ApiError::BadRequest(format!("dataset {id} has no symbols to preview")))?,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_PREVIEW_LIMIT).clamp(1, MAX_PREVIEW_LIMIT);
+    let at = query.at.unwrap_or((record.start + record.end) / 2);
+
+    Ok(Json(dataset::preview(&state, &symbol, &record.interval, record.start, record.end, at, limit)))
+}
+
+/// How often [`dataset_events`] polls every session's dataset-progress
+/// tracker for a fresh snapshot. There's no push channel behind
+/// `DatasetProgressTracker` (see `dataset_progress`'s module doc) for this
+/// to subscribe to instead.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize)]
+pub struct DatasetEventsQuery {
+    /// Only emit sessions currently in this [`DatasetProgressStatus`].
+    /// Unset streams every session's progress, the same set
+    /// `get_session_dataset_progress` would show one at a time.
+    pub status: Option<DatasetProgressStatus>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DatasetProgressEvent {
+    pub session_id: uuid::Uuid,
+    pub progress: DatasetProgress,
+    pub status: DatasetProgressStatus,
+}
+
+/// `GET /api/v1/datasets/events` - firehose SSE: every session's dataset
+/// backfill progress (see [`DatasetProgressEvent`]), optionally filtered to
+/// one [`DatasetProgressStatus`], polled every [`POLL_INTERVAL`] instead of
+/// requiring one `get_session_dataset_progress` connection per session —
+/// for admin dashboards watching a bulk ingestion across many sessions at
+/// once.
+#[utoipa::path(get, path = "/api/v1/datasets/events", tag = "datasets",
+    params(("status" = Option<DatasetProgressStatus>, Query)),
+    responses((status = 200, body = Vec<DatasetProgressEvent>)))]
+pub async fn dataset_events(
+    State(state): State<AppState>,
+    Query(query): Query<DatasetEventsQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = stream::unfold((state, query.status), |(state, status)| async move {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let events: Vec<DatasetProgressEvent> = state
+            .dataset_progress_snapshots()
+            .into_iter()
+            .map(|(session_id, progress)| DatasetProgressEvent { session_id, status: progress.status(), progress })
+            .filter(|event| status.is_none_or(|wanted| wanted == event.status))
+            .collect();
+        Some((events, (state, status)))
+    })
+    .flat_map(stream::iter)
+    .map(|event| Ok(Event::default().data(serde_json::to_string(&event).unwrap_or_default())));
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,83 @@
+//! Endpoints for poking at a live instance while debugging it, distinct
+//! from the operator control-plane surface in [`crate::http::admin`].
+
+use axum::extract::{Extension, Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{SymbolPrice, TokenRole};
+use crate::error::ApiError;
+use crate::http::tenancy::{self, NamespaceId};
+use crate::services::mark_price;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct SetLogLevelRequest {
+    /// `EnvFilter` directive string, e.g. `"debug"` or
+    /// `"info,exchange_simulator_backend::services=debug"`.
+    pub directives: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct LogLevelResponse {
+    pub directives: String,
+}
+
+/// Reloads the live tracing filter without restarting the process. Returns
+/// 400 if the directive string doesn't parse, or if the process wasn't
+/// started with [`crate::logging::init`] (e.g. the simulator embedded
+/// directly in a test harness). Requires an `Admin` token.
+#[utoipa::path(post, path = "/api/v1/debug/log-level", tag = "debug",
+    request_body = SetLogLevelRequest, responses((status = 200, body = LogLevelResponse)))]
+pub async fn set_log_level(
+    State(state): State<AppState>,
+    Extension(role): Extension<TokenRole>,
+    Json(req): Json<SetLogLevelRequest>,
+) -> Result<Json<LogLevelResponse>, ApiError> {
+    tenancy::require_role(role, TokenRole::Admin)?;
+    state.set_log_filter(&req.directives)?;
+    Ok(Json(LogLevelResponse { directives: req.directives }))
+}
+
+/// One [`SymbolPrice`] per symbol the session trades or watches, to
+/// speed up "why didn't my order fill" reports: the last kline close, the
+/// last trade print, and the session's mark price, all in one call instead
+/// of cross-referencing `/market/klines`, `/market/trades`, and the
+/// `mark_price` event stream by hand. There's no simulated order book in
+/// this tree, so unlike Binance's `bookTicker` there's no bid/ask here —
+/// `mark_price` is the closest equivalent this simulator has.
+#[utoipa::path(get, path = "/api/v1/debug/sessions/{id}/prices", tag = "debug",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<SymbolPrice>)))]
+pub async fn get_session_prices(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<SymbolPrice>>, ApiError> {
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let as_of = session.current_time;
+
+    let mut symbols: Vec<_> = session.symbols.iter().chain(&session.watchlist).cloned().collect();
+    symbols.sort();
+    symbols.dedup();
+
+    let prices = symbols
+        .into_iter()
+        .map(|symbol| {
+            let last_kline = state.market_store().klines(&symbol, &session.interval, 0, as_of + 1).into_iter().last();
+            let last_trade = state.trade_store().latest_trades(&symbol, 1).into_iter().last();
+            let mark = mark_price::compute(&state, &symbol, &session.interval, as_of, session.mark_price.as_ref());
+
+            SymbolPrice {
+                symbol,
+                last_kline_close: last_kline.as_ref().map(|k| k.close),
+                last_kline_time: last_kline.as_ref().map(|k| k.open_time),
+                last_trade_price: last_trade.as_ref().map(|t| t.price),
+                last_trade_time: last_trade.as_ref().map(|t| t.trade_time),
+                mark_price: mark,
+            }
+        })
+        .collect();
+
+    Ok(Json(prices))
+}
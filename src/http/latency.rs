@@ -0,0 +1,18 @@
+//! Middleware wiring for [`crate::latency::LatencyInjector`]: sleeps before
+//! calling through for any `/api/v3` request whose path matches a
+//! configured profile. Installed only on the v3 router, not the session
+//! management or admin surfaces, since the point is to exercise bots'
+//! handling of a slow *trading* API.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::state::AppState;
+
+pub(crate) async fn inject(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if let Some(delay) = state.latency_delay_for(req.uri().path()) {
+        tokio::time::sleep(delay).await;
+    }
+    next.run(req).await
+}
@@ -0,0 +1,63 @@
+//! Shared response-shaping helpers for the v3 (Binance-compatible) surface.
+//!
+//! [`format_decimal`] (and the `format_price`/`format_qty` wrappers below
+//! it) is the *only* numeric-to-string formatter in this tree — every
+//! price/quantity string this surface hands back, across every `http::v3`
+//! module, goes through it rather than a per-endpoint `format!` call, so
+//! there's one place to audit for Binance's fixed-width, non-scientific
+//! decimal strings (e.g. `"100.00000000"`, never `"1e2"`). Fields typed as
+//! `Decimal` directly (`domain::Trade`, `domain::Kline`, ...) don't go
+//! through this at all — `rust_decimal`'s own `serde-with-str` feature
+//! serializes them via `Decimal`'s `Display`, which never emits scientific
+//! notation either, just without padding to a symbol's configured
+//! precision. Reach for `format_decimal` specifically when a response needs
+//! that padding; plain `Decimal` fields are fine as they are everywhere
+//! else.
+
+use rust_decimal::Decimal;
+
+use crate::domain::SymbolFilters;
+
+/// Formats `value` with exactly `precision` decimal places, matching the
+/// fixed-width strings Binance returns (e.g. `"100.00000000"`), rather than
+/// the precision-less default `Decimal` rendering. Never produces
+/// scientific notation, regardless of `value`'s magnitude or scale — unlike
+/// `f64`, `Decimal`'s `Display` has no exponential form to fall back to.
+pub fn format_decimal(value: Decimal, precision: u32) -> String {
+    format!("{:.*}", precision as usize, value)
+}
+
+pub fn format_price(value: Decimal, filters: &SymbolFilters) -> String {
+    format_decimal(value, filters.price_precision())
+}
+
+pub fn format_qty(value: Decimal, filters: &SymbolFilters) -> String {
+    format_decimal(value, filters.qty_precision())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn pads_to_requested_precision() {
+        assert_eq!(format_decimal(dec!(1.5), 8), "1.50000000");
+    }
+
+    #[test]
+    fn truncates_excess_precision_with_rounding() {
+        assert_eq!(format_decimal(dec!(1.23456789), 2), "1.23");
+    }
+
+    #[test]
+    fn never_falls_back_to_scientific_notation_for_very_small_values() {
+        assert_eq!(format_decimal(dec!(0.00000001), 8), "0.00000001");
+    }
+
+    #[test]
+    fn never_falls_back_to_scientific_notation_for_very_large_values() {
+        assert_eq!(format_decimal(dec!(123456789012345), 2), "123456789012345.00");
+    }
+}
@@ -0,0 +1,232 @@
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use futures::stream;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Kline, MarketAvailability, Symbol, Trade};
+use crate::error::ApiError;
+use crate::services::{dataset, kline_reconstruction, resample};
+use crate::state::AppState;
+
+const DEFAULT_PAGE_SIZE: usize = 500;
+const MAX_PAGE_SIZE: usize = 5_000;
+
+#[derive(Debug, Deserialize)]
+pub struct KlinesQuery {
+    pub symbol: String,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+    /// Resume point: only klines with `open_time` after `cursor` are
+    /// returned. Defaults to `start - 1` so the first page includes `start`.
+    pub cursor: Option<i64>,
+    pub limit: Option<usize>,
+    /// When `true`, responds with `application/x-ndjson` instead of a JSON
+    /// page, pulling pages from the store lazily as the client reads.
+    pub stream: Option<bool>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct KlinesPage {
+    pub klines: Vec<Kline>,
+    /// Pass back as `cursor` to fetch the next page; `None` once the range
+    /// is exhausted.
+    pub next_cursor: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResampleKlinesQuery {
+    pub symbol: String,
+    pub from: String,
+    pub to: String,
+    pub start: i64,
+    pub end: i64,
+    /// Shifts bucket boundaries away from UTC, e.g. `28_800_000` (8h) so
+    /// daily candles close on exchange-local UTC+8 day boundaries instead
+    /// of UTC midnight. Defaults to `0` (UTC-aligned, the original
+    /// behavior). See [`aligned_bucket_open`](crate::domain::aligned_bucket_open).
+    #[serde(default)]
+    pub align_offset_ms: i64,
+}
+
+/// Aggregates stored `from`-interval klines into `to`-interval ones for
+/// `[start, end)`, so a dataset only has to be ingested once at its
+/// finest interval. `to` must be an exact multiple of `from` (e.g. `1m`
+/// into `15m`); anything else is a 400. See
+/// [`resample`](crate::services::resample).
+#[utoipa::path(get, path = "/api/v1/market/klines/resample", tag = "market-data",
+    params(
+        ("symbol" = String, Query),
+        ("from" = String, Query),
+        ("to" = String, Query),
+        ("start" = i64, Query),
+        ("end" = i64, Query),
+        ("align_offset_ms" = Option<i64>, Query, description = "Shifts bucket boundaries away from UTC, e.g. for exchange-local day candles")
+    ),
+    responses((status = 200, body = Vec<Kline>)))]
+pub async fn resample_klines(
+    State(state): State<AppState>,
+    Query(query): Query<ResampleKlinesQuery>,
+) -> Result<Json<Vec<Kline>>, ApiError> {
+    let symbol = Symbol::new(query.symbol);
+    let klines = resample::resample(&state, &symbol, &query.from, &query.to, query.start, query.end, query.align_offset_ms)?;
+    Ok(Json(klines))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReconstructKlinesQuery {
+    pub symbol: String,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Rebuilds `interval` klines for `symbol` in `[start, end)` from stored
+/// trades and persists them, for datasets that were only ever ingested as
+/// aggTrades. See [`kline_reconstruction`](crate::services::kline_reconstruction).
+#[utoipa::path(post, path = "/api/v1/market/klines/reconstruct", tag = "market-data",
+    params(
+        ("symbol" = String, Query),
+        ("interval" = String, Query),
+        ("start" = i64, Query),
+        ("end" = i64, Query)
+    ),
+    responses((status = 200, body = Vec<Kline>)))]
+pub async fn reconstruct_klines(
+    State(state): State<AppState>,
+    Query(query): Query<ReconstructKlinesQuery>,
+) -> Result<Json<Vec<Kline>>, ApiError> {
+    let symbol = Symbol::new(query.symbol);
+    let klines = kline_reconstruction::reconstruct(&state, &symbol, &query.interval, query.start, query.end)?;
+    Ok(Json(klines))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradesQuery {
+    pub symbol: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Historical klines for `symbol`/`interval` in `[start, end)`. Results are
+/// cursor-paginated (`limit`, default 500, max 5000) so a multi-year range
+/// doesn't come back as one huge JSON array; pass `stream=true` instead to
+/// get the whole range as an `application/x-ndjson` body, one kline per
+/// line, fetched from the store page by page as the response streams out.
+#[utoipa::path(get, path = "/api/v1/market/klines", tag = "market-data",
+    params(
+        ("symbol" = String, Query),
+        ("interval" = String, Query),
+        ("start" = i64, Query),
+        ("end" = i64, Query),
+        ("cursor" = Option<i64>, Query),
+        ("limit" = Option<usize>, Query),
+        ("stream" = Option<bool>, Query)
+    ),
+    responses((status = 200, body = KlinesPage)))]
+pub async fn get_klines(State(state): State<AppState>, Query(query): Query<KlinesQuery>) -> Response {
+    let symbol = Symbol::new(query.symbol);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let cursor = query.cursor.unwrap_or(query.start - 1);
+
+    if query.stream.unwrap_or(false) {
+        return stream_klines(state, symbol, query.interval, cursor, query.end, limit);
+    }
+
+    let page = state
+        .market_store()
+        .klines_page(&symbol, &query.interval, cursor, query.end, limit);
+    let next_cursor = next_cursor(&page, limit);
+    Json(KlinesPage { klines: page, next_cursor }).into_response()
+}
+
+fn next_cursor(page: &[Kline], limit: usize) -> Option<i64> {
+    if page.len() < limit {
+        None
+    } else {
+        page.last().map(|k| k.open_time)
+    }
+}
+
+/// Streams `application/x-ndjson`, fetching one page at a time from the
+/// store as the previous page is written out instead of materializing the
+/// full range up front.
+fn stream_klines(state: AppState, symbol: Symbol, interval: String, cursor: i64, end: i64, limit: usize) -> Response {
+    let body = stream::unfold(Some(cursor), move |cursor| {
+        let state = state.clone();
+        let symbol = symbol.clone();
+        let interval = interval.clone();
+        async move {
+            let cursor = cursor?;
+            let page = state.market_store().klines_page(&symbol, &interval, cursor, end, limit);
+            if page.is_empty() {
+                return None;
+            }
+
+            let next = next_cursor(&page, limit);
+            let mut ndjson = String::new();
+            for kline in &page {
+                if let Ok(line) = serde_json::to_string(kline) {
+                    ndjson.push_str(&line);
+                    ndjson.push('\n');
+                }
+            }
+            Some((Ok::<_, std::convert::Infallible>(ndjson), next))
+        }
+    });
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from_stream(body))
+        .unwrap()
+}
+
+#[utoipa::path(get, path = "/api/v1/market/trades", tag = "market-data",
+    params(
+        ("symbol" = String, Query),
+        ("start" = i64, Query),
+        ("end" = i64, Query)
+    ),
+    responses((status = 200, body = Vec<Trade>)))]
+pub async fn get_trades(
+    State(state): State<AppState>,
+    Query(query): Query<TradesQuery>,
+) -> Json<Vec<Trade>> {
+    let symbol = Symbol::new(query.symbol);
+    Json(state.trade_store().trades(&symbol, query.start, query.end))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvailabilityQuery {
+    pub symbol: String,
+    pub interval: String,
+    /// Bounded the same way every other market-data endpoint in this file
+    /// is — there's no "whole history" query here any more than there is
+    /// for `get_klines`/`get_trades`.
+    pub start: i64,
+    pub end: i64,
+}
+
+/// `GET /api/v1/market/availability` - the contiguous spans of `[start,
+/// end)` this instance has no klines for, so a UI can offer "ingest the
+/// missing part" per span. See [`MarketAvailability`]'s doc comment for
+/// why this only ever reports local coverage.
+#[utoipa::path(get, path = "/api/v1/market/availability", tag = "market-data",
+    params(
+        ("symbol" = String, Query),
+        ("interval" = String, Query),
+        ("start" = i64, Query),
+        ("end" = i64, Query)
+    ),
+    responses((status = 200, body = MarketAvailability)))]
+pub async fn availability(
+    State(state): State<AppState>,
+    Query(query): Query<AvailabilityQuery>,
+) -> Result<Json<MarketAvailability>, ApiError> {
+    let symbol = Symbol::new(query.symbol);
+    let missing_spans = dataset::missing_spans(&state, &symbol, &query.interval, query.start, query.end)?;
+    Ok(Json(MarketAvailability { symbol, interval: query.interval, start: query.start, end: query.end, missing_spans }))
+}
@@ -0,0 +1,175 @@
+pub mod admin;
+mod compression;
+pub mod datasets;
+pub mod debug;
+mod latency;
+pub mod mappers;
+pub mod market;
+pub mod namespaces;
+pub(crate) mod request_id;
+pub mod reports;
+pub mod sessions;
+mod sse;
+pub(crate) mod tenancy;
+pub mod v3;
+pub mod webhooks;
+mod ws;
+
+use axum::http::HeaderValue;
+use axum::{middleware, Router};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::{ServeDir, ServeFile};
+
+use crate::config::Config;
+use crate::state::AppState;
+
+pub fn router(state: AppState, config: &Config) -> Router {
+    let v3_router = v3::router().layer(middleware::from_fn_with_state(state.clone(), latency::inject));
+
+    // Bootstrap namespace/token endpoints stay outside `require_token` —
+    // something has to be able to mint a first token. Every other v1 route
+    // is gated.
+    let v1_router = v1_router()
+        .route_layer(middleware::from_fn_with_state(state.clone(), tenancy::require_token))
+        .merge(bootstrap_router());
+
+    let mut app = Router::new()
+        .nest("/api/v1", v1_router)
+        .nest("/api/v3", v3_router)
+        .merge(crate::oas::router())
+        .with_state(state);
+
+    if let Some(cors) = cors_layer(&config.cors_origins) {
+        app = app.layer(cors);
+    }
+
+    if config.enable_compression {
+        app = app.layer(middleware::from_fn(compression::gzip));
+    }
+
+    if let Some(ui_dir) = &config.ui_dir {
+        app = app.fallback_service(static_ui_service(ui_dir));
+    }
+
+    app.layer(middleware::from_fn(request_id::propagate))
+}
+
+fn v1_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/sessions",
+            axum::routing::post(sessions::create_session).get(sessions::list_sessions),
+        )
+        .route("/sessions/:id", axum::routing::get(sessions::get_session))
+        .route(
+            "/sessions/:id/start",
+            axum::routing::post(sessions::start_session),
+        )
+        .route(
+            "/sessions/:id/pause-at",
+            axum::routing::post(sessions::pause_at),
+        )
+        .route(
+            "/sessions/:id/breakpoints",
+            axum::routing::post(sessions::add_breakpoint),
+        )
+        .route("/sessions/:id/seek", axum::routing::post(sessions::seek_session))
+        .route("/sessions/:id/matching", axum::routing::patch(sessions::patch_matching_config))
+        .route("/sessions/:id/stream", axum::routing::get(sse::stream_session))
+        .route("/sessions/:id/ws", axum::routing::get(ws::ws_session))
+        .route(
+            "/sessions/:id/market/klines",
+            axum::routing::get(sessions::get_session_klines),
+        )
+        .route(
+            "/sessions/:id/market/trades",
+            axum::routing::get(sessions::get_session_trades),
+        )
+        .route("/sessions/:id/tape", axum::routing::get(sessions::get_session_tape))
+        .route("/sessions/:id/equity-curve", axum::routing::get(sessions::get_session_equity_curve))
+        .route("/sessions/:id/events", axum::routing::get(sessions::get_session_events))
+        .route("/sessions/:id/margin", axum::routing::get(sessions::get_session_margin))
+        .route("/sessions/:id/shorts", axum::routing::get(sessions::get_session_shorts))
+        .route("/sessions/:id/positions", axum::routing::get(sessions::get_session_positions))
+        .route("/sessions/:id/order-rate", axum::routing::get(sessions::get_session_order_rate))
+        .route("/sessions/:id/export/trades", axum::routing::get(sessions::export_session_trades))
+        .route(
+            "/sessions/:id/analytics/:symbol",
+            axum::routing::get(sessions::get_session_analytics),
+        )
+        .route(
+            "/sessions/:id/dataset/progress",
+            axum::routing::get(sessions::get_session_dataset_progress),
+        )
+        .route("/sessions/:id/ws-stats", axum::routing::get(sessions::get_session_ws_stats))
+        .route("/sessions/:id/datasets", axum::routing::get(sessions::get_session_datasets))
+        .route("/sessions/:id/summary", axum::routing::get(sessions::get_session_summary))
+        .route("/webhooks", axum::routing::post(webhooks::register_webhook))
+        .route(
+            "/webhooks/:id/deliveries",
+            axum::routing::get(webhooks::get_webhook_deliveries),
+        )
+        .route("/market/klines", axum::routing::get(market::get_klines))
+        .route("/market/klines/resample", axum::routing::get(market::resample_klines))
+        .route("/market/klines/reconstruct", axum::routing::post(market::reconstruct_klines))
+        .route("/market/trades", axum::routing::get(market::get_trades))
+        .route("/market/availability", axum::routing::get(market::availability))
+        .route("/datasets/estimate", axum::routing::post(datasets::estimate_dataset))
+        .route("/datasets/register", axum::routing::post(datasets::register_dataset))
+        .route("/datasets/events", axum::routing::get(datasets::dataset_events))
+        .route("/datasets/:id/runs", axum::routing::get(datasets::get_ingestion_runs))
+        .route("/datasets/:id", axum::routing::get(datasets::get_dataset))
+        .route("/datasets/:id/preview", axum::routing::get(datasets::preview_dataset))
+        .route("/reports/compare", axum::routing::get(reports::compare_sessions))
+        .route(
+            "/admin/maintenance",
+            axum::routing::post(admin::set_maintenance),
+        )
+        .route("/admin/cache-stats", axum::routing::get(admin::cache_stats))
+        .route("/admin/pause-all", axum::routing::post(admin::pause_all))
+        .route("/admin/resume-all", axum::routing::post(admin::resume_all))
+        .route("/admin/backup", axum::routing::post(admin::backup_dataset))
+        .route("/admin/symbols/:symbol/metadata", axum::routing::post(admin::set_symbol_metadata))
+        .route("/admin/datasets/merge", axum::routing::post(admin::merge_datasets))
+        .route("/admin/datasets/:id", axum::routing::delete(admin::delete_dataset))
+        .route(
+            "/admin/symbols/lifecycle",
+            axum::routing::post(admin::register_symbol_lifecycle_event).get(admin::list_symbol_lifecycle_events),
+        )
+        .route("/admin/datasets/export", axum::routing::get(admin::export_dataset))
+        .route("/admin/datasets/import", axum::routing::post(admin::import_dataset))
+        .route("/config", axum::routing::get(admin::get_config))
+        .route("/debug/log-level", axum::routing::post(debug::set_log_level))
+        .route("/debug/sessions/:id/prices", axum::routing::get(debug::get_session_prices))
+}
+
+fn bootstrap_router() -> Router<AppState> {
+    Router::new()
+        .route("/namespaces", axum::routing::post(namespaces::create_namespace))
+        .route("/namespaces/:id/tokens", axum::routing::post(namespaces::issue_token))
+}
+
+/// `None` when no origins are configured, so the layer is opt-in rather
+/// than silently wide open.
+fn cors_layer(origins: &[String]) -> Option<CorsLayer> {
+    if origins.is_empty() {
+        return None;
+    }
+
+    if origins.iter().any(|o| o == "*") {
+        return Some(CorsLayer::permissive());
+    }
+
+    let parsed: Vec<HeaderValue> = origins
+        .iter()
+        .filter_map(|o| HeaderValue::from_str(o).ok())
+        .collect();
+
+    Some(CorsLayer::new().allow_origin(AllowOrigin::list(parsed)))
+}
+
+/// Serves the bundled web frontend from `ui_dir`, falling back to
+/// `index.html` for unknown paths so client-side routing works.
+fn static_ui_service(ui_dir: &str) -> ServeDir<ServeFile> {
+    ServeDir::new(ui_dir).fallback(ServeFile::new(format!("{ui_dir}/index.html")))
+}
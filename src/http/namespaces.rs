@@ -0,0 +1,51 @@
+//! Bootstrap endpoints for creating namespaces and minting their first API
+//! tokens. Deliberately outside [`crate::http::tenancy::require_token`]'s
+//! gate — something has to be able to mint a token before any token exists.
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{Namespace, TokenRole};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateNamespaceRequest {
+    pub name: String,
+}
+
+#[utoipa::path(post, path = "/api/v1/namespaces", tag = "namespaces",
+    request_body = CreateNamespaceRequest, responses((status = 200, body = Namespace)))]
+pub async fn create_namespace(
+    State(state): State<AppState>,
+    Json(req): Json<CreateNamespaceRequest>,
+) -> Json<Namespace> {
+    Json(state.create_namespace(req.name))
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct IssueTokenRequest {
+    pub role: TokenRole,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct IssueTokenResponse {
+    pub token: String,
+    pub role: TokenRole,
+}
+
+/// Mints an opaque API token scoped to `id` with the requested role.
+/// Callers send it back as `X-Api-Token` on every other v1 request.
+#[utoipa::path(post, path = "/api/v1/namespaces/{id}/tokens", tag = "namespaces",
+    params(("id" = Uuid, Path)), request_body = IssueTokenRequest,
+    responses((status = 200, body = IssueTokenResponse)))]
+pub async fn issue_token(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<IssueTokenRequest>,
+) -> Result<Json<IssueTokenResponse>, ApiError> {
+    let token = state.issue_token(id, req.role).ok_or(ApiError::NamespaceNotFound(id))?;
+    Ok(Json(IssueTokenResponse { token, role: req.role }))
+}
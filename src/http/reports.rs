@@ -0,0 +1,53 @@
+//! Cross-session comparison, built from [`SessionSummary`] — the only
+//! per-session metrics this tree persists. There's no fill or balance
+//! pipeline anywhere in this tree (see `orders`' and `domain::summary`'s
+//! module docs), so there's no equity curve to align across sessions
+//! either; what [`compare_sessions`] returns is each session's final
+//! summary, in the order requested, which is the honest "key metrics" half
+//! of what an A/B comparison needs until a real fill/balance history
+//! exists to chart a curve from.
+
+use axum::extract::{Extension, Query, State};
+use axum::Json;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::SessionSummary;
+use crate::error::ApiError;
+use crate::http::tenancy::NamespaceId;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct CompareSessionsQuery {
+    /// Comma-separated session ids, e.g. `?sessions=a,b,c`.
+    pub sessions: String,
+}
+
+/// Final [`SessionSummary`] for each session in `sessions`, in the order
+/// given, so a caller can line them up as columns in an A/B comparison.
+/// 400 if the list is empty or any id doesn't parse; 404 if any session
+/// doesn't exist, isn't in the caller's namespace, or hasn't ended yet —
+/// the same `ApiError` [`crate::state::AppState::summary`] already reports
+/// for a single session.
+#[utoipa::path(get, path = "/api/v1/reports/compare", tag = "reports",
+    params(("sessions" = String, Query, description = "Comma-separated session ids")),
+    responses((status = 200, body = Vec<SessionSummary>)))]
+pub async fn compare_sessions(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Query(query): Query<CompareSessionsQuery>,
+) -> Result<Json<Vec<SessionSummary>>, ApiError> {
+    let ids: Vec<&str> = query.sessions.split(',').map(str::trim).filter(|s| !s.is_empty()).collect();
+    if ids.is_empty() {
+        return Err(ApiError::BadRequest("sessions must not be empty".into()));
+    }
+
+    let mut summaries = Vec::with_capacity(ids.len());
+    for id in ids {
+        let id: Uuid = id.parse().map_err(|_| ApiError::BadRequest(format!("invalid session id: {id}")))?;
+        state.get_session_scoped(id, namespace_id)?;
+        summaries.push(state.summary(id)?);
+    }
+
+    Ok(Json(summaries))
+}
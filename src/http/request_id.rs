@@ -0,0 +1,39 @@
+//! Per-request correlation id. An inbound `X-Request-Id` is honored as-is;
+//! otherwise a fresh one is generated. Either way it's echoed on the
+//! response header and available to [`crate::error::ApiError`] for its
+//! JSON error envelope, so a failure in a multi-service test setup can be
+//! traced back to server logs instead of guessed at.
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+
+pub const HEADER_NAME: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// The current request's id, when called from within a handler reached
+/// through [`propagate`]. `None` outside that scope, e.g. a unit test
+/// calling a handler function directly.
+pub(crate) fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+pub(crate) async fn propagate(req: Request, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(HEADER_NAME)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let mut response = REQUEST_ID.scope(id.clone(), next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(HEADER_NAME, value);
+    }
+    response
+}
@@ -0,0 +1,757 @@
+use axum::body::Body;
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::header;
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::{
+    BorrowPosition, BreakpointCondition, ChaosConfig, ClockDriftConfig, CommissionConfig, DatasetProgress,
+    EquitySnapshot, IngestionRecord, LiveDataSource, MarginConfig, MarkPriceConfig, MarketEvent, MarketMode,
+    MatchingEngineKind, OrderLimitsConfig, OrderRateBucket, Position, Session, SessionStatus, SessionSummary,
+    ShortPosition, Symbol, SymbolAnalytics, TapeBucket, TokenRole, Trade, WsConnectionRecord,
+};
+use crate::error::ApiError;
+use crate::http::market::KlinesPage;
+use crate::http::tenancy::{self, NamespaceId};
+use crate::services::{dataset, execution_export, symbol_history, tape, ReplayService};
+use crate::state::AppState;
+
+const DEFAULT_PAGE_SIZE: usize = 500;
+const MAX_PAGE_SIZE: usize = 5_000;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateSessionRequest {
+    pub symbols: Vec<String>,
+    /// Extra symbols to stream read-only alongside `symbols`. See
+    /// [`Session::watchlist`].
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+    pub interval: String,
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    #[serde(default)]
+    pub clock_drift: Option<ClockDriftConfig>,
+    #[serde(default)]
+    pub commission: Option<CommissionConfig>,
+    #[serde(default)]
+    pub margin: Option<MarginConfig>,
+    #[serde(default)]
+    pub allow_short: bool,
+    #[serde(default)]
+    pub chaos: Option<ChaosConfig>,
+    /// See [`Session::mark_price`]. Defaults to
+    /// [`MarkPriceConfig::LastClose`] when unset.
+    #[serde(default)]
+    pub mark_price: Option<MarkPriceConfig>,
+    /// If set, validates that every symbol in `symbols` has data for this
+    /// range before the session is created, instead of starting silently
+    /// against an empty store. See [`dataset`](crate::services::dataset).
+    #[serde(default)]
+    pub dataset: Option<DatasetRequirement>,
+    /// See [`Session::start_time`]. Defaults to `0`.
+    #[serde(default)]
+    pub start_time: i64,
+    /// See [`Session::warmup_start`]. Must be earlier than `start_time` if set.
+    #[serde(default)]
+    pub warmup_start: Option<i64>,
+    /// See [`Session::order_limits`].
+    #[serde(default)]
+    pub order_limits: Option<OrderLimitsConfig>,
+    /// See [`Session::clock_group`].
+    #[serde(default)]
+    pub clock_group: Option<Uuid>,
+    /// See [`Session::live_source`].
+    #[serde(default)]
+    pub live_source: Option<LiveDataSource>,
+    /// See [`Session::matching_engine`].
+    #[serde(default)]
+    pub matching_engine: Option<MatchingEngineKind>,
+    /// Copies `open_borrows`/`open_shorts` from this session's
+    /// [`SessionSummary`] (it must have ended) into the new session's
+    /// margin/short ledgers, so chained backtests (e.g. month by month)
+    /// start from where the last one left off instead of flat. The new
+    /// session's own `symbols`/`interval`/`margin`/`allow_short` are
+    /// otherwise independent of the source session.
+    #[serde(default)]
+    pub from_session: Option<Uuid>,
+    /// See [`Session::candle_align_offset_ms`]. Defaults to `0` (UTC-aligned).
+    #[serde(default)]
+    pub candle_align_offset_ms: i64,
+    /// See [`Session::equity_snapshot_interval_ms`]. `None` (the default)
+    /// disables periodic equity-curve snapshotting.
+    #[serde(default)]
+    pub equity_snapshot_interval_ms: Option<i64>,
+    /// See [`Session::ack_before_trade`]. Defaults to `false`.
+    #[serde(default)]
+    pub ack_before_trade: bool,
+    /// See [`Session::market_mode`]. `None` falls back to the instance's
+    /// configured `default_market_mode`.
+    #[serde(default)]
+    pub market_mode: Option<MarketMode>,
+    /// See [`Session::name`].
+    #[serde(default)]
+    pub name: Option<String>,
+    /// See [`Session::tags`].
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// See [`Session::matching_enabled`]. Defaults to `false`.
+    #[serde(default)]
+    pub matching_enabled: bool,
+}
+
+/// A `[start, end)` range session creation should check is covered by
+/// existing data before returning. With `auto_ingest` unset (the default),
+/// a gap fails the request with a 400 listing the missing symbols; with it
+/// set, the session is created immediately and missing symbols are
+/// backfilled in the background (see
+/// [`dataset::spawn_backfill`](crate::services::dataset::spawn_backfill)).
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct DatasetRequirement {
+    pub start: i64,
+    pub end: i64,
+    #[serde(default)]
+    pub auto_ingest: bool,
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+#[utoipa::path(post, path = "/api/v1/sessions", tag = "sessions",
+    request_body = CreateSessionRequest, responses((status = 200, body = Session)))]
+pub async fn create_session(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Json(req): Json<CreateSessionRequest>,
+) -> Result<Json<Session>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    if req.symbols.is_empty() {
+        return Err(ApiError::BadRequest("symbols must not be empty".into()));
+    }
+    if let Some(warmup_start) = req.warmup_start {
+        if warmup_start >= req.start_time {
+            return Err(ApiError::BadRequest("warmup_start must be earlier than start_time".into()));
+        }
+    }
+
+    let seed_summary = match req.from_session {
+        Some(from_session) => {
+            state.get_session_scoped(from_session, namespace_id)?;
+            Some(state.summary(from_session)?)
+        }
+        None => None,
+    };
+
+    let symbols: Vec<Symbol> = req.symbols.into_iter().map(Symbol::new).collect();
+    let market_mode = req.market_mode.unwrap_or(state.config().default_market_mode);
+    let mut pending_backfill = None;
+    if let Some(requirement) = req.dataset {
+        if market_mode.includes_trades() {
+            let missing = dataset::trade_gaps(&state, &symbols, requirement.start, requirement.end);
+            if !missing.is_empty() {
+                let missing = missing.iter().map(Symbol::as_str).collect::<Vec<_>>().join(", ");
+                return Err(ApiError::BadRequest(format!(
+                    "market_mode {market_mode:?} requires trade data but none exists in [{}, {}) for symbols: {} \
+                     (there is no trade backfill pipeline yet, see services::dataset's module doc)",
+                    requirement.start, requirement.end, missing
+                )));
+            }
+        }
+
+        let gaps = dataset::gaps(&state, &symbols, &req.interval, requirement.start, requirement.end);
+        if !gaps.is_empty() {
+            if !requirement.auto_ingest {
+                let missing = gaps.iter().map(Symbol::as_str).collect::<Vec<_>>().join(", ");
+                return Err(ApiError::BadRequest(format!(
+                    "no data in [{}, {}) for symbols: {}",
+                    requirement.start, requirement.end, missing
+                )));
+            }
+            pending_backfill = Some((gaps, requirement));
+        }
+    }
+
+    let session = Session {
+        id: Uuid::new_v4(),
+        namespace_id,
+        symbols,
+        watchlist: req.watchlist.into_iter().map(Symbol::new).collect(),
+        interval: req.interval,
+        speed: req.speed,
+        status: SessionStatus::Created,
+        clock_drift: req.clock_drift,
+        commission: req.commission,
+        margin: req.margin,
+        allow_short: req.allow_short,
+        chaos: req.chaos,
+        mark_price: req.mark_price,
+        start_time: req.start_time,
+        warmup_start: req.warmup_start,
+        current_time: 0,
+        order_limits: req.order_limits,
+        clock_group: req.clock_group,
+        live_source: req.live_source,
+        matching_engine: req.matching_engine,
+        pause_at: None,
+        breakpoints: Vec::new(),
+        candle_align_offset_ms: req.candle_align_offset_ms,
+        equity_snapshot_interval_ms: req.equity_snapshot_interval_ms,
+        seek_to: None,
+        ack_before_trade: req.ack_before_trade,
+        market_mode: req.market_mode,
+        name: req.name,
+        tags: req.tags,
+        matching_enabled: req.matching_enabled,
+    };
+    state.insert_session(session.clone());
+
+    if let Some(summary) = seed_summary {
+        state.margin_ledger(session.id)?.seed(summary.open_borrows);
+        state.short_ledger(session.id)?.seed(summary.open_shorts);
+    }
+
+    if let Some((gaps, requirement)) = pending_backfill {
+        dataset::spawn_backfill(state, session.id, gaps, session.interval.clone(), requirement.start, requirement.end);
+    }
+
+    Ok(Json(session))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListSessionsQuery {
+    pub tag: Option<String>,
+}
+
+/// Every session in the caller's namespace, optionally filtered to those
+/// carrying `tag`, so dozens of experiment sessions (see [`Session::name`],
+/// [`Session::tags`]) can be found without tracking ids externally.
+#[utoipa::path(get, path = "/api/v1/sessions", tag = "sessions",
+    params(("tag" = Option<String>, Query)),
+    responses((status = 200, body = Vec<Session>)))]
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Query(query): Query<ListSessionsQuery>,
+) -> Result<Json<Vec<Session>>, ApiError> {
+    let sessions = state.list_sessions(namespace_id);
+    let sessions = match query.tag {
+        Some(tag) => sessions.into_iter().filter(|session| session.tags.contains(&tag)).collect(),
+        None => sessions,
+    };
+    Ok(Json(sessions))
+}
+
+#[utoipa::path(get, path = "/api/v1/sessions/{id}", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Session)))]
+pub async fn get_session(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Session>, ApiError> {
+    state.get_session_scoped(id, namespace_id).map(Json)
+}
+
+#[utoipa::path(post, path = "/api/v1/sessions/{id}/start", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Session)))]
+pub async fn start_session(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Session>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    state.get_session_scoped(id, namespace_id)?;
+    state.set_status(id, SessionStatus::Running)?;
+    ReplayService::spawn(state.clone(), id);
+    state.get_session_scoped(id, namespace_id).map(Json)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PauseAtQuery {
+    pub time: i64,
+}
+
+/// Registers a breakpoint at simulated `time`: once `ReplayService`'s
+/// clock reaches it, the session is paused before that tick's events are
+/// broadcast, so a caller can stop right before a known market event to
+/// inspect state instead of racing a `pause-all` call against the replay
+/// loop. Replaces any breakpoint registered earlier. Does not itself
+/// start or resume the session — see [`start_session`].
+#[utoipa::path(post, path = "/api/v1/sessions/{id}/pause-at", tag = "sessions",
+    params(("id" = Uuid, Path), ("time" = i64, Query, description = "Simulated open_time to pause at")),
+    responses((status = 200, body = Session)))]
+pub async fn pause_at(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PauseAtQuery>,
+) -> Result<Json<Session>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    state.get_session_scoped(id, namespace_id)?;
+    state.set_pause_at(id, Some(query.time))?;
+    state.get_session_scoped(id, namespace_id).map(Json)
+}
+
+/// Registers a condition-based breakpoint (e.g. "pause when BTCUSDT
+/// crosses 105") in addition to any already registered — unlike
+/// [`pause_at`], this is additive rather than replacing. The first
+/// condition to fire pauses the session, publishes a breakpoint event on
+/// its stream, and is removed from the list; see
+/// `services::replay_service::find_triggered_breakpoint`. Does not itself
+/// start or resume the session — see [`start_session`].
+#[utoipa::path(post, path = "/api/v1/sessions/{id}/breakpoints", tag = "sessions",
+    params(("id" = Uuid, Path)), request_body = BreakpointCondition,
+    responses((status = 200, body = Session)))]
+pub async fn add_breakpoint(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Path(id): Path<Uuid>,
+    Json(condition): Json<BreakpointCondition>,
+) -> Result<Json<Session>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    state.get_session_scoped(id, namespace_id)?;
+    state.add_breakpoint(id, condition).map(Json)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SeekQuery {
+    pub time: i64,
+}
+
+/// Jumps a running session's replay loop forward to simulated time `time`
+/// on its next tick, skipping over everything in between without
+/// synthesizing or publishing it. Matcher state (see [`crate::matching`])
+/// is left exactly as it was across the jump, since there's no order book
+/// in this tree for a forward jump to invalidate — the same reason
+/// rewinding isn't supported: there's no journal to restore order-book
+/// state from on the way back. 400s if `time` is before the session's
+/// current simulated time.
+#[utoipa::path(post, path = "/api/v1/sessions/{id}/seek", tag = "sessions",
+    params(("id" = Uuid, Path), ("time" = i64, Query, description = "Simulated open_time to jump forward to")),
+    responses((status = 200, body = Session)))]
+pub async fn seek_session(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SeekQuery>,
+) -> Result<Json<Session>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    state.get_session_scoped(id, namespace_id)?;
+    state.seek(id, query.time)?;
+    state.get_session_scoped(id, namespace_id).map(Json)
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct PatchMatchingRequest {
+    /// See [`Session::matching_enabled`]. Unset leaves it as-is.
+    #[serde(default)]
+    pub matching_enabled: Option<bool>,
+    /// See [`Session::matching_engine`]. Unset leaves it as-is; there's no
+    /// way to clear it back to `None` through this endpoint, the same as
+    /// every other `Option`-shaped session field.
+    #[serde(default)]
+    pub matching_engine: Option<MatchingEngineKind>,
+}
+
+/// Flips a paused session between a dry, market-data-only replay and a
+/// trading one, and/or swaps its fill model — so a strategy can replay a
+/// dataset once to validate signals, then re-run the same session with
+/// matching turned on instead of recreating it from scratch. 400s if the
+/// session isn't [`SessionStatus::Paused`]. See
+/// [`AppState::set_matching_config`].
+#[utoipa::path(patch, path = "/api/v1/sessions/{id}/matching", tag = "sessions",
+    params(("id" = Uuid, Path)), request_body = PatchMatchingRequest,
+    responses((status = 200, body = Session), (status = 400, description = "session is not paused")))]
+pub async fn patch_matching_config(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<PatchMatchingRequest>,
+) -> Result<Json<Session>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    state.get_session_scoped(id, namespace_id)?;
+    state.set_matching_config(id, req.matching_enabled, req.matching_engine).map(Json)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionKlinesQuery {
+    pub symbol: String,
+    pub interval: String,
+    pub start: i64,
+    pub end: Option<i64>,
+    pub cursor: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// Same shape as `GET /api/v1/market/klines`, but `end` is clamped to the
+/// session's `current_time` so a strategy reading through this endpoint
+/// can't see candles the replay loop hasn't emitted yet, and the lower
+/// bound is clamped to [`Session::readable_from`] so a query can't reach
+/// further back than the session's warm-up window either. A range that
+/// spans a registered rename is stitched together under `symbol`'s current
+/// name — see [`symbol_history::klines_page`].
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/market/klines", tag = "sessions",
+    params(
+        ("id" = Uuid, Path),
+        ("symbol" = String, Query),
+        ("interval" = String, Query),
+        ("start" = i64, Query),
+        ("end" = Option<i64>, Query),
+        ("cursor" = Option<i64>, Query),
+        ("limit" = Option<usize>, Query)
+    ),
+    responses((status = 200, body = KlinesPage)))]
+pub async fn get_session_klines(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionKlinesQuery>,
+) -> Result<Json<KlinesPage>, ApiError> {
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let symbol = Symbol::new(query.symbol);
+    let end = query.end.unwrap_or(session.current_time).min(session.current_time);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let cursor = query.cursor.unwrap_or(query.start - 1).max(session.readable_from() - 1);
+
+    let page = symbol_history::klines_page(&state, &symbol, &query.interval, cursor, end, limit);
+    let next_cursor = if page.len() < limit {
+        None
+    } else {
+        page.last().map(|k| k.open_time)
+    };
+    Ok(Json(KlinesPage { klines: page, next_cursor }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionTradesQuery {
+    pub symbol: String,
+    pub start: i64,
+    pub end: Option<i64>,
+}
+
+/// Same shape as `GET /api/v1/market/trades`, but `end` is clamped to the
+/// session's `current_time` to avoid look-ahead, and `start` is clamped to
+/// [`Session::readable_from`] to avoid reaching earlier than the session's
+/// warm-up window.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/market/trades", tag = "sessions",
+    params(
+        ("id" = Uuid, Path),
+        ("symbol" = String, Query),
+        ("start" = i64, Query),
+        ("end" = Option<i64>, Query)
+    ),
+    responses((status = 200, body = Vec<Trade>)))]
+pub async fn get_session_trades(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionTradesQuery>,
+) -> Result<Json<Vec<Trade>>, ApiError> {
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let symbol = Symbol::new(query.symbol);
+    let end = query.end.unwrap_or(session.current_time).min(session.current_time);
+    let start = query.start.max(session.readable_from());
+    Ok(Json(state.trade_store().trades(&symbol, start, end)))
+}
+
+/// The session's trade prints, across `symbols` and `watchlist`, rendered
+/// as Binance spot "Trade History" CSV (see
+/// [`execution_export::trade_history_csv`]) so a tax/PnL tool already
+/// wired up for real Binance exports can consume simulator output
+/// unchanged. Bounded to `[readable_from, current_time)`, same as
+/// [`get_session_trades`].
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/export/trades", tag = "sessions",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "text/csv body", content_type = "text/csv")))]
+pub async fn export_session_trades(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let start = session.readable_from();
+    let end = session.current_time;
+
+    let mut trades: Vec<Trade> = session
+        .symbols
+        .iter()
+        .chain(session.watchlist.iter())
+        .flat_map(|symbol| state.trade_store().trades(symbol, start, end))
+        .collect();
+    trades.sort_by_key(|trade| trade.trade_time);
+
+    let csv = execution_export::trade_history_csv(&trades);
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "text/csv")
+        .body(Body::from(csv))
+        .unwrap())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionTapeQuery {
+    pub symbol: String,
+    pub bucket: String,
+    pub start: Option<i64>,
+}
+
+/// Aggregated trade prints (count, volume, VWAP) per `bucket`-sized window
+/// (e.g. `"1s"`), from the replayed trades up to the session's
+/// `current_time` — powers time & sales style UIs without streaming every
+/// individual trade. See [`tape::aggregate`].
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/tape", tag = "sessions",
+    params(
+        ("id" = Uuid, Path),
+        ("symbol" = String, Query),
+        ("bucket" = String, Query, description = "Aggregation window, e.g. \"1s\""),
+        ("start" = Option<i64>, Query)
+    ),
+    responses((status = 200, body = Vec<TapeBucket>)))]
+pub async fn get_session_tape(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionTapeQuery>,
+) -> Result<Json<Vec<TapeBucket>>, ApiError> {
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let symbol = Symbol::new(query.symbol);
+    let start = query.start.unwrap_or_else(|| session.readable_from()).max(session.readable_from());
+    let end = session.current_time;
+    tape::aggregate(&state, &symbol, &query.bucket, start, end, session.candle_align_offset_ms).map(Json)
+}
+
+/// The session's fine-grained equity curve: one point per
+/// `Session::equity_snapshot_interval_ms` of simulated time, recorded by
+/// `services::replay_service` as the session runs rather than only once at
+/// the end like [`SessionSummary`]. Empty if the session never set an
+/// interval.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/equity-curve", tag = "sessions",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = Vec<EquitySnapshot>)))]
+pub async fn get_session_equity_curve(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<EquitySnapshot>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    Ok(Json(state.equity_curve(id)?.all()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionEventsQuery {
+    #[serde(rename = "fromSeq")]
+    pub from_seq: Option<u64>,
+}
+
+/// Re-fetches journaled broadcast events for a session from `fromSeq`
+/// onward, so a websocket/SSE client that disconnected can recover
+/// deterministically instead of losing everything it missed.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/events", tag = "sessions",
+    params(("id" = Uuid, Path), ("fromSeq" = Option<u64>, Query)),
+    responses((status = 200, body = Vec<MarketEvent>)))]
+pub async fn get_session_events(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionEventsQuery>,
+) -> Result<Json<Vec<MarketEvent>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let broadcaster = state.broadcaster(id)?;
+    Ok(Json(broadcaster.events_from(query.from_seq.unwrap_or(0))))
+}
+
+/// Outstanding borrow positions for a margin-enabled session, with interest
+/// accrued up to the session's current simulated time. Returns an empty
+/// list for sessions that never opted into margin.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/margin", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<BorrowPosition>)))]
+pub async fn get_session_margin(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<BorrowPosition>>, ApiError> {
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let Some(config) = session.margin else {
+        return Ok(Json(Vec::new()));
+    };
+    let ledger = state.margin_ledger(id)?;
+    Ok(Json(ledger.positions(session.current_time, &config)))
+}
+
+/// Open short positions for a session, empty if the session never opted
+/// into `allow_short`.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/shorts", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<ShortPosition>)))]
+pub async fn get_session_shorts(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ShortPosition>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let ledger = state.short_ledger(id)?;
+    Ok(Json(ledger.positions()))
+}
+
+/// Per-symbol net positions for a session, with average entry price and
+/// unrealized PnL marked at the session's current simulated time. See
+/// [`Position`]'s doc comment for why this reports the same data as
+/// `GET /shorts`, just reshaped into net-position terms, rather than a
+/// true net position spanning both sides of the book.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/positions", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<Position>)))]
+pub async fn get_session_positions(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Position>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    Ok(Json(state.positions(id)?))
+}
+
+/// Orders placed/canceled/filled per simulated hour, so a caller can
+/// sanity-check a bot isn't massively over-trading relative to what it
+/// expected before looking at PnL. Empty for every session today, since
+/// no order-placement endpoint exists yet to feed
+/// [`crate::orders::OrderRateTracker`] — see its doc comment.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/order-rate", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<OrderRateBucket>)))]
+pub async fn get_session_order_rate(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<OrderRateBucket>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    Ok(Json(state.order_rate_tracker().snapshot(id)))
+}
+
+/// Rolling VWAP, realized volatility and volume profile for `symbol`,
+/// folded in incrementally by `ReplayService` as the session replays (see
+/// `crate::analytics::AnalyticsTracker`). Zero-valued if the session
+/// hasn't emitted any klines for `symbol` yet — never an error, since
+/// `symbol` isn't validated against the session's `symbols`/`watchlist`
+/// the way no other market-data endpoint in this tree validates it either.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/analytics/{symbol}", tag = "sessions",
+    params(("id" = Uuid, Path), ("symbol" = String, Path)), responses((status = 200, body = SymbolAnalytics)))]
+pub async fn get_session_analytics(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path((id, symbol)): Path<(Uuid, String)>,
+) -> Result<Json<SymbolAnalytics>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let tracker = state.analytics_tracker(id)?;
+    Ok(Json(tracker.snapshot(&Symbol::new(symbol))))
+}
+
+/// Throughput and ETA for `id`'s dataset backfill (see
+/// `services::dataset::spawn_backfill`), if `POST /api/v1/sessions` had to
+/// auto-ingest any symbols for this session. Zero-valued if no backfill
+/// has run — never an error, same as `get_session_analytics` before any
+/// klines have landed.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/dataset/progress", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = DatasetProgress)))]
+pub async fn get_session_dataset_progress(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DatasetProgress>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let tracker = state.dataset_progress_tracker(id)?;
+    Ok(Json(tracker.snapshot()))
+}
+
+/// Every ingestion record `id` has triggered, including resumes, with each
+/// one's [`IngestionRecord::content_hash`] — the dataset versions this
+/// session actually replayed against, so a result can be traced back to
+/// the exact data it ran with rather than just "this symbol/interval/range".
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/datasets", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<IngestionRecord>)))]
+pub async fn get_session_datasets(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<IngestionRecord>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    Ok(Json(state.ingestion_ledger().for_session(id)))
+}
+
+/// Every websocket connection this session's stream has seen so far,
+/// oldest first, for diagnosing flaky consumers after the fact — see
+/// `ws_stats`. Connect/disconnect timestamps are simulated time
+/// (`Session::current_time`), not wall-clock time.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/ws-stats", tag = "sessions",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<WsConnectionRecord>)))]
+pub async fn get_session_ws_stats(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<WsConnectionRecord>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let tracker = state.ws_stats_tracker(id)?;
+    Ok(Json(tracker.history()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SessionSummaryQuery {
+    /// `"json"` (default) or `"html"` for the rendered email-style view.
+    pub format: Option<String>,
+}
+
+/// The session's end-of-run statement, generated once when the session
+/// transitioned to `Ended` (see `AppState::set_status`). 400 if the
+/// session exists but hasn't ended yet.
+#[utoipa::path(get, path = "/api/v1/sessions/{id}/summary", tag = "sessions",
+    params(("id" = Uuid, Path), ("format" = Option<String>, Query)),
+    responses((status = 200, body = SessionSummary)))]
+pub async fn get_session_summary(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SessionSummaryQuery>,
+) -> Result<Response, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let summary = state.summary(id)?;
+    match query.format.as_deref() {
+        Some("html") => Ok(Html(render_summary_html(&summary)).into_response()),
+        _ => Ok(Json(summary).into_response()),
+    }
+}
+
+/// A minimal, self-contained HTML rendering of `summary`, in the spirit of
+/// an old-fashioned email receipt rather than a full dashboard — there's no
+/// templating engine in this tree, so this is hand-assembled the same way
+/// `oas::swagger_page` builds its page.
+fn render_summary_html(summary: &SessionSummary) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><title>Session {session_id} summary</title></head>
+<body>
+<h1>Session statement</h1>
+<p>Session: {session_id}</p>
+<p>Generated at simulated time: {generated_at}</p>
+<table border="1" cellpadding="4" cellspacing="0">
+<tr><th>Events replayed</th><td>{event_count}</td></tr>
+<tr><th>Trades replayed</th><td>{trade_count}</td></tr>
+<tr><th>Total volume</th><td>{total_volume}</td></tr>
+<tr><th>Total fees</th><td>{total_fees}</td></tr>
+<tr><th>Open borrows</th><td>{open_borrows}</td></tr>
+<tr><th>Open shorts</th><td>{open_shorts}</td></tr>
+</table>
+</body>
+</html>"#,
+        session_id = summary.session_id,
+        generated_at = summary.generated_at,
+        event_count = summary.event_count,
+        trade_count = summary.trade_count,
+        total_volume = summary.total_volume,
+        total_fees = summary.total_fees,
+        open_borrows = summary.open_borrows.len(),
+        open_shorts = summary.open_shorts.len(),
+    )
+}
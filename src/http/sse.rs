@@ -0,0 +1,46 @@
+//! SSE alternative to the session websocket stream for clients behind
+//! proxies that strip `Upgrade` headers. Emits the same [`MarketEvent`]
+//! payloads as the websocket, off the same [`SessionBroadcaster`].
+
+use axum::extract::{Extension, Path, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::stream::{self, Stream};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use crate::domain::MarketEvent;
+use crate::error::ApiError;
+use crate::http::tenancy::NamespaceId;
+use crate::state::AppState;
+
+pub async fn stream_session(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>>, ApiError> {
+    state.get_session_scoped(id, namespace_id)?;
+    let broadcaster = state.broadcaster(id)?;
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+
+    let backlog = last_event_id
+        .and_then(|last_seq| broadcaster.events_since(last_seq))
+        .unwrap_or_default();
+
+    let live = BroadcastStream::new(broadcaster.subscribe()).filter_map(|r| r.ok());
+    let stream = stream::iter(backlog).chain(live).map(to_sse_event);
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn to_sse_event(event: MarketEvent) -> Result<Event, std::convert::Infallible> {
+    let seq = event.seq;
+    let data = serde_json::to_string(&event).unwrap_or_default();
+    Ok(Event::default().id(seq.to_string()).data(data))
+}
@@ -0,0 +1,45 @@
+//! Token-to-namespace enforcement for the v1 surface. Applied as a
+//! `route_layer` over every v1 route except the bootstrap namespace/token
+//! endpoints in [`crate::http::namespaces`], which have to stay open or
+//! nothing could ever mint a first token.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+
+use crate::domain::TokenRole;
+use crate::error::ApiError;
+use crate::state::AppState;
+
+pub const HEADER_NAME: &str = "x-api-token";
+
+/// The namespace a request's `X-Api-Token` resolved to, inserted as a
+/// request extension by [`require_token`] and read back out by handlers
+/// via `Extension<NamespaceId>`.
+#[derive(Debug, Clone, Copy)]
+pub struct NamespaceId(pub Uuid);
+
+pub(crate) async fn require_token(State(state): State<AppState>, mut req: Request, next: Next) -> Result<Response, ApiError> {
+    let token = req
+        .headers()
+        .get(HEADER_NAME)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(ApiError::Unauthorized)?;
+    let (namespace_id, role) = state.resolve_token(token).ok_or(ApiError::Unauthorized)?;
+    req.extensions_mut().insert(NamespaceId(namespace_id));
+    req.extensions_mut().insert(role);
+    Ok(next.run(req).await)
+}
+
+/// Rejects with `ApiError::Forbidden` unless `role` meets `minimum`. Called
+/// at the top of handlers that need more than `TokenRole::Viewer` — there's
+/// no per-route way to express a minimum role with `middleware::from_fn`
+/// without capturing it per call site, so the check lives inline instead.
+pub(crate) fn require_role(role: TokenRole, minimum: TokenRole) -> Result<(), ApiError> {
+    if role >= minimum {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden)
+    }
+}
@@ -0,0 +1,43 @@
+use axum::extract::State;
+use axum::Json;
+use serde::Serialize;
+
+use crate::http::mappers::{format_price, format_qty};
+use crate::state::AppState;
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ExchangeInfoResponse {
+    pub symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub tick_size: String,
+    pub step_size: String,
+}
+
+/// `GET /api/v3/exchangeInfo` - registered symbols and their price/qty
+/// filters, the precision every other v3 response is formatted against.
+/// Reads through [`AppState::cached_symbol_filters`] rather than
+/// `all_symbol_filters` directly, so a UI polling this on every page load
+/// isn't rebuilding the symbol list from scratch every time.
+#[utoipa::path(get, path = "/api/v3/exchangeInfo", tag = "market",
+    responses((status = 200, body = ExchangeInfoResponse)))]
+pub async fn exchange_info(State(state): State<AppState>) -> Json<ExchangeInfoResponse> {
+    let symbols = state
+        .cached_symbol_filters()
+        .into_iter()
+        .map(|(symbol, filters)| SymbolInfo {
+            symbol: symbol.to_string(),
+            base_asset: filters.base_asset.clone(),
+            quote_asset: filters.quote_asset.clone(),
+            tick_size: format_price(filters.tick_size, &filters),
+            step_size: format_qty(filters.step_size, &filters),
+        })
+        .collect();
+
+    Json(ExchangeInfoResponse { symbols })
+}
@@ -0,0 +1,21 @@
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ServerTime {
+    #[schema(example = 1_700_000_000_000i64)]
+    pub server_time: i64,
+}
+
+/// `GET /api/v3/ping` - connectivity check, matching Binance's empty-object reply.
+#[utoipa::path(get, path = "/api/v3/ping", tag = "market", responses((status = 200, body = Value)))]
+pub async fn ping() -> Json<Value> {
+    Json(json!({}))
+}
+
+/// `GET /api/v3/time` - current server time in epoch milliseconds.
+#[utoipa::path(get, path = "/api/v3/time", tag = "market", responses((status = 200, body = ServerTime)))]
+pub async fn server_time() -> Json<Value> {
+    Json(json!({ "serverTime": chrono::Utc::now().timestamp_millis() }))
+}
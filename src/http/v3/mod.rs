@@ -0,0 +1,57 @@
+//! Binance-compatible REST surface (`/api/v3/...`). Deliberately mirrors
+//! Binance's own endpoint shapes and field names so bots can integrate
+//! with minimal changes; see the compatibility notes in the project README.
+//!
+//! There's no order placement endpoint here yet, and no session-scoping at
+//! all — every route below is global (`exchangeInfo` lists every
+//! registered symbol regardless of caller, and `ticker::ticker_24hr`
+//! reports on whatever any session has produced), unlike the v1 surface
+//! where a session id in the path scopes the request
+//! (`http::tenancy::require_token` resolves a token to a namespace, not a
+//! session). So there's nowhere today to reject a `symbol` that isn't in a
+//! session's tradable set with Binance's `-1121 Invalid symbol` for that
+//! reason — `ticker_24hr` only returns it when no kline has ever been
+//! observed for the symbol at all: once a v3 order endpoint exists and
+//! takes a session id (or a token resolves to one), that validation
+//! belongs there, checked against `Session::symbols`/`Session::watchlist`
+//! (`domain::session`) rather than the hardcoded "does it exist in
+//! `exchangeInfo`" check a global surface would otherwise default to.
+//!
+//! There's also no `/api/v3` websocket here at all - every stream this
+//! tree actually has lives under the session-scoped v1 surface
+//! (`http::ws::ws_session`), which already requires a valid namespace
+//! token for every frame regardless of mode, so there's no "unsigned"
+//! variant of it to lock down further. `user_data_stream`'s three
+//! endpoints are the one place this module requires Binance's
+//! `X-MBX-APIKEY` header rather than staying open - see that module's doc
+//! comment for why a listenKey minted there has nothing to actually gate
+//! yet.
+
+pub mod exchange_info;
+pub mod general;
+pub mod system;
+pub mod ticker;
+pub mod trades;
+pub mod user_data_stream;
+
+use axum::Router;
+
+use crate::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/ping", axum::routing::get(general::ping))
+        .route("/time", axum::routing::get(general::server_time))
+        .route("/exchangeInfo", axum::routing::get(exchange_info::exchange_info))
+        .route("/system/status", axum::routing::get(system::system_status))
+        .route("/ticker/24hr", axum::routing::get(ticker::ticker_24hr))
+        .route("/avgPrice", axum::routing::get(ticker::avg_price))
+        .route("/historicalTrades", axum::routing::get(trades::historical_trades))
+        .route("/aggTrades", axum::routing::get(trades::agg_trades))
+        .route(
+            "/userDataStream",
+            axum::routing::post(user_data_stream::create_listen_key)
+                .put(user_data_stream::keepalive_listen_key)
+                .delete(user_data_stream::close_listen_key),
+        )
+}
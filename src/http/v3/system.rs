@@ -0,0 +1,19 @@
+use axum::extract::State;
+use axum::Json;
+use serde::Serialize;
+
+use crate::state::AppState;
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct SystemStatus {
+    /// `0` = normal, `1` = maintenance, matching Binance's `sapi/v1/system/status`.
+    pub status: u8,
+}
+
+#[utoipa::path(get, path = "/api/v3/system/status", tag = "market",
+    responses((status = 200, body = SystemStatus)))]
+pub async fn system_status(State(state): State<AppState>) -> Json<SystemStatus> {
+    Json(SystemStatus {
+        status: if state.is_maintenance() { 1 } else { 0 },
+    })
+}
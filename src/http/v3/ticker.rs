@@ -0,0 +1,91 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+
+use crate::domain::{AvgPrice, Symbol, Ticker24hr};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Binance's own default for `AVG_PRICE_MINS` when the caller doesn't pass one.
+const DEFAULT_AVG_PRICE_MINS: i64 = 5;
+
+#[derive(Debug, Deserialize)]
+pub struct TickerQuery {
+    pub symbol: Option<String>,
+    /// Either Binance's own JSON-array-encoded string (e.g.
+    /// `symbols=["BTCUSDT","ETHUSDT"]`) or a plain comma-separated list,
+    /// whichever the caller already has on hand.
+    pub symbols: Option<String>,
+}
+
+/// `GET /api/v3/ticker/24hr` - rolling 24h stats per symbol (see
+/// [`crate::ticker::TickerTracker`]). `symbol` or `symbols` scopes the
+/// result to those symbols; with neither set, every symbol observed so far
+/// comes back. Always an array, even for a single `symbol` - unlike
+/// Binance's single-object shorthand in that case - since nothing else on
+/// this surface's response shapes are byte-exact either (see
+/// `exchange_info::SymbolInfo`'s snake_case fields). Global like the rest
+/// of `http::v3`; see that module's doc comment on why there's no
+/// session-scoped variant.
+#[utoipa::path(get, path = "/api/v3/ticker/24hr", tag = "market",
+    params(("symbol" = Option<String>, Query), ("symbols" = Option<String>, Query)),
+    responses((status = 200, body = Vec<Ticker24hr>)))]
+pub async fn ticker_24hr(
+    State(state): State<AppState>,
+    Query(query): Query<TickerQuery>,
+) -> Result<Json<Vec<Ticker24hr>>, ApiError> {
+    let tracker = state.ticker_tracker();
+
+    if let Some(symbol) = query.symbol {
+        let symbol = Symbol::new(symbol);
+        let ticker = tracker
+            .snapshot(&symbol)
+            .ok_or_else(|| ApiError::BadRequest(format!("-1121 Invalid symbol: no data observed yet for {symbol}")))?;
+        return Ok(Json(vec![ticker]));
+    }
+
+    let symbols = match query.symbols {
+        Some(raw) => parse_symbols(&raw).into_iter().map(Symbol::new).collect(),
+        None => tracker.symbols(),
+    };
+
+    let tickers = symbols.iter().filter_map(|symbol| tracker.snapshot(symbol)).collect();
+    Ok(Json(tickers))
+}
+
+/// Accepts Binance's `["BTCUSDT","ETHUSDT"]` form or a plain
+/// `BTCUSDT,ETHUSDT` list.
+fn parse_symbols(raw: &str) -> Vec<String> {
+    if let Ok(parsed) = serde_json::from_str::<Vec<String>>(raw) {
+        return parsed;
+    }
+    raw.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvgPriceQuery {
+    pub symbol: String,
+    /// Defaults to Binance's own `AVG_PRICE_MINS` of 5, unlike the fixed
+    /// exchange-wide filter Binance itself uses — configurable here since
+    /// this tree has no per-symbol filter config to hang a fixed value off.
+    pub mins: Option<i64>,
+}
+
+/// `GET /api/v3/avgPrice` - volume-weighted average price over the
+/// trailing `mins` minutes (default 5) of replayed klines for `symbol`.
+/// See [`crate::ticker::TickerTracker::avg_price`].
+#[utoipa::path(get, path = "/api/v3/avgPrice", tag = "market",
+    params(("symbol" = String, Query), ("mins" = Option<i64>, Query)),
+    responses((status = 200, body = AvgPrice)))]
+pub async fn avg_price(
+    State(state): State<AppState>,
+    Query(query): Query<AvgPriceQuery>,
+) -> Result<Json<AvgPrice>, ApiError> {
+    let symbol = Symbol::new(query.symbol);
+    let mins = query.mins.unwrap_or(DEFAULT_AVG_PRICE_MINS);
+    let price = state
+        .ticker_tracker()
+        .avg_price(&symbol, mins)
+        .ok_or_else(|| ApiError::BadRequest(format!("-1121 Invalid symbol: no data observed yet for {symbol}")))?;
+    Ok(Json(AvgPrice { mins, price }))
+}
@@ -0,0 +1,88 @@
+//! Public trade history, read straight off the global `store::TradeStore`
+//! — like the rest of `http::v3`, not session-scoped (see the module doc
+//! comment on that). Nothing here needs truncating against "now": trades
+//! only ever land in the store as a session replays them, so there's never
+//! a future trade sitting there to hide.
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+
+use crate::domain::{Symbol, Trade};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+const DEFAULT_LIMIT: usize = 500;
+const MAX_LIMIT: usize = 1_000;
+
+#[derive(Debug, Deserialize)]
+pub struct HistoricalTradesQuery {
+    pub symbol: String,
+    #[serde(rename = "fromId")]
+    pub from_id: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+fn trades_for(state: &AppState, symbol: &str, from_id: Option<i64>, limit: Option<usize>) -> Vec<Trade> {
+    let symbol = Symbol::new(symbol);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    match from_id {
+        Some(from_id) => state.trade_store().trades_by_id(&symbol, from_id, limit),
+        None => state.trade_store().latest_trades(&symbol, limit),
+    }
+}
+
+/// `GET /api/v3/historicalTrades` - older trades for `symbol`, paginated
+/// with `fromId`/`limit` (default 500, max 1000); without `fromId`, the
+/// most recent `limit` trades.
+#[utoipa::path(get, path = "/api/v3/historicalTrades", tag = "market",
+    params(("symbol" = String, Query), ("fromId" = Option<i64>, Query), ("limit" = Option<usize>, Query)),
+    responses((status = 200, body = Vec<Trade>)))]
+pub async fn historical_trades(
+    State(state): State<AppState>,
+    Query(query): Query<HistoricalTradesQuery>,
+) -> Result<Json<Vec<Trade>>, ApiError> {
+    Ok(Json(trades_for(&state, &query.symbol, query.from_id, query.limit)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AggTradesQuery {
+    pub symbol: String,
+    #[serde(rename = "fromId")]
+    pub from_id: Option<i64>,
+    #[serde(rename = "startTime")]
+    pub start_time: Option<i64>,
+    #[serde(rename = "endTime")]
+    pub end_time: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// `GET /api/v3/aggTrades` - aggregated trade prints for `symbol`. `fromId`
+/// pages by id the same way `historicalTrades` does; `startTime`/`endTime`
+/// instead scopes by `trade_time` (see [`crate::store::TradeStore::trades`]);
+/// with none of the three, the most recent `limit` trades.
+#[utoipa::path(get, path = "/api/v3/aggTrades", tag = "market",
+    params(
+        ("symbol" = String, Query),
+        ("fromId" = Option<i64>, Query),
+        ("startTime" = Option<i64>, Query),
+        ("endTime" = Option<i64>, Query),
+        ("limit" = Option<usize>, Query)
+    ),
+    responses((status = 200, body = Vec<Trade>)))]
+pub async fn agg_trades(
+    State(state): State<AppState>,
+    Query(query): Query<AggTradesQuery>,
+) -> Result<Json<Vec<Trade>>, ApiError> {
+    let symbol = Symbol::new(&query.symbol);
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    if let (Some(start), end) = (query.start_time, query.end_time) {
+        let end = end.unwrap_or(i64::MAX);
+        let mut trades = state.trade_store().trades(&symbol, start, end);
+        trades.truncate(limit);
+        return Ok(Json(trades));
+    }
+
+    Ok(Json(trades_for(&state, &query.symbol, query.from_id, Some(limit))))
+}
@@ -0,0 +1,99 @@
+//! `POST`/`PUT`/`DELETE /api/v3/userDataStream` - listenKey issuance,
+//! keepalive, and revocation (see [`crate::listen_key::ListenKeyRegistry`]
+//! for why nothing actually streams against one yet). Unlike the rest of
+//! `http::v3`, which is intentionally open the same way `ticker_24hr` is
+//! (see this module's parent doc comment), these three endpoints require
+//! the `X-MBX-APIKEY` header Binance itself requires here, resolved
+//! against the same namespace tokens `http::tenancy::require_token` checks
+//! on the v1 surface - the closest thing this tree has to Binance's
+//! HMAC/API-key signed-endpoint split.
+//!
+//! This only covers the REST half of "WS authentication parity: reject
+//! unauthenticated streams when signed mode is on" - `http::v3` has no
+//! websocket route at all (see its module doc), so there is nothing here
+//! that rejects an unauthenticated *stream* or closes one with a
+//! Binance-style close code. `http::ws::ws_session` is the tree's one real
+//! websocket and already requires a v1 namespace token independent of
+//! this registry. Until a v3 websocket surface exists to check a listenKey
+//! against, this is bookkeeping ahead of its consumer, not a tested
+//! end-to-end auth path.
+
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Resolves the caller's namespace from the `X-MBX-APIKEY` header, the
+/// same way `http::tenancy::require_token` resolves `X-Api-Token` for the
+/// v1 surface - there's no `route_layer` doing this uniformly here since
+/// every other `http::v3` route stays deliberately open.
+fn resolve_api_key(state: &AppState, headers: &HeaderMap) -> Result<Uuid, ApiError> {
+    let token = headers
+        .get("x-mbx-apikey")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(ApiError::Unauthorized)?;
+    state.resolve_token(token).map(|(namespace_id, _)| namespace_id).ok_or(ApiError::Unauthorized)
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ListenKeyResponse {
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// `POST /api/v3/userDataStream` - mints a new listenKey for the caller's
+/// namespace.
+#[utoipa::path(post, path = "/api/v3/userDataStream", tag = "market",
+    responses((status = 200, body = ListenKeyResponse), (status = 401, description = "missing or invalid X-MBX-APIKEY")))]
+pub async fn create_listen_key(State(state): State<AppState>, headers: HeaderMap) -> Result<Json<ListenKeyResponse>, ApiError> {
+    let namespace_id = resolve_api_key(&state, &headers)?;
+    let listen_key = state.listen_keys().issue(namespace_id, chrono::Utc::now().timestamp_millis());
+    Ok(Json(ListenKeyResponse { listen_key }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListenKeyQuery {
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// `PUT /api/v3/userDataStream` - resets `listenKey`'s TTL clock. Binance
+/// replies with an empty object on success; a `listenKey` that doesn't
+/// belong to the caller's namespace (or doesn't exist at all) is
+/// rejected the same way an unrecognized one is on real Binance.
+#[utoipa::path(put, path = "/api/v3/userDataStream", tag = "market",
+    params(("listenKey" = String, Query)),
+    responses((status = 200, description = "keepalive accepted"), (status = 400, description = "unknown listenKey")))]
+pub async fn keepalive_listen_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListenKeyQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let namespace_id = resolve_api_key(&state, &headers)?;
+    let renewed = state.listen_keys().renew(&query.listen_key, namespace_id, chrono::Utc::now().timestamp_millis());
+    if !renewed {
+        return Err(ApiError::BadRequest("-1125 This listenKey does not exist".into()));
+    }
+    Ok(Json(serde_json::json!({})))
+}
+
+/// `DELETE /api/v3/userDataStream` - revokes `listenKey` outright.
+#[utoipa::path(delete, path = "/api/v3/userDataStream", tag = "market",
+    params(("listenKey" = String, Query)),
+    responses((status = 200, description = "revoked"), (status = 400, description = "unknown listenKey")))]
+pub async fn close_listen_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListenKeyQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let namespace_id = resolve_api_key(&state, &headers)?;
+    let revoked = state.listen_keys().revoke(&query.listen_key, namespace_id);
+    if !revoked {
+        return Err(ApiError::BadRequest("-1125 This listenKey does not exist".into()));
+    }
+    Ok(Json(serde_json::json!({})))
+}
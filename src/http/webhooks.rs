@@ -0,0 +1,55 @@
+use axum::extract::{Extension, Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{TokenRole, WebhookConfig, WebhookDelivery};
+use crate::error::ApiError;
+use crate::http::tenancy::{self, NamespaceId};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RegisterWebhookRequest {
+    pub session_id: Uuid,
+    #[serde(flatten)]
+    pub config: WebhookConfig,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RegisterWebhookResponse {
+    pub id: Uuid,
+}
+
+/// Registers a webhook subscription for a session, scoped to the caller's
+/// namespace. The session doesn't have to exist yet — it just won't
+/// receive anything until a matching event actually fires for it.
+#[utoipa::path(post, path = "/api/v1/webhooks", tag = "webhooks",
+    request_body = RegisterWebhookRequest, responses((status = 200, body = RegisterWebhookResponse)))]
+pub async fn register_webhook(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Extension(role): Extension<TokenRole>,
+    Json(req): Json<RegisterWebhookRequest>,
+) -> Result<Json<RegisterWebhookResponse>, ApiError> {
+    tenancy::require_role(role, TokenRole::Operator)?;
+    let id = state.webhooks().register(req.session_id, namespace_id, req.config);
+    Ok(Json(RegisterWebhookResponse { id }))
+}
+
+/// Delivery log for a webhook, oldest first, so CI pipelines can audit
+/// whether an event actually reached them instead of trusting a
+/// fire-and-forget POST. Scoped to the caller's namespace the same way
+/// sessions are.
+#[utoipa::path(get, path = "/api/v1/webhooks/{id}/deliveries", tag = "webhooks",
+    params(("id" = Uuid, Path)), responses((status = 200, body = Vec<WebhookDelivery>)))]
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<WebhookDelivery>>, ApiError> {
+    state
+        .webhooks()
+        .deliveries(id, namespace_id)
+        .map(Json)
+        .ok_or(ApiError::WebhookNotFound(id))
+}
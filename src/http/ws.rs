@@ -0,0 +1,474 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Extension, Path, Query, State};
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::broadcaster::SessionBroadcaster;
+use crate::domain::{ChaosGenerator, Decimal, MarketEvent, MarketEventPayload, Session, SessionStatus, Symbol, Ticker24hr};
+use crate::error::ApiError;
+use crate::http::tenancy::NamespaceId;
+use crate::state::AppState;
+use crate::ws_stats::WsStatsTracker;
+
+/// At high replay speeds a session can emit hundreds of events per second;
+/// sending one websocket frame per event dominates with per-message
+/// overhead. Setting either knob opts into flushing accumulated events as a
+/// single JSON array frame once `batch_events` have queued or `batch_ms`
+/// have elapsed, whichever comes first. Leaving both unset preserves the
+/// original one-frame-per-event behavior.
+#[derive(Debug, Deserialize)]
+pub struct WsBatchParams {
+    pub batch_events: Option<usize>,
+    pub batch_ms: Option<u64>,
+    /// Opts into `order`/`fill`/`balance` events alongside the regular
+    /// market data. Defaults to `false` so existing clients that only
+    /// expect `kline`/`trade`/`mark_price` frames keep seeing exactly
+    /// that. As of this writing nothing publishes those event kinds —
+    /// there's no `OrdersService`/`SpotMatcher` in this tree to change
+    /// state in the first place (see `orders`' and `matching`'s module
+    /// docs, and [`crate::domain::MarketEventPayload::Order`]) — so
+    /// setting this to `true` today changes nothing observable. The flag
+    /// is real, not a stub: once a pipeline starts publishing those
+    /// variants, this filter starts doing something without another
+    /// client-facing change.
+    #[serde(default)]
+    pub include_orders: bool,
+    /// Opts into an extra frame, emitted once per simulated second,
+    /// carrying every one of the session's symbols' tickers in a single
+    /// message — this tree's analog of Binance's combined `!ticker@arr`
+    /// / `!bookTicker` all-market streams, for screener/scanner clients
+    /// that watch dozens of symbols and don't want a subscription per
+    /// symbol. See [`AggTickerFrame`]/[`AggBookTickerFrame`].
+    #[serde(default)]
+    pub agg_ticker: bool,
+}
+
+/// True for the event kinds `include_orders` gates.
+fn is_order_lifecycle_event(event: &MarketEvent) -> bool {
+    matches!(
+        event.payload,
+        MarketEventPayload::Order(_) | MarketEventPayload::Fill(_) | MarketEventPayload::Balance(_)
+    )
+}
+
+/// Effectively "never" for the periodic flush tick when only an event-count
+/// threshold was negotiated; a session outlives this in practice.
+const NO_TIME_FLUSH: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// How often the socket checks session/server lifecycle state between
+/// events, to decide whether it should close and with which code.
+const LIFECYCLE_POLL: Duration = Duration::from_millis(500);
+
+/// How often a heartbeat frame goes out regardless of market activity, so a
+/// client can tell "the session is paused/idle" apart from "the connection
+/// died" — there's no separate `/api/v3` streaming surface in this tree
+/// (`http::v3` is REST-only), so this session socket is the one place a
+/// heartbeat like this applies.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A periodic liveness frame carrying the session's current simulated time
+/// and the sequence number of the last market event actually delivered on
+/// this socket (`None` if none has gone out yet), sent on
+/// [`HEARTBEAT_INTERVAL`] independent of the event stream. A client that
+/// stops seeing these can conclude the connection itself is dead, while
+/// one that keeps seeing them with an unmoving `simulated_time` knows the
+/// session is merely paused rather than stalled.
+#[derive(Serialize)]
+struct HeartbeatNotice {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    simulated_time: i64,
+    last_seq: Option<u64>,
+}
+
+/// Sends a heartbeat frame. Returns `false` if the socket is gone.
+async fn send_heartbeat(socket: &mut WebSocket, simulated_time: i64, last_seq: Option<u64>) -> bool {
+    let notice = HeartbeatNotice { kind: "heartbeat", simulated_time, last_seq };
+    let Ok(payload) = serde_json::to_string(&notice) else {
+        return true;
+    };
+    socket.send(Message::Text(payload)).await.is_ok()
+}
+
+/// `!ticker@arr` analog: every tracked symbol's rolling 24hr ticker, from
+/// the same global `crate::ticker::TickerTracker` `v3::ticker::ticker_24hr`
+/// reads from, scoped down to the symbols this session actually streams.
+#[derive(Serialize)]
+struct AggTickerFrame {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    tickers: Vec<Ticker24hr>,
+}
+
+/// `!bookTicker` analog. This tree has no order book anywhere — there's no
+/// bid/ask spread to report (see `orders`' and `matching`'s module docs) —
+/// so `bid_price`/`ask_price` both fall back to the symbol's last traded
+/// price, the same approximation [`crate::matching::LastPriceMatcher`]
+/// uses for fills; `bid_qty`/`ask_qty` are always `0` since there's no
+/// depth to size them against.
+#[derive(Serialize)]
+struct BookTickerSnapshot {
+    symbol: Symbol,
+    bid_price: Decimal,
+    bid_qty: Decimal,
+    ask_price: Decimal,
+    ask_qty: Decimal,
+}
+
+#[derive(Serialize)]
+struct AggBookTickerFrame {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    tickers: Vec<BookTickerSnapshot>,
+}
+
+/// Builds and sends the combined ticker/bookTicker frames for `symbols` if
+/// any of them has a ticker snapshot yet, then returns the simulated
+/// second `current_time` falls in so the caller can skip re-sending until
+/// it advances again. Returns `None` (without sending anything) if the
+/// socket is gone.
+async fn send_agg_ticker(socket: &mut WebSocket, state: &AppState, symbols: &[Symbol], current_time: i64) -> Option<i64> {
+    let tracker = state.ticker_tracker();
+    let tickers: Vec<Ticker24hr> = symbols.iter().filter_map(|symbol| tracker.snapshot(symbol)).collect();
+    if !tickers.is_empty() {
+        let book_tickers = tickers
+            .iter()
+            .map(|ticker| BookTickerSnapshot {
+                symbol: ticker.symbol.clone(),
+                bid_price: ticker.last_price,
+                bid_qty: Decimal::ZERO,
+                ask_price: ticker.last_price,
+                ask_qty: Decimal::ZERO,
+            })
+            .collect();
+
+        let ticker_frame = AggTickerFrame { kind: "ticker_arr", tickers };
+        let book_frame = AggBookTickerFrame { kind: "book_ticker_arr", tickers: book_tickers };
+        if let Ok(payload) = serde_json::to_string(&ticker_frame) {
+            socket.send(Message::Text(payload)).await.ok()?;
+        }
+        if let Ok(payload) = serde_json::to_string(&book_frame) {
+            socket.send(Message::Text(payload)).await.ok()?;
+        }
+    }
+    Some(current_time / 1000)
+}
+
+/// `symbols ∪ watchlist`, the set an aggregate ticker frame covers —
+/// mirrors what the session already streams individual `kline`/`trade`
+/// events for.
+fn agg_ticker_symbols(session: &Session) -> Vec<Symbol> {
+    session.symbols.iter().chain(session.watchlist.iter()).cloned().collect()
+}
+
+/// Documented close-code policy: distinct codes per reason so a client can
+/// tell "the session is done, don't reconnect" apart from "this node is
+/// restarting, retry me" instead of guessing from a bare 1008/1000 split.
+/// `4004` is an application-defined code (the 4000-4999 range is reserved
+/// for that by RFC 6455) since no standard code means "resource deleted".
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CloseReason {
+    Ended,
+    Stopped,
+    Disabled,
+    Deleted,
+    ServerShutdown,
+}
+
+impl CloseReason {
+    fn code(self) -> u16 {
+        match self {
+            CloseReason::Ended => 1000,
+            CloseReason::Stopped => 1001,
+            CloseReason::Disabled => 1008,
+            CloseReason::Deleted => 4004,
+            CloseReason::ServerShutdown => 1012,
+        }
+    }
+
+    fn reconnect(self) -> bool {
+        matches!(self, CloseReason::Disabled | CloseReason::ServerShutdown)
+    }
+}
+
+#[derive(Serialize)]
+struct CloseNotice {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    reason: CloseReason,
+    reconnect: bool,
+}
+
+pub async fn ws_session(
+    State(state): State<AppState>,
+    Extension(NamespaceId(namespace_id)): Extension<NamespaceId>,
+    Path(id): Path<Uuid>,
+    Query(batch): Query<WsBatchParams>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let broadcaster = state.broadcaster(id)?;
+    let session = state.get_session_scoped(id, namespace_id)?;
+    let chaos = session.chaos.map(ChaosGenerator::new);
+    let stats = state.ws_stats_tracker(id)?;
+    let connection = stats.open(session.current_time);
+    let agg_ticker_symbols = agg_ticker_symbols(&session);
+    Ok(ws.on_upgrade(move |socket| async move {
+        handle_socket(socket, state.clone(), id, broadcaster, batch, chaos, &stats, connection, agg_ticker_symbols).await;
+        let disconnected_at = state.get_session(id).map(|s| s.current_time).unwrap_or(session.current_time);
+        stats.close(connection, disconnected_at);
+    }))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_socket(
+    mut socket: WebSocket,
+    state: AppState,
+    session_id: Uuid,
+    broadcaster: Arc<SessionBroadcaster>,
+    batch: WsBatchParams,
+    mut chaos: Option<ChaosGenerator>,
+    stats: &WsStatsTracker,
+    connection: usize,
+    agg_ticker_symbols: Vec<Symbol>,
+) {
+    let mut rx = broadcaster.subscribe();
+    let mut lifecycle = tokio::time::interval(LIFECYCLE_POLL);
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut last_seq: Option<u64> = None;
+    let mut last_agg_second: Option<i64> = None;
+
+    if batch.batch_events.is_none() && batch.batch_ms.is_none() {
+        let mut held: Option<MarketEvent> = None;
+        loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Ok(event) => {
+                        if !batch.include_orders && is_order_lifecycle_event(&event) {
+                            continue;
+                        }
+                        let seq = event.seq;
+                        let delivered = match chaos.as_mut() {
+                            Some(chaos) => send_with_chaos(&mut socket, chaos, &mut held, event).await,
+                            None => send_event(&mut socket, &event).await,
+                        };
+                        if delivered {
+                            stats.record_message(connection);
+                            last_seq = Some(seq);
+                        } else {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(dropped)) => stats.record_lag_drop(connection, dropped),
+                    Err(RecvError::Closed) => return,
+                },
+                _ = lifecycle.tick() => {
+                    if batch.agg_ticker {
+                        let current_time = state.get_session(session_id).map(|s| s.current_time).unwrap_or_default();
+                        if last_agg_second != Some(current_time / 1000) {
+                            match send_agg_ticker(&mut socket, &state, &agg_ticker_symbols, current_time).await {
+                                Some(second) => last_agg_second = Some(second),
+                                None => return,
+                            }
+                        }
+                    }
+                    if let Some(reason) = termination_reason(&state, session_id) {
+                        close_with_notice(&mut socket, reason).await;
+                        return;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    let simulated_time = state.get_session(session_id).map(|s| s.current_time).unwrap_or_default();
+                    if !send_heartbeat(&mut socket, simulated_time, last_seq).await {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    let max_events = batch.batch_events.unwrap_or(usize::MAX).max(1);
+    let flush_period = batch.batch_ms.map(Duration::from_millis).unwrap_or(NO_TIME_FLUSH);
+    let mut flush = tokio::time::interval(flush_period);
+    flush.tick().await; // first tick fires immediately; nothing to flush yet
+
+    let mut pending: Vec<MarketEvent> = Vec::new();
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Ok(event) => {
+                    if !batch.include_orders && is_order_lifecycle_event(&event) {
+                        continue;
+                    }
+                    last_seq = Some(event.seq);
+                    pending.push(event);
+                    if pending.len() >= max_events {
+                        if flush_batch(&mut socket, &mut pending).await {
+                            stats.record_message(connection);
+                        } else {
+                            return;
+                        }
+                    }
+                }
+                Err(RecvError::Lagged(dropped)) => stats.record_lag_drop(connection, dropped),
+                Err(RecvError::Closed) => break,
+            },
+            _ = flush.tick() => {
+                if !pending.is_empty() {
+                    if flush_batch(&mut socket, &mut pending).await {
+                        stats.record_message(connection);
+                    } else {
+                        return;
+                    }
+                }
+            }
+            _ = lifecycle.tick() => {
+                if batch.agg_ticker {
+                    let current_time = state.get_session(session_id).map(|s| s.current_time).unwrap_or_default();
+                    if last_agg_second != Some(current_time / 1000) {
+                        match send_agg_ticker(&mut socket, &state, &agg_ticker_symbols, current_time).await {
+                            Some(second) => last_agg_second = Some(second),
+                            None => return,
+                        }
+                    }
+                }
+                if let Some(reason) = termination_reason(&state, session_id) {
+                    if !pending.is_empty() && flush_batch(&mut socket, &mut pending).await {
+                        stats.record_message(connection);
+                    }
+                    close_with_notice(&mut socket, reason).await;
+                    return;
+                }
+            }
+            _ = heartbeat.tick() => {
+                let simulated_time = state.get_session(session_id).map(|s| s.current_time).unwrap_or_default();
+                if !send_heartbeat(&mut socket, simulated_time, last_seq).await {
+                    return;
+                }
+            }
+        }
+    }
+
+    if !pending.is_empty() && flush_batch(&mut socket, &mut pending).await {
+        stats.record_message(connection);
+    }
+}
+
+/// `None` while the session is still live; otherwise the reason the socket
+/// should close, checked in priority order (shutdown and maintenance are
+/// process-wide and pre-empt whatever the individual session is doing).
+fn termination_reason(state: &AppState, session_id: Uuid) -> Option<CloseReason> {
+    if state.is_shutting_down() {
+        return Some(CloseReason::ServerShutdown);
+    }
+    if state.is_maintenance() {
+        return Some(CloseReason::Disabled);
+    }
+    match state.get_session(session_id) {
+        Err(_) => Some(CloseReason::Deleted),
+        Ok(session) => match session.status {
+            SessionStatus::Ended => Some(CloseReason::Ended),
+            SessionStatus::Stopped => Some(CloseReason::Stopped),
+            _ => None,
+        },
+    }
+}
+
+/// Sends a JSON notice frame describing why the socket is closing and
+/// whether the client should reconnect, then the websocket close frame
+/// itself with the matching code.
+async fn close_with_notice(socket: &mut WebSocket, reason: CloseReason) {
+    let notice = CloseNotice {
+        kind: "close",
+        reason,
+        reconnect: reason.reconnect(),
+    };
+    if let Ok(payload) = serde_json::to_string(&notice) {
+        let _ = socket.send(Message::Text(payload)).await;
+    }
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame {
+            code: reason.code(),
+            reason: Cow::Borrowed(close_reason_text(reason)),
+        })))
+        .await;
+}
+
+fn close_reason_text(reason: CloseReason) -> &'static str {
+    match reason {
+        CloseReason::Ended => "session ended",
+        CloseReason::Stopped => "session stopped",
+        CloseReason::Disabled => "exchange in maintenance mode",
+        CloseReason::Deleted => "session no longer exists",
+        CloseReason::ServerShutdown => "server shutting down",
+    }
+}
+
+/// Sends `pending` as one JSON array frame and clears it. Returns `false`
+/// if the socket is gone, so the caller can stop the loop.
+async fn flush_batch(socket: &mut WebSocket, pending: &mut Vec<MarketEvent>) -> bool {
+    let Ok(payload) = serde_json::to_string(&pending) else {
+        pending.clear();
+        return true;
+    };
+    pending.clear();
+    socket.send(Message::Text(payload)).await.is_ok()
+}
+
+/// Sends a single event as a JSON text frame. Returns `false` if the
+/// socket is gone.
+async fn send_event(socket: &mut WebSocket, event: &MarketEvent) -> bool {
+    let Ok(payload) = serde_json::to_string(event) else {
+        return true;
+    };
+    socket.send(Message::Text(payload)).await.is_ok()
+}
+
+/// Applies `chaos` to `event` before it reaches the wire: may drop the
+/// connection outright, delay the frame, hold it back to swap order with
+/// whichever event arrives next, or duplicate it once sent. Only used on
+/// the unbatched `/ws` path — once events are aggregated into one frame
+/// by `batch_events`/`batch_ms`, there's no single frame left for
+/// per-event chaos to act on.
+///
+/// Returns `false` if the connection should be torn down, either because
+/// chaos chose to simulate a drop or because a send actually failed.
+async fn send_with_chaos(
+    socket: &mut WebSocket,
+    chaos: &mut ChaosGenerator,
+    held: &mut Option<MarketEvent>,
+    event: MarketEvent,
+) -> bool {
+    if chaos.should_disconnect() {
+        return false;
+    }
+    if let Some(delay) = chaos.delay() {
+        tokio::time::sleep(delay).await;
+    }
+
+    let to_send = match held.take() {
+        Some(previous) if chaos.should_reorder() => vec![event, previous],
+        Some(previous) => vec![previous, event],
+        None if chaos.should_reorder() => {
+            *held = Some(event);
+            return true;
+        }
+        None => vec![event],
+    };
+
+    for event in &to_send {
+        if !send_event(socket, event).await {
+            return false;
+        }
+        if chaos.should_duplicate() && !send_event(socket, event).await {
+            return false;
+        }
+    }
+    true
+}
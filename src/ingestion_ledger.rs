@@ -0,0 +1,227 @@
+//! Tracks `services::dataset::spawn_backfill` jobs across process
+//! restarts, so a job killed mid-flight doesn't silently stay `Ingesting`
+//! forever. `services::backup` persists every record as part of its
+//! snapshot; `reconcile_stale` runs once, right after
+//! `backup::restore_if_present` loads one, flipping any record still
+//! `Ingesting` to [`IngestStatus::Resumable`] — the process that owned it
+//! is gone, so it can't actually still be running.
+//!
+//! Actually resuming a `Resumable` record needs the session it was
+//! backfilling for, and sessions aren't part of the snapshot (see
+//! `backup`'s module doc: only klines/trades are). [`resume_stale`] only
+//! resumes a record whose `session_id` still resolves in `state` when
+//! it's called, which is never true after a real process restart today —
+//! it's real, working logic the moment session persistence lands, the
+//! same kind of honest gap `services::rate_limit` documents for weight
+//! pressure it can never observe yet.
+
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use crate::domain::{IngestStatus, IngestionRecord, IngestionRun, IngestionTrigger, Symbol};
+
+pub struct IngestionLedger {
+    records: Mutex<Vec<IngestionRecord>>,
+}
+
+impl IngestionLedger {
+    pub fn new() -> Self {
+        Self { records: Mutex::new(Vec::new()) }
+    }
+
+    /// Registers a new `Ingesting` record, with its first [`IngestionRun`],
+    /// and returns its id.
+    pub fn start(&self, session_id: Uuid, symbols: Vec<Symbol>, interval: String, start: i64, end: i64) -> Uuid {
+        let id = Uuid::new_v4();
+        self.records.lock().unwrap().push(IngestionRecord {
+            id,
+            session_id,
+            symbols,
+            interval,
+            start,
+            end,
+            status: IngestStatus::Ingesting,
+            runs: vec![new_run(IngestionTrigger::SessionCreate)],
+            content_hash: None,
+        });
+        id
+    }
+
+    /// Marks `id` `Completed` and stamps `content_hash` on the record, the
+    /// way `services::dataset::spawn_backfill_job` calls this once the
+    /// synthetic rows it wrote are actually in `MarketStore`.
+    pub fn complete(&self, id: Uuid, rows_inserted: i64, content_hash: String) {
+        self.finish_run(id, IngestStatus::Completed, rows_inserted, None);
+        if let Some(record) = self.records.lock().unwrap().iter_mut().find(|record| record.id == id) {
+            record.content_hash = Some(content_hash);
+        }
+    }
+
+    pub fn fail(&self, id: Uuid, error: String) {
+        self.finish_run(id, IngestStatus::Failed, 0, Some(error));
+    }
+
+    fn finish_run(&self, id: Uuid, status: IngestStatus, rows_inserted: i64, error: Option<String>) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|record| record.id == id) {
+            record.status = status;
+            if let Some(run) = record.runs.last_mut() {
+                run.finished_at = Some(now_ms());
+                run.status = status;
+                run.rows_inserted = rows_inserted;
+                run.error = error;
+            }
+        }
+    }
+
+    /// Every ingestion record started for `session_id`, in no particular
+    /// order — the dataset versions a session actually ran against (see
+    /// [`IngestionRecord::content_hash`]).
+    pub fn for_session(&self, session_id: Uuid) -> Vec<IngestionRecord> {
+        self.records.lock().unwrap().iter().filter(|record| record.session_id == session_id).cloned().collect()
+    }
+
+    /// Flips a `Resumable` record back to `Ingesting`, reusing its id
+    /// rather than minting a new one, so its history stays one record
+    /// instead of fragmenting across a retry — but still appends a new
+    /// [`IngestionRun`], so `runs` keeps why the earlier attempt didn't
+    /// finish even once this one succeeds.
+    pub fn resume(&self, id: Uuid) {
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.iter_mut().find(|record| record.id == id) {
+            record.status = IngestStatus::Ingesting;
+            record.runs.push(new_run(IngestionTrigger::Resume));
+        }
+    }
+
+    pub fn all(&self) -> Vec<IngestionRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<IngestionRecord> {
+        self.records.lock().unwrap().iter().find(|record| record.id == id).cloned()
+    }
+
+    /// The run history for `id`, oldest first — `None` if `id` isn't a
+    /// known ingestion record.
+    pub fn runs(&self, id: Uuid) -> Option<Vec<IngestionRun>> {
+        self.records.lock().unwrap().iter().find(|record| record.id == id).map(|record| record.runs.clone())
+    }
+
+    /// Overwrites every record, for restoring from a snapshot.
+    pub fn replace_all(&self, records: Vec<IngestionRecord>) {
+        *self.records.lock().unwrap() = records;
+    }
+
+    /// Flips every `Ingesting` record to `Resumable`, closing out its
+    /// current run as abandoned rather than leaving it `finished_at: None`
+    /// forever. Returns the ids that changed.
+    pub fn reconcile_stale(&self) -> Vec<Uuid> {
+        let mut records = self.records.lock().unwrap();
+        records
+            .iter_mut()
+            .filter(|record| record.status == IngestStatus::Ingesting)
+            .map(|record| {
+                record.status = IngestStatus::Resumable;
+                if let Some(run) = record.runs.last_mut() {
+                    run.status = IngestStatus::Resumable;
+                    run.finished_at = Some(now_ms());
+                }
+                record.id
+            })
+            .collect()
+    }
+}
+
+fn new_run(triggered_by: IngestionTrigger) -> IngestionRun {
+    IngestionRun {
+        started_at: now_ms(),
+        finished_at: None,
+        status: IngestStatus::Ingesting,
+        rows_inserted: 0,
+        error: None,
+        triggered_by,
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+impl Default for IngestionLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_started_record_is_ingesting() {
+        let ledger = IngestionLedger::new();
+        let id = ledger.start(Uuid::new_v4(), vec![Symbol::new("BTCUSDT")], "1m".into(), 0, 60_000);
+        assert_eq!(ledger.all()[0].id, id);
+        assert_eq!(ledger.all()[0].status, IngestStatus::Ingesting);
+    }
+
+    #[test]
+    fn completing_a_record_updates_its_status_and_latest_run() {
+        let ledger = IngestionLedger::new();
+        let id = ledger.start(Uuid::new_v4(), vec![Symbol::new("BTCUSDT")], "1m".into(), 0, 60_000);
+        ledger.complete(id, 42, "abc123".into());
+        let record = &ledger.all()[0];
+        assert_eq!(record.status, IngestStatus::Completed);
+        assert_eq!(record.runs.len(), 1);
+        assert_eq!(record.runs[0].status, IngestStatus::Completed);
+        assert_eq!(record.runs[0].rows_inserted, 42);
+        assert!(record.runs[0].finished_at.is_some());
+    }
+
+    #[test]
+    fn failing_a_record_records_the_error_on_its_latest_run() {
+        let ledger = IngestionLedger::new();
+        let id = ledger.start(Uuid::new_v4(), vec![Symbol::new("BTCUSDT")], "1m".into(), 0, 60_000);
+        ledger.fail(id, "upstream timed out".into());
+        let record = &ledger.all()[0];
+        assert_eq!(record.status, IngestStatus::Failed);
+        assert_eq!(record.runs[0].error.as_deref(), Some("upstream timed out"));
+    }
+
+    #[test]
+    fn reconcile_stale_only_touches_ingesting_records() {
+        let ledger = IngestionLedger::new();
+        let ingesting = ledger.start(Uuid::new_v4(), vec![Symbol::new("BTCUSDT")], "1m".into(), 0, 60_000);
+        let completed = ledger.start(Uuid::new_v4(), vec![Symbol::new("ETHUSDT")], "1m".into(), 0, 60_000);
+        ledger.complete(completed, 10, "abc123".into());
+
+        let changed = ledger.reconcile_stale();
+        assert_eq!(changed, vec![ingesting]);
+        let records = ledger.all();
+        assert!(records.iter().any(|r| r.id == ingesting && r.status == IngestStatus::Resumable));
+        assert!(records.iter().any(|r| r.id == completed && r.status == IngestStatus::Completed));
+    }
+
+    #[test]
+    fn resuming_a_record_reuses_its_id_but_appends_a_new_run() {
+        let ledger = IngestionLedger::new();
+        let id = ledger.start(Uuid::new_v4(), vec![Symbol::new("BTCUSDT")], "1m".into(), 0, 60_000);
+        ledger.reconcile_stale();
+        ledger.resume(id);
+        let records = ledger.all();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, IngestStatus::Ingesting);
+        assert_eq!(records[0].runs.len(), 2);
+        assert_eq!(records[0].runs[0].status, IngestStatus::Resumable);
+        assert_eq!(records[0].runs[0].triggered_by, IngestionTrigger::SessionCreate);
+        assert_eq!(records[0].runs[1].triggered_by, IngestionTrigger::Resume);
+    }
+
+    #[test]
+    fn runs_returns_none_for_an_unknown_id() {
+        let ledger = IngestionLedger::new();
+        assert!(ledger.runs(Uuid::new_v4()).is_none());
+    }
+}
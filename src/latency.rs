@@ -0,0 +1,83 @@
+//! Runtime controller backing the `/api/v3` latency-injection middleware in
+//! [`crate::http::latency`]. Pairs a list of pure
+//! [`domain::LatencyProfile`](crate::domain::LatencyProfile)s with a seeded
+//! xorshift64* generator for the jitter component, the same generator
+//! [`domain::clock::DriftGenerator`](crate::domain::DriftGenerator) and
+//! [`domain::chaos::ChaosGenerator`](crate::domain::ChaosGenerator) use, so
+//! a given seed reproduces the same delays run to run.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::domain::LatencyProfile;
+
+pub struct LatencyInjector {
+    profiles: Vec<LatencyProfile>,
+    rng_state: Mutex<u64>,
+}
+
+impl LatencyInjector {
+    pub fn new(profiles: Vec<LatencyProfile>, seed: u64) -> Self {
+        Self {
+            profiles,
+            rng_state: Mutex::new(seed | 1),
+        }
+    }
+
+    /// Delay to apply to a request for `path`, if any profile's prefix
+    /// matches. The first matching profile wins, so more specific prefixes
+    /// should be listed before broader ones.
+    pub fn delay_for(&self, path: &str) -> Option<Duration> {
+        let profile = self.profiles.iter().find(|p| path.starts_with(p.route_prefix.as_str()))?;
+        let jitter = if profile.jitter_ms == 0 { 0 } else { self.next_jitter(profile.jitter_ms) };
+        Some(Duration::from_millis(profile.fixed_ms + jitter))
+    }
+
+    fn next_jitter(&self, max: u64) -> u64 {
+        let mut state = self.rng_state.lock().unwrap();
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state % (max + 1)
+    }
+}
+
+impl Default for LatencyInjector {
+    fn default() -> Self {
+        Self::new(Vec::new(), 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_profile_matches_means_no_delay() {
+        let injector = LatencyInjector::new(
+            vec![LatencyProfile {
+                route_prefix: "/api/v3/exchangeInfo".into(),
+                fixed_ms: 50,
+                jitter_ms: 0,
+            }],
+            1,
+        );
+        assert!(injector.delay_for("/api/v3/ping").is_none());
+    }
+
+    #[test]
+    fn matching_profile_applies_fixed_delay_plus_bounded_jitter() {
+        let injector = LatencyInjector::new(
+            vec![LatencyProfile {
+                route_prefix: "/api/v3/exchangeInfo".into(),
+                fixed_ms: 50,
+                jitter_ms: 20,
+            }],
+            7,
+        );
+        for _ in 0..20 {
+            let delay = injector.delay_for("/api/v3/exchangeInfo").unwrap();
+            assert!(delay.as_millis() >= 50 && delay.as_millis() <= 70);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Library crate behind the `exchange-simulator-backend` binary. Split out
+//! so integration benchmarks (see `benches/`) can exercise storage and
+//! service internals directly instead of going through the HTTP surface.
+
+pub mod analytics;
+pub mod broadcaster;
+pub mod clock_group;
+pub mod config;
+pub mod dataset_progress;
+pub mod dataset_registry;
+pub mod domain;
+pub mod equity_curve;
+pub mod error;
+pub mod event_bus;
+pub mod http;
+pub mod ingestion_ledger;
+pub mod latency;
+pub mod listen_key;
+pub mod logging;
+pub mod margin;
+pub mod matching;
+pub mod oas;
+pub mod orders;
+pub mod services;
+pub mod shorts;
+pub mod simulator;
+pub mod state;
+pub mod store;
+pub mod symbol_registry;
+pub mod tenancy;
+pub mod ticker;
+pub mod ttl_cache;
+pub mod webhook;
+pub mod ws_stats;
@@ -0,0 +1,133 @@
+//! Binance's user-data-stream listenKey lifecycle
+//! (`POST`/`PUT`/`DELETE /api/v3/userDataStream`) — a client exchanges an
+//! issued key for a `wss://.../ws/<listenKey>` subscription carrying
+//! account-level events (order/balance updates), renewing it periodically
+//! to keep it alive.
+//!
+//! This tree has no `/api/v3` websocket surface at all (see `http::v3`'s
+//! module doc on why every v3 route is REST) and no account-level event
+//! pipeline to gate behind one in the first place (see `orders`'s and
+//! `matching`'s module docs) — the one real websocket,
+//! `http::ws::ws_session`, is session-scoped rather than account-scoped
+//! and already requires a valid namespace token for every frame it sends,
+//! with no separate signed-stream mode to extend. So a listenKey minted
+//! here has nothing to actually authorize yet; this is the
+//! issuance/keepalive/revocation bookkeeping such a stream would check
+//! against once one exists, the same "ready but unused" status as
+//! `orders::OrderFillsLedger`.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use uuid::Uuid;
+
+/// Binance's own listenKey TTL: a key not renewed within this window is
+/// considered expired.
+const LISTEN_KEY_TTL_MS: i64 = 60 * 60 * 1000;
+
+#[derive(Debug, Clone)]
+struct ListenKeyRecord {
+    namespace_id: Uuid,
+    last_renewed_at: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct ListenKeyRegistry {
+    keys: RwLock<HashMap<String, ListenKeyRecord>>,
+}
+
+impl ListenKeyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mints and stores a new opaque listen key for `namespace_id`,
+    /// considered freshly renewed as of `at`.
+    pub fn issue(&self, namespace_id: Uuid, at: i64) -> String {
+        let key = Uuid::new_v4().simple().to_string();
+        self.keys.write().unwrap().insert(key.clone(), ListenKeyRecord { namespace_id, last_renewed_at: at });
+        key
+    }
+
+    /// Resets `key`'s TTL clock to `at`. `false` if `key` isn't
+    /// registered, or is registered to a different namespace.
+    pub fn renew(&self, key: &str, namespace_id: Uuid, at: i64) -> bool {
+        match self.keys.write().unwrap().get_mut(key) {
+            Some(record) if record.namespace_id == namespace_id => {
+                record.last_renewed_at = at;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes `key`. `false` if it isn't registered, or is registered to
+    /// a different namespace.
+    pub fn revoke(&self, key: &str, namespace_id: Uuid) -> bool {
+        let mut keys = self.keys.write().unwrap();
+        match keys.get(key) {
+            Some(record) if record.namespace_id == namespace_id => {
+                keys.remove(key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// `true` if `key` is registered and has been renewed within
+    /// [`LISTEN_KEY_TTL_MS`] of `at`.
+    pub fn is_valid(&self, key: &str, at: i64) -> bool {
+        self.keys
+            .read()
+            .unwrap()
+            .get(key)
+            .map(|record| at - record.last_renewed_at < LISTEN_KEY_TTL_MS)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_issued_key_is_valid_immediately() {
+        let registry = ListenKeyRegistry::new();
+        let namespace_id = Uuid::new_v4();
+        let key = registry.issue(namespace_id, 0);
+        assert!(registry.is_valid(&key, 0));
+    }
+
+    #[test]
+    fn a_key_expires_once_its_ttl_elapses_without_renewal() {
+        let registry = ListenKeyRegistry::new();
+        let key = registry.issue(Uuid::new_v4(), 0);
+        assert!(registry.is_valid(&key, LISTEN_KEY_TTL_MS - 1));
+        assert!(!registry.is_valid(&key, LISTEN_KEY_TTL_MS));
+    }
+
+    #[test]
+    fn renewing_resets_the_ttl_clock() {
+        let registry = ListenKeyRegistry::new();
+        let namespace_id = Uuid::new_v4();
+        let key = registry.issue(namespace_id, 0);
+        assert!(registry.renew(&key, namespace_id, LISTEN_KEY_TTL_MS - 1));
+        assert!(registry.is_valid(&key, LISTEN_KEY_TTL_MS * 2 - 2));
+    }
+
+    #[test]
+    fn a_namespace_cannot_renew_or_revoke_another_namespaces_key() {
+        let registry = ListenKeyRegistry::new();
+        let key = registry.issue(Uuid::new_v4(), 0);
+        let intruder = Uuid::new_v4();
+        assert!(!registry.renew(&key, intruder, 0));
+        assert!(!registry.revoke(&key, intruder));
+        assert!(registry.is_valid(&key, 0));
+    }
+
+    #[test]
+    fn revoking_an_unknown_key_is_a_no_op() {
+        let registry = ListenKeyRegistry::new();
+        assert!(!registry.revoke("not-a-real-key", Uuid::new_v4()));
+    }
+}
@@ -0,0 +1,30 @@
+//! Runtime-adjustable log filtering. `main` installs a reloadable
+//! [`tracing_subscriber::EnvFilter`] via [`init`] and hands the returned
+//! handle to [`AppState`](crate::state::AppState), so
+//! `POST /api/v1/debug/log-level` can change what gets logged (globally or
+//! per module, using the same directive syntax as the `RUST_LOG` env var)
+//! without restarting the process.
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Installs the global subscriber and returns a handle that can later
+/// reload its filter. Starts from `RUST_LOG` if set, otherwise `"info"`,
+/// matching the defaults `tracing_subscriber::fmt::init()` used before
+/// this became reloadable.
+pub fn init() -> LogFilterHandle {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter_layer, handle) = reload::Layer::new(filter);
+    Registry::default().with(filter_layer).with(fmt::Layer::default()).init();
+    handle
+}
+
+/// Parses `directives` (e.g. `"debug"` or `"info,exchange_simulator_backend::services=debug"`)
+/// and swaps it in as the live filter.
+pub fn set_filter(handle: &LogFilterHandle, directives: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(directives).map_err(|err| err.to_string())?;
+    handle.reload(filter).map_err(|err| err.to_string())
+}
@@ -0,0 +1,65 @@
+use exchange_simulator_backend::config::Config;
+use exchange_simulator_backend::http;
+use exchange_simulator_backend::logging;
+use exchange_simulator_backend::services::backup;
+use exchange_simulator_backend::services::dataset;
+use exchange_simulator_backend::services::SessionReaper;
+use exchange_simulator_backend::simulator::SimulatorBuilder;
+use exchange_simulator_backend::state::AppState;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let log_filter_handle = logging::init();
+
+    let config = Config::load();
+    let simulator = SimulatorBuilder::new().with_demo_data(true).build().await;
+    let state = simulator.state();
+    state.set_config(config.clone());
+    if let Some(dataset_path) = &config.dataset_path {
+        backup::restore_if_present(&state, dataset_path);
+        if config.auto_resume_ingestions {
+            dataset::resume_stale_ingestions(&state);
+        }
+    }
+    state.set_log_filter_handle(log_filter_handle);
+    state.configure_latency(config.v3_latency.clone(), config.v3_latency_seed);
+    SessionReaper::spawn(state.clone());
+    let shutdown_state = state.clone();
+    let app = http::router(state, &config);
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    tracing::info!("listening on {}", listener.local_addr()?);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(wait_for_shutdown_signal(shutdown_state))
+        .await?;
+
+    Ok(())
+}
+
+/// Flips [`AppState::begin_shutdown`] as soon as a shutdown signal arrives
+/// so in-flight websocket/SSE handlers can send subscribers a distinct
+/// "server shutdown" close notice before axum stops accepting connections.
+async fn wait_for_shutdown_signal(state: AppState) {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        let Ok(mut sigterm) = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) else {
+            return;
+        };
+        sigterm.recv().await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    tracing::info!("shutdown signal received");
+    state.begin_shutdown();
+}
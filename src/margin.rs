@@ -0,0 +1,97 @@
+//! Per-session borrow bookkeeping for margin-enabled sessions.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::domain::{BorrowPosition, Decimal, MarginConfig};
+
+/// Tracks outstanding borrow principal per asset for one session. Folding
+/// accrued interest into `principal` on every borrow (rather than storing
+/// interest separately) keeps `positions` a cheap read with no per-query
+/// accrual math beyond the final, still-outstanding window.
+#[derive(Debug, Default)]
+pub struct MarginLedger {
+    borrows: Mutex<HashMap<String, BorrowPosition>>,
+}
+
+impl MarginLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads `positions` as this session's starting borrows, e.g. copied
+    /// from a prior session's `SessionSummary::open_borrows` when chaining
+    /// backtests (see `http::sessions::create_session`'s `from_session`).
+    /// Overwrites anything already borrowed for a given asset rather than
+    /// folding it in, since this is meant to run once, right after
+    /// `AppState::insert_session` creates an empty ledger.
+    pub fn seed(&self, positions: Vec<BorrowPosition>) {
+        let mut borrows = self.borrows.lock().unwrap();
+        for position in positions {
+            borrows.insert(position.asset.clone(), position);
+        }
+    }
+
+    /// Borrows `amount` of `asset` as of simulated time `at`, accruing any
+    /// interest already owed on the existing principal first.
+    pub fn borrow(&self, asset: &str, amount: Decimal, at: i64, config: &MarginConfig) {
+        let mut borrows = self.borrows.lock().unwrap();
+        let position = borrows.entry(asset.to_string()).or_insert_with(|| BorrowPosition {
+            asset: asset.to_string(),
+            principal: Decimal::ZERO,
+            borrowed_at: at,
+        });
+        let interest = position.interest(at, config);
+        position.principal += interest + amount;
+        position.borrowed_at = at;
+    }
+
+    /// Outstanding positions with interest accrued up to `as_of` folded in.
+    pub fn positions(&self, as_of: i64, config: &MarginConfig) -> Vec<BorrowPosition> {
+        self.borrows
+            .lock()
+            .unwrap()
+            .values()
+            .map(|position| BorrowPosition {
+                asset: position.asset.clone(),
+                principal: position.principal + position.interest(as_of, config),
+                borrowed_at: as_of,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn config() -> MarginConfig {
+        MarginConfig {
+            max_leverage: dec!(3.0),
+            interest_rate_per_ms: dec!(0.00001),
+        }
+    }
+
+    #[test]
+    fn borrowing_again_folds_in_prior_interest() {
+        let ledger = MarginLedger::new();
+        ledger.borrow("USDT", dec!(1000.0), 0, &config());
+        ledger.borrow("USDT", dec!(0.0), 1000, &config());
+
+        let positions = ledger.positions(1000, &config());
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].principal, dec!(1010.00000));
+    }
+
+    #[test]
+    fn seeding_carries_a_prior_sessions_borrows_into_a_new_ledger() {
+        let ledger = MarginLedger::new();
+        ledger.seed(vec![BorrowPosition { asset: "USDT".into(), principal: dec!(500.0), borrowed_at: 0 }]);
+
+        let positions = ledger.positions(0, &config());
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].principal, dec!(500.0));
+    }
+}
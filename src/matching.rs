@@ -0,0 +1,168 @@
+//! Pluggable fill-model trait for a future order/matching pipeline.
+//!
+//! There's no `SpotMatcher` anywhere in this tree to extract a trait out
+//! of — `orders`' module doc covers why there's no matching engine, order
+//! placement, or order book at all yet. [`MatchingEngine`] builds the
+//! abstraction point from scratch instead: once a real order pipeline
+//! lands, it drives whichever engine a session selected via
+//! `Session::matching_engine` instead of being hardwired to one fill
+//! model, so comparing fill models against the same replay doesn't mean
+//! forking the code.
+//!
+//! [`LastPriceMatcher`] is the only engine actually implemented —
+//! `MatchingEngineKind::OrderBook` needs a simulated book
+//! (`services::depth_sequencing` isn't one, just update-id bookkeeping for
+//! a future diff stream) and `MatchingEngineKind::Probabilistic` needs a
+//! fill pipeline to inject probability into, neither of which exist yet.
+//! [`build`] falls back to [`LastPriceMatcher`] for both until they do,
+//! the same way `services::dataset`'s interval stepping falls back to a
+//! fixed constant for an interval it can't parse.
+//!
+//! Seek semantics: [`MatchingEngine`] has no reset/rewind method, so a
+//! session jumping forward via `Session::seek_to`
+//! (`services::replay_service`) leaves an engine's state exactly as it
+//! was — there's no order book to invalidate, so "keep resting orders"
+//! is just the natural consequence of this trait not clearing anything on
+//! its own. Seeking backward isn't accepted at all (`AppState::seek`
+//! rejects it outright): there's no journal anywhere in this tree that
+//! records engine state over time, only the current snapshot, so there's
+//! nothing to restore an engine to as of an earlier simulated time.
+
+use std::collections::HashMap;
+
+use crate::domain::{Decimal, Kline, MatchingEngineKind, Symbol, Trade};
+
+/// Reacts to one session's market data and tracks whatever a fill model
+/// needs to answer "what would this order have filled at". A future order
+/// pipeline would call `on_trade`/`on_kline` as events arrive and
+/// `on_session_end` when the session ends, the same lifecycle
+/// `ReplayService` already drives other per-session trackers through.
+pub trait MatchingEngine: Send {
+    fn on_trade(&mut self, trade: &Trade) {
+        let _ = trade;
+    }
+
+    fn on_kline(&mut self, kline: &Kline) {
+        let _ = kline;
+    }
+
+    fn on_session_end(&mut self) {}
+}
+
+/// Fills at the last trade/kline-close price seen for the order's symbol —
+/// the simplest possible model, and the one every price this tree already
+/// reports (`services::mark_price`, `ticker::TickerTracker`,
+/// `http::debug::get_session_prices`) implicitly assumes when it marks or
+/// displays a symbol at its last close.
+#[derive(Debug, Default)]
+pub struct LastPriceMatcher {
+    last_price: HashMap<Symbol, Decimal>,
+}
+
+impl LastPriceMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most recent price observed for `symbol`, `None` if neither
+    /// `on_trade` nor `on_kline` has seen it yet.
+    pub fn last_price(&self, symbol: &Symbol) -> Option<Decimal> {
+        self.last_price.get(symbol).copied()
+    }
+}
+
+impl MatchingEngine for LastPriceMatcher {
+    fn on_trade(&mut self, trade: &Trade) {
+        self.last_price.insert(trade.symbol.clone(), trade.price);
+    }
+
+    fn on_kline(&mut self, kline: &Kline) {
+        self.last_price.insert(kline.symbol.clone(), kline.close);
+    }
+}
+
+/// Builds the engine a session selected. See this module's doc comment for
+/// why `OrderBook` and `Probabilistic` currently fall back to the same
+/// [`LastPriceMatcher`] as `LastPrice`.
+pub fn build(kind: MatchingEngineKind) -> Box<dyn MatchingEngine> {
+    match kind {
+        MatchingEngineKind::LastPrice | MatchingEngineKind::OrderBook | MatchingEngineKind::Probabilistic => {
+            Box::new(LastPriceMatcher::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn kline(symbol: &Symbol, close: Decimal) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: dec!(1),
+            quote_volume: close,
+            is_closed: true,
+        }
+    }
+
+    fn trade(symbol: &Symbol, price: Decimal) -> Trade {
+        Trade { symbol: symbol.clone(), agg_trade_id: 1, price, quantity: dec!(1), trade_time: 0, is_buyer_maker: false }
+    }
+
+    #[test]
+    fn tracks_the_most_recent_kline_close_per_symbol() {
+        let mut matcher = LastPriceMatcher::new();
+        let symbol = Symbol::new("BTCUSDT");
+        matcher.on_kline(&kline(&symbol, dec!(100)));
+        matcher.on_kline(&kline(&symbol, dec!(101)));
+        assert_eq!(matcher.last_price(&symbol), Some(dec!(101)));
+    }
+
+    #[test]
+    fn a_trade_overrides_the_last_kline_price() {
+        let mut matcher = LastPriceMatcher::new();
+        let symbol = Symbol::new("BTCUSDT");
+        matcher.on_kline(&kline(&symbol, dec!(100)));
+        matcher.on_trade(&trade(&symbol, dec!(102)));
+        assert_eq!(matcher.last_price(&symbol), Some(dec!(102)));
+    }
+
+    #[test]
+    fn an_unobserved_symbol_has_no_last_price() {
+        let matcher = LastPriceMatcher::new();
+        assert_eq!(matcher.last_price(&Symbol::new("ETHUSDT")), None);
+    }
+
+    #[test]
+    fn engine_state_survives_a_forward_seek_since_nothing_resets_it() {
+        // There's no `MatchingEngine::on_seek`/`reset` method — a session
+        // jumping `open_time` forward (see `services::replay_service`)
+        // just keeps ticking the same engine instance, so whatever it last
+        // observed before the jump is still what it reports after.
+        let mut matcher = LastPriceMatcher::new();
+        let symbol = Symbol::new("BTCUSDT");
+        matcher.on_trade(&trade(&symbol, dec!(100)));
+        // ... a forward seek happens here; nothing calls into the engine ...
+        matcher.on_trade(&trade(&symbol, dec!(105)));
+        assert_eq!(matcher.last_price(&symbol), Some(dec!(105)));
+    }
+
+    #[test]
+    fn every_engine_kind_currently_builds_a_last_price_matcher() {
+        for kind in [MatchingEngineKind::LastPrice, MatchingEngineKind::OrderBook, MatchingEngineKind::Probabilistic] {
+            let mut engine = build(kind);
+            let symbol = Symbol::new("BTCUSDT");
+            engine.on_trade(&trade(&symbol, dec!(50)));
+            engine.on_session_end();
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! OpenAPI document generation. Two surfaces are published separately
+//! since they serve different audiences: `v1` is the simulator management
+//! API (datasets, sessions, bots, runs), `v3` is the Binance-compatible
+//! trading surface bots integrate against.
+
+use axum::response::Html;
+use axum::routing::get;
+use axum::{Json, Router};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::http::v3::user_data_stream;
+use crate::http::{admin, datasets, debug, market, namespaces, reports, sessions, v3, webhooks};
+use crate::state::AppState;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        sessions::create_session,
+        sessions::list_sessions,
+        sessions::get_session,
+        sessions::start_session,
+        sessions::pause_at,
+        sessions::add_breakpoint,
+        sessions::seek_session,
+        sessions::patch_matching_config,
+        sessions::get_session_klines,
+        sessions::get_session_trades,
+        sessions::get_session_tape,
+        sessions::get_session_equity_curve,
+        sessions::get_session_events,
+        sessions::get_session_margin,
+        sessions::get_session_shorts,
+        sessions::get_session_positions,
+        sessions::get_session_order_rate,
+        sessions::export_session_trades,
+        sessions::get_session_analytics,
+        sessions::get_session_dataset_progress,
+        sessions::get_session_ws_stats,
+        sessions::get_session_datasets,
+        sessions::get_session_summary,
+        webhooks::register_webhook,
+        webhooks::get_webhook_deliveries,
+        admin::set_maintenance,
+        admin::cache_stats,
+        admin::pause_all,
+        admin::resume_all,
+        admin::backup_dataset,
+        admin::set_symbol_metadata,
+        admin::merge_datasets,
+        admin::delete_dataset,
+        admin::register_symbol_lifecycle_event,
+        admin::list_symbol_lifecycle_events,
+        admin::export_dataset,
+        admin::import_dataset,
+        admin::get_config,
+        debug::set_log_level,
+        debug::get_session_prices,
+        market::get_klines,
+        market::resample_klines,
+        market::reconstruct_klines,
+        market::get_trades,
+        market::availability,
+        datasets::estimate_dataset,
+        datasets::register_dataset,
+        datasets::get_ingestion_runs,
+        datasets::get_dataset,
+        datasets::preview_dataset,
+        datasets::dataset_events,
+        reports::compare_sessions,
+        namespaces::create_namespace,
+        namespaces::issue_token
+    ),
+    components(schemas(
+        crate::domain::Session,
+        crate::domain::SessionStatus,
+        crate::domain::Symbol,
+        crate::domain::ClockDriftConfig,
+        crate::domain::ChaosConfig,
+        crate::domain::CommissionConfig,
+        crate::domain::SymbolFeeOverride,
+        crate::domain::MarginConfig,
+        crate::domain::OrderLimitsConfig,
+        crate::domain::OrderRateBucket,
+        crate::domain::BorrowPosition,
+        crate::domain::ShortPosition,
+        crate::domain::Position,
+        crate::domain::SessionSummary,
+        crate::domain::WebhookConfig,
+        crate::domain::WebhookEvent,
+        crate::domain::WebhookDelivery,
+        crate::domain::DeliveryStatus,
+        webhooks::RegisterWebhookRequest,
+        webhooks::RegisterWebhookResponse,
+        crate::domain::Kline,
+        crate::domain::Trade,
+        crate::domain::MarketEvent,
+        crate::domain::MarketEventPayload,
+        crate::domain::MarkPrice,
+        crate::domain::MarkPriceConfig,
+        crate::domain::SymbolAnalytics,
+        crate::domain::VolumeBucket,
+        crate::domain::DatasetProgress,
+        crate::domain::ThrottleReason,
+        crate::domain::WsConnectionRecord,
+        crate::domain::DatasetEstimate,
+        crate::domain::DatasetRecord,
+        crate::domain::TapeBucket,
+        crate::domain::EquitySnapshot,
+        crate::domain::DatasetProgressStatus,
+        crate::domain::MarketAvailability,
+        crate::domain::MissingSpan,
+        crate::domain::IngestionRun,
+        crate::domain::IngestionRecord,
+        crate::domain::IngestionTrigger,
+        crate::domain::IngestStatus,
+        datasets::EstimateDatasetRequest,
+        datasets::RegisterDatasetRequest,
+        datasets::DatasetProgressEvent,
+        market::KlinesPage,
+        sessions::CreateSessionRequest,
+        sessions::DatasetRequirement,
+        sessions::PatchMatchingRequest,
+        admin::SetMaintenanceRequest,
+        admin::MaintenanceStatus,
+        admin::CacheStats,
+        admin::BulkSessionResult,
+        admin::BulkSessionActionResponse,
+        admin::DeleteDatasetResponse,
+        admin::BackupRequest,
+        crate::services::backup::BackupSummary,
+        crate::services::bundle::ImportSummary,
+        admin::SetSymbolMetadataRequest,
+        crate::config::Config,
+        crate::domain::LatencyProfile,
+        crate::domain::LiveDataSource,
+        crate::domain::MatchingEngineKind,
+        crate::domain::MarketMode,
+        crate::domain::OrderEvent,
+        crate::domain::OrderSide,
+        crate::domain::OrderStatus,
+        crate::domain::FillEvent,
+        crate::domain::BalanceEvent,
+        crate::domain::OrderAmendmentEvent,
+        crate::domain::BreakpointCondition,
+        crate::domain::BreakpointEvent,
+        crate::domain::CrossDirection,
+        crate::domain::SymbolLifecycleEvent,
+        debug::SetLogLevelRequest,
+        debug::LogLevelResponse,
+        crate::domain::SymbolPrice,
+        crate::domain::Namespace,
+        crate::domain::TokenRole,
+        namespaces::CreateNamespaceRequest,
+        namespaces::IssueTokenRequest,
+        namespaces::IssueTokenResponse
+    )),
+    tags(
+        (name = "sessions", description = "Session lifecycle and market data streaming"),
+        (name = "admin", description = "Operator control-plane endpoints"),
+        (name = "debug", description = "Live-instance inspection and debugging endpoints"),
+        (name = "webhooks", description = "Outbound event notifications for session lifecycle events"),
+        (name = "market-data", description = "Historical kline queries backed by the candle store"),
+        (name = "namespaces", description = "Tenant namespace and API token bootstrap endpoints"),
+        (name = "datasets", description = "Dataset sizing and dry-run endpoints"),
+        (name = "reports", description = "Cross-session comparison and reporting endpoints")
+    ),
+    modifiers(&ApiKeyAuth)
+)]
+struct V1Api;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        v3::general::ping,
+        v3::general::server_time,
+        v3::exchange_info::exchange_info,
+        v3::system::system_status,
+        v3::ticker::ticker_24hr,
+        v3::ticker::avg_price,
+        v3::trades::historical_trades,
+        v3::trades::agg_trades,
+        user_data_stream::create_listen_key,
+        user_data_stream::keepalive_listen_key,
+        user_data_stream::close_listen_key
+    ),
+    components(schemas(
+        v3::general::ServerTime,
+        v3::exchange_info::ExchangeInfoResponse,
+        v3::exchange_info::SymbolInfo,
+        v3::system::SystemStatus,
+        crate::domain::Ticker24hr,
+        crate::domain::AvgPrice,
+        crate::domain::Trade,
+        user_data_stream::ListenKeyResponse
+    )),
+    tags((name = "market", description = "Binance-compatible market/account/order endpoints")),
+    modifiers(&ApiKeyAuth)
+)]
+struct V3Api;
+
+struct ApiKeyAuth;
+
+impl Modify for ApiKeyAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-MBX-APIKEY"))),
+        );
+    }
+}
+
+/// Mounts `/api-doc/v1.json`, `/api-doc/v3.json` and a Swagger UI for each,
+/// served from the CDN swagger-ui-dist bundle so the binary doesn't have to
+/// vendor it.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api-doc/v1.json", get(|| async { Json(V1Api::openapi()) }))
+        .route("/api-doc/v3.json", get(|| async { Json(V3Api::openapi()) }))
+        .route("/swagger-ui/v1", get(|| async { Html(swagger_page("/api-doc/v1.json")) }))
+        .route("/swagger-ui/v3", get(|| async { Html(swagger_page("/api-doc/v3.json")) }))
+}
+
+fn swagger_page(spec_url: &str) -> String {
+    format!(
+        r##"<!DOCTYPE html>
+<html>
+<head><title>exchange-simulator API docs</title>
+<link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist/swagger-ui.css" />
+</head>
+<body>
+<div id="swagger-ui"></div>
+<script src="https://unpkg.com/swagger-ui-dist/swagger-ui-bundle.js"></script>
+<script>
+  window.onload = () => SwaggerUIBundle({{ url: "{spec_url}", dom_id: "#swagger-ui" }});
+</script>
+</body>
+</html>"##
+    )
+}
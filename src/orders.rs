@@ -0,0 +1,513 @@
+//! Client-order-id idempotency bookkeeping.
+//!
+//! This tree has no `OrdersService` or order placement endpoint at all —
+//! there's no matching engine behind the v3 surface yet, only the
+//! order-adjacent bookkeeping that already exists standalone (`margin.rs`,
+//! `shorts.rs`, `domain::commission`). So there's nothing to wire
+//! duplicate-detection into today. This registers the piece the request
+//! actually asked for — per-session dedup keyed by Binance's
+//! `newClientOrderId`, replaying the original order id instead of minting a
+//! new one on resubmission — so that whenever a real `place_order` lands it
+//! has this ready to call rather than reinventing it. Binance itself
+//! reports a fresh duplicate as error `-2010 Duplicate clientOrderId`;
+//! there's no `ApiError` variant for that here yet since nothing can emit
+//! it.
+//!
+//! [`OrderIdempotencyRegistry`] doesn't stamp a time on placement at all
+//! (see `PlacementOutcome`), so there's no wall-clock-vs-simulation-clock
+//! question to resolve here yet either. Whenever a real order/fill repo
+//! does land, its timestamps belong on `Session::current_time`
+//! (`domain::session`) the same way every other persisted event in this
+//! tree already reads its time — `http::sessions::get_session_klines` and
+//! `state::AppState::positions_snapshot` both clamp against it rather than
+//! `Utc::now` — not on wall-clock time.
+//!
+//! There's no `my_trades`/`/api/v3/myTrades` endpoint either, and so no
+//! fill records to assign an id to — there's nothing that "fabricates
+//! trade ids from enumeration indexes" in this tree today. [`FillIdSequence`]
+//! is the other half of the same not-yet-existent fill path: a stable,
+//! per-session, monotonically increasing id assigned once at fill time and
+//! persisted with the fill, rather than derived from a `Vec` index at
+//! response time. `fromId`-based pagination for a future `myTrades` would
+//! compare against the id this hands out, not against a position in
+//! whatever list happens to be in memory when the response is built.
+//!
+//! [`OrderCountTracker`] is the same story for `Session::order_limits`
+//! (`domain::OrderLimitsConfig`): there's no open-order book to count
+//! against, so this only counts orders placed through it directly — a
+//! future `place_order` would call `try_record` before registering an
+//! order and surface its `Err` as Binance's `-1015 TOO_MANY_ORDERS`.
+//! There's also no v3 session scoping to hang a `/api/v3/rateLimit/order`
+//! endpoint off of yet (see `http::v3`'s module doc), so current counts
+//! aren't exposed over HTTP either — `open_order_count`/`orders_today` are
+//! there for whichever surface ends up needing them.
+//!
+//! [`sequence_ack_and_fill`] is the same story for Binance's
+//! `executionReport NEW`-before-`TRADE` ordering
+//! (`Session::ack_before_trade`, `domain::session`): a future `place_order`
+//! that fills an order immediately shouldn't stamp both the acknowledgement
+//! and the fill with the same simulated timestamp and return them
+//! atomically, since a bot driven by `MarketEventPayload::Order`/`Fill`
+//! events (`http::ws::WsBatchParams::include_orders`) needs to see the `NEW`
+//! event strictly before the paired fill to drive an event-driven state
+//! machine correctly. This computes the two distinct timestamps that pair
+//! of events should carry; there's no order pipeline to call it from yet.
+//!
+//! [`validate_quantity_amendment`] is the same story for Binance's order
+//! amendment (quantity-only, price-time-priority-preserving "PATCH"
+//! instead of cancel/replace): rejects an amendment that would increase a
+//! resting order's quantity, or reduce it below what's already filled.
+//! There's no open order or audit journal to apply the amendment to yet —
+//! `domain::OrderAmendmentEvent` is the wire shape a future `amend_order`
+//! endpoint would publish once one exists.
+//!
+//! [`OrderFillsLedger`] is the same story for Binance's `GET
+//! /api/v3/order?orderId=...&include=fills` (the "FULL" response that
+//! inlines an order's fills instead of forcing a second `myTrades` call):
+//! there's no `GET /api/v3/order` endpoint at all, so nothing writes to
+//! this yet either. This is the lookup table such an endpoint would query
+//! directly by `order_id` instead of scanning every fill in `myTrades` and
+//! filtering client-side, the N+1 pattern the request calls out.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use uuid::Uuid;
+
+use crate::domain::{Decimal, FillEvent, OrderLimitsConfig, OrderRateBucket};
+
+/// Given the simulated time an order was placed, returns `(ack_at,
+/// fill_at)` for its paired `NEW` acknowledgement and (if it fills
+/// immediately) trade — strictly increasing, a millisecond apart, so
+/// nothing downstream ever has to special-case two events sharing a
+/// timestamp to recover Binance's `NEW`-before-`TRADE` ordering. Only
+/// meaningful when `Session::ack_before_trade` is set — see this module's
+/// doc comment.
+pub fn sequence_ack_and_fill(order_placed_at: i64) -> (i64, i64) {
+    (order_placed_at, order_placed_at + 1)
+}
+
+/// Binance's order amendment keeps a resting order's original price-time
+/// priority in the book, unlike cancel/replace — but only lets `quantity`
+/// shrink, and never below what's already filled. Validates `requested`
+/// against an order currently resting at `current_qty` with `filled_qty`
+/// already executed; there's no order book to amend in place yet (see this
+/// module's doc comment), so this is the piece a future `amend_order`
+/// endpoint would call before emitting a
+/// `MarketEventPayload::OrderAmendment` and mutating anything.
+pub fn validate_quantity_amendment(current_qty: Decimal, filled_qty: Decimal, requested: Decimal) -> Result<(), String> {
+    if requested > current_qty {
+        return Err(format!(
+            "-1013 Invalid quantity: amending to {requested} would increase the order above its current {current_qty}; only reductions preserve price-time priority"
+        ));
+    }
+    if requested < filled_qty {
+        return Err(format!(
+            "-1013 Invalid quantity: amending to {requested} is below the {filled_qty} already filled"
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementOutcome {
+    /// First time this `(session, clientOrderId)` pair has been seen.
+    Created(Uuid),
+    /// A prior call already placed an order under this `clientOrderId`;
+    /// this is that order's id, not a new one.
+    Replayed(Uuid),
+}
+
+/// Tracks which `newClientOrderId` values have already been used per
+/// session, across the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct OrderIdempotencyRegistry {
+    orders: RwLock<HashMap<(Uuid, String), Uuid>>,
+}
+
+impl OrderIdempotencyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `client_order_id` for `session_id` if it hasn't been seen
+    /// before, otherwise replays the order id it was first registered
+    /// with.
+    pub fn place(&self, session_id: Uuid, client_order_id: &str) -> PlacementOutcome {
+        let key = (session_id, client_order_id.to_string());
+        let mut orders = self.orders.write().unwrap();
+        if let Some(&order_id) = orders.get(&key) {
+            return PlacementOutcome::Replayed(order_id);
+        }
+        let order_id = Uuid::new_v4();
+        orders.insert(key, order_id);
+        PlacementOutcome::Created(order_id)
+    }
+}
+
+/// Fills recorded against an order, keyed by `order_id`, so a future `GET
+/// /api/v3/order` can inline them (Binance's `FULL` response type) instead
+/// of making its caller fetch `myTrades` and filter client-side. See this
+/// module's doc comment.
+#[derive(Debug, Default)]
+pub struct OrderFillsLedger {
+    fills: RwLock<HashMap<Uuid, Vec<FillEvent>>>,
+}
+
+impl OrderFillsLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `fill` to `order_id`'s fill list. `fill.order_id` is not
+    /// checked against `order_id` — the caller already knows which order
+    /// it just filled.
+    pub fn record_fill(&self, order_id: Uuid, fill: FillEvent) {
+        self.fills.write().unwrap().entry(order_id).or_default().push(fill);
+    }
+
+    /// Every fill recorded against `order_id`, oldest first. Empty for an
+    /// order that hasn't filled (or doesn't exist) rather than an error,
+    /// matching `include=fills` simply inlining nothing to report.
+    pub fn list_order_fills(&self, order_id: Uuid) -> Vec<FillEvent> {
+        self.fills.read().unwrap().get(&order_id).cloned().unwrap_or_default()
+    }
+}
+
+/// Hands out stable, monotonically increasing trade/fill ids, per session,
+/// across the lifetime of the process. Ids start at 1 and never repeat or
+/// go backwards for a given session, so pagination by `fromId` (Binance's
+/// `myTrades` semantics) stays correct even as fills accumulate — unlike
+/// enumerating a list at response time, whose indexes shift as items are
+/// added or removed.
+#[derive(Debug, Default)]
+pub struct FillIdSequence {
+    next_id: RwLock<HashMap<Uuid, i64>>,
+}
+
+impl FillIdSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns and returns the next fill id for `session_id`.
+    pub fn next(&self, session_id: Uuid) -> i64 {
+        let mut next_id = self.next_id.write().unwrap();
+        let id = next_id.entry(session_id).or_insert(1);
+        let assigned = *id;
+        *id += 1;
+        assigned
+    }
+}
+
+/// Counts orders placed per `(session, symbol)` and per `(session, day)`,
+/// checked against a session's [`OrderLimitsConfig`] before a new order
+/// would be registered. `day_bucket` is caller-supplied (e.g. simulated
+/// time divided by milliseconds-per-day) rather than computed here, since
+/// this tree has no wall clock to measure a day against — see `orders`'
+/// module doc on why every persisted timestamp should come from
+/// `Session::current_time` instead.
+#[derive(Debug, Default)]
+pub struct OrderCountTracker {
+    open_orders: RwLock<HashMap<(Uuid, String), u32>>,
+    orders_today: RwLock<HashMap<(Uuid, i64), u32>>,
+}
+
+impl OrderCountTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an order for `symbol` in `session_id` on `day_bucket`, or
+    /// rejects it with a Binance-style `-1015 TOO_MANY_ORDERS` message if
+    /// doing so would exceed either limit configured in `limits`. A
+    /// `None` limit is unbounded.
+    pub fn try_record(&self, session_id: Uuid, symbol: &str, day_bucket: i64, limits: &OrderLimitsConfig) -> Result<(), String> {
+        if let Some(max) = limits.max_open_orders_per_symbol {
+            if self.open_order_count(session_id, symbol) >= max {
+                return Err(format!("-1015 TOO_MANY_ORDERS: {symbol} already has {max} open orders for this session"));
+            }
+        }
+        if let Some(max) = limits.max_orders_per_day {
+            if self.orders_today(session_id, day_bucket) >= max {
+                return Err(format!("-1015 TOO_MANY_ORDERS: {max} orders have already been placed today for this session"));
+            }
+        }
+        *self.open_orders.write().unwrap().entry((session_id, symbol.to_string())).or_insert(0) += 1;
+        *self.orders_today.write().unwrap().entry((session_id, day_bucket)).or_insert(0) += 1;
+        Ok(())
+    }
+
+    pub fn open_order_count(&self, session_id: Uuid, symbol: &str) -> u32 {
+        self.open_orders.read().unwrap().get(&(session_id, symbol.to_string())).copied().unwrap_or(0)
+    }
+
+    pub fn orders_today(&self, session_id: Uuid, day_bucket: i64) -> u32 {
+        self.orders_today.read().unwrap().get(&(session_id, day_bucket)).copied().unwrap_or(0)
+    }
+}
+
+/// Simulated time divided into hour-long buckets (`time_ms / 3_600_000`),
+/// the unit [`OrderRateTracker`] groups counts by. A caller-computed bucket
+/// rather than a method on `OrderRateTracker` itself, the same division of
+/// labor `OrderCountTracker::try_record`'s `day_bucket` already has — this
+/// tree has no wall clock to measure an hour against either, see this
+/// module's doc comment.
+pub fn hour_bucket(simulated_time_ms: i64) -> i64 {
+    simulated_time_ms.div_euclid(3_600_000)
+}
+
+/// Counts orders placed/canceled/filled per `(session, simulated hour)`, so
+/// a caller can sanity-check a bot isn't massively over-trading before ever
+/// looking at PnL. Exposed live via `GET /api/v1/sessions/:id/order-rate`
+/// and folded into [`crate::domain::SessionSummary::order_rate`] once a
+/// session ends. Like [`OrderCountTracker`], nothing calls
+/// `record_placed`/`record_canceled`/`record_filled` today — there's no
+/// order placement endpoint to call them from (see this module's doc
+/// comment) — so every session's rate is an empty series until a real
+/// order pipeline starts feeding it.
+type RateBucketKey = (Uuid, i64);
+
+#[derive(Debug, Default)]
+struct RateCounts {
+    placed: u64,
+    canceled: u64,
+    filled: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct OrderRateTracker {
+    buckets: RwLock<HashMap<RateBucketKey, RateCounts>>,
+}
+
+impl OrderRateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_placed(&self, session_id: Uuid, hour_bucket: i64) {
+        self.buckets.write().unwrap().entry((session_id, hour_bucket)).or_default().placed += 1;
+    }
+
+    pub fn record_canceled(&self, session_id: Uuid, hour_bucket: i64) {
+        self.buckets.write().unwrap().entry((session_id, hour_bucket)).or_default().canceled += 1;
+    }
+
+    pub fn record_filled(&self, session_id: Uuid, hour_bucket: i64) {
+        self.buckets.write().unwrap().entry((session_id, hour_bucket)).or_default().filled += 1;
+    }
+
+    /// Every hour bucket recorded for `session_id`, oldest first.
+    pub fn snapshot(&self, session_id: Uuid) -> Vec<OrderRateBucket> {
+        let mut buckets: Vec<OrderRateBucket> = self
+            .buckets
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|((session, _), _)| *session == session_id)
+            .map(|((_, hour), counts)| OrderRateBucket {
+                hour: *hour,
+                placed: counts.placed,
+                canceled: counts.canceled,
+                filled: counts.filled,
+            })
+            .collect();
+        buckets.sort_by_key(|bucket| bucket.hour);
+        buckets
+    }
+
+    /// Drops every bucket recorded for `session_id`, so a long-lived
+    /// instance doesn't grow this map forever across many short sessions.
+    pub fn clear(&self, session_id: Uuid) {
+        self.buckets.write().unwrap().retain(|(session, _), _| *session != session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_fill_timestamp_is_strictly_after_the_ack_timestamp() {
+        let (ack_at, fill_at) = sequence_ack_and_fill(1_000);
+        assert_eq!(ack_at, 1_000);
+        assert!(fill_at > ack_at);
+    }
+
+    #[test]
+    fn a_reduction_above_the_filled_quantity_is_accepted() {
+        use rust_decimal_macros::dec;
+        assert!(validate_quantity_amendment(dec!(10), dec!(2), dec!(5)).is_ok());
+    }
+
+    #[test]
+    fn an_increase_is_rejected() {
+        use rust_decimal_macros::dec;
+        assert!(validate_quantity_amendment(dec!(10), dec!(2), dec!(11)).is_err());
+    }
+
+    #[test]
+    fn a_reduction_below_the_filled_quantity_is_rejected() {
+        use rust_decimal_macros::dec;
+        assert!(validate_quantity_amendment(dec!(10), dec!(6), dec!(5)).is_err());
+    }
+
+    #[test]
+    fn first_placement_is_created() {
+        let registry = OrderIdempotencyRegistry::new();
+        let outcome = registry.place(Uuid::new_v4(), "my-order-1");
+        assert!(matches!(outcome, PlacementOutcome::Created(_)));
+    }
+
+    #[test]
+    fn resubmitting_the_same_client_id_replays_the_original_order() {
+        let registry = OrderIdempotencyRegistry::new();
+        let session_id = Uuid::new_v4();
+
+        let first = registry.place(session_id, "my-order-1");
+        let second = registry.place(session_id, "my-order-1");
+
+        let PlacementOutcome::Created(first_id) = first else {
+            panic!("expected Created");
+        };
+        assert_eq!(second, PlacementOutcome::Replayed(first_id));
+    }
+
+    #[test]
+    fn the_same_client_id_in_a_different_session_is_independent() {
+        let registry = OrderIdempotencyRegistry::new();
+        let first = registry.place(Uuid::new_v4(), "my-order-1");
+        let second = registry.place(Uuid::new_v4(), "my-order-1");
+        assert!(matches!(first, PlacementOutcome::Created(_)));
+        assert!(matches!(second, PlacementOutcome::Created(_)));
+    }
+
+    #[test]
+    fn an_order_with_no_recorded_fills_has_an_empty_list() {
+        let ledger = OrderFillsLedger::new();
+        assert!(ledger.list_order_fills(Uuid::new_v4()).is_empty());
+    }
+
+    #[test]
+    fn fills_are_listed_oldest_first_and_scoped_to_their_order() {
+        use rust_decimal_macros::dec;
+
+        let ledger = OrderFillsLedger::new();
+        let order_id = Uuid::new_v4();
+        let other_order_id = Uuid::new_v4();
+        let symbol = crate::domain::Symbol::new("BTCUSDT");
+
+        let fill = |quantity: Decimal| FillEvent {
+            order_id,
+            symbol: symbol.clone(),
+            price: dec!(100),
+            quantity,
+            commission: dec!(0.01),
+        };
+        ledger.record_fill(order_id, fill(dec!(1)));
+        ledger.record_fill(order_id, fill(dec!(2)));
+        ledger.record_fill(other_order_id, fill(dec!(99)));
+
+        let fills = ledger.list_order_fills(order_id);
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].quantity, dec!(1));
+        assert_eq!(fills[1].quantity, dec!(2));
+    }
+
+    #[test]
+    fn fill_ids_increase_monotonically_within_a_session() {
+        let sequence = FillIdSequence::new();
+        let session_id = Uuid::new_v4();
+        assert_eq!(sequence.next(session_id), 1);
+        assert_eq!(sequence.next(session_id), 2);
+        assert_eq!(sequence.next(session_id), 3);
+    }
+
+    #[test]
+    fn fill_ids_are_independent_per_session() {
+        let sequence = FillIdSequence::new();
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        assert_eq!(sequence.next(a), 1);
+        assert_eq!(sequence.next(b), 1);
+        assert_eq!(sequence.next(a), 2);
+    }
+
+    #[test]
+    fn rejects_once_the_per_symbol_open_order_cap_is_reached() {
+        let tracker = OrderCountTracker::new();
+        let session_id = Uuid::new_v4();
+        let limits = OrderLimitsConfig {
+            max_open_orders_per_symbol: Some(2),
+            max_orders_per_day: None,
+        };
+        assert!(tracker.try_record(session_id, "BTCUSDT", 0, &limits).is_ok());
+        assert!(tracker.try_record(session_id, "BTCUSDT", 0, &limits).is_ok());
+        assert!(tracker.try_record(session_id, "BTCUSDT", 0, &limits).is_err());
+    }
+
+    #[test]
+    fn the_open_order_cap_is_independent_per_symbol() {
+        let tracker = OrderCountTracker::new();
+        let session_id = Uuid::new_v4();
+        let limits = OrderLimitsConfig {
+            max_open_orders_per_symbol: Some(1),
+            max_orders_per_day: None,
+        };
+        assert!(tracker.try_record(session_id, "BTCUSDT", 0, &limits).is_ok());
+        assert!(tracker.try_record(session_id, "ETHUSDT", 0, &limits).is_ok());
+    }
+
+    #[test]
+    fn rejects_once_the_daily_order_cap_is_reached() {
+        let tracker = OrderCountTracker::new();
+        let session_id = Uuid::new_v4();
+        let limits = OrderLimitsConfig {
+            max_open_orders_per_symbol: None,
+            max_orders_per_day: Some(1),
+        };
+        assert!(tracker.try_record(session_id, "BTCUSDT", 0, &limits).is_ok());
+        assert!(tracker.try_record(session_id, "ETHUSDT", 0, &limits).is_err());
+        assert!(tracker.try_record(session_id, "BTCUSDT", 1, &limits).is_ok());
+    }
+
+    #[test]
+    fn hour_bucket_divides_simulated_time_into_one_hour_spans() {
+        assert_eq!(hour_bucket(0), 0);
+        assert_eq!(hour_bucket(3_599_999), 0);
+        assert_eq!(hour_bucket(3_600_000), 1);
+    }
+
+    #[test]
+    fn order_rate_snapshot_is_grouped_by_hour_and_sorted() {
+        let tracker = OrderRateTracker::new();
+        let session_id = Uuid::new_v4();
+        tracker.record_placed(session_id, 1);
+        tracker.record_placed(session_id, 0);
+        tracker.record_canceled(session_id, 0);
+        tracker.record_filled(session_id, 1);
+
+        let snapshot = tracker.snapshot(session_id);
+        assert_eq!(
+            snapshot,
+            vec![
+                OrderRateBucket { hour: 0, placed: 1, canceled: 1, filled: 0 },
+                OrderRateBucket { hour: 1, placed: 1, canceled: 0, filled: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn order_rate_buckets_do_not_leak_across_sessions() {
+        let tracker = OrderRateTracker::new();
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        tracker.record_placed(a, 0);
+        tracker.record_placed(b, 0);
+        assert_eq!(tracker.snapshot(a).len(), 1);
+
+        tracker.clear(a);
+        assert!(tracker.snapshot(a).is_empty());
+        assert_eq!(tracker.snapshot(b).len(), 1);
+    }
+}
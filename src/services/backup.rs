@@ -0,0 +1,146 @@
+//! Backs up and restores the full in-memory dataset (every kline and
+//! trade across every symbol) to a single file, since every store in this
+//! tree is in-memory only — see `store`'s module doc. [`backup`] pauses
+//! every running session before writing so a reader never sees a
+//! session's clock or replay stream advance mid-snapshot, then resumes
+//! exactly the sessions it paused.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{IngestionRecord, Kline, SessionStatus, Trade};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    klines: Vec<Kline>,
+    trades: Vec<Trade>,
+    /// Added after `klines`/`trades` shipped — defaulted so a backup
+    /// written before `ingestion_ledger` existed still restores cleanly.
+    #[serde(default)]
+    ingestion_records: Vec<IngestionRecord>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BackupSummary {
+    pub path: String,
+    pub kline_count: usize,
+    pub trade_count: usize,
+}
+
+/// Writes every kline and trade in `state`'s stores to `path` as JSON.
+/// Every `Running` session is paused for the duration of the write and
+/// resumed afterward (even if the write fails), so ingestion and replay
+/// can't mutate a store the snapshot is supposed to be consistent with.
+pub fn backup(state: &AppState, path: &str) -> Result<BackupSummary, ApiError> {
+    let paused_session_ids: Vec<_> = state.sessions_with_status(SessionStatus::Running).into_iter().map(|session| session.id).collect();
+    for id in &paused_session_ids {
+        let _ = state.set_status(*id, SessionStatus::Paused);
+    }
+
+    let snapshot = Snapshot {
+        klines: state.market_store().all_klines(),
+        trades: state.trade_store().all_trades(),
+        ingestion_records: state.ingestion_ledger().all(),
+    };
+    let result = write_snapshot(path, &snapshot);
+
+    for id in &paused_session_ids {
+        let _ = state.set_status(*id, SessionStatus::Running);
+    }
+
+    result.map(|()| BackupSummary {
+        path: path.to_string(),
+        kline_count: snapshot.klines.len(),
+        trade_count: snapshot.trades.len(),
+    })
+}
+
+fn write_snapshot(path: &str, snapshot: &Snapshot) -> Result<(), ApiError> {
+    let json = serde_json::to_vec(snapshot).map_err(|err| ApiError::BadRequest(format!("failed to serialize backup: {err}")))?;
+    fs::write(path, json).map_err(|err| ApiError::BadRequest(format!("failed to write backup to {path}: {err}")))
+}
+
+/// Loads a backup written by [`backup`] from `path` back into `state`'s
+/// stores, for startup restore (see `main`, gated on
+/// `Config::dataset_path`). Does nothing, successfully, if `path` doesn't
+/// exist — a fresh deployment has nothing to restore. Also restores
+/// `IngestionLedger` and immediately reconciles it: any record still
+/// `Ingesting` when the snapshot was written belonged to the process that
+/// just died, so it's flipped to `Resumable` — see `ingestion_ledger`'s
+/// module doc.
+pub fn restore_if_present(state: &AppState, path: &str) {
+    if !Path::new(path).exists() {
+        return;
+    }
+    let Ok(bytes) = fs::read(path) else {
+        tracing::warn!(path, "failed to read backup file, starting with an empty dataset");
+        return;
+    };
+    let Ok(snapshot) = serde_json::from_slice::<Snapshot>(&bytes) else {
+        tracing::warn!(path, "failed to parse backup file, starting with an empty dataset");
+        return;
+    };
+    let kline_count = snapshot.klines.len();
+    let trade_count = snapshot.trades.len();
+    state.market_store().insert_klines(snapshot.klines);
+    state.trade_store().insert_trades(snapshot.trades);
+    state.ingestion_ledger().replace_all(snapshot.ingestion_records);
+    let stale = state.ingestion_ledger().reconcile_stale();
+    tracing::info!(path, kline_count, trade_count, stale_ingestions = stale.len(), "restored dataset from backup");
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::Symbol;
+
+    fn kline(open_time: i64) -> Kline {
+        Kline {
+            symbol: Symbol::new("BTCUSDT"),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: dec!(1),
+            high: dec!(1),
+            low: dec!(1),
+            close: dec!(1),
+            volume: dec!(1),
+            quote_volume: dec!(1),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn backs_up_and_restores_every_kline() {
+        let dir = std::env::temp_dir().join(format!("exchange-simulator-backup-test-{}", std::process::id()));
+        let path = dir.to_string_lossy().to_string();
+
+        let state = AppState::new();
+        state.market_store().insert_kline(kline(0));
+        state.market_store().insert_kline(kline(60_000));
+
+        let summary = backup(&state, &path).unwrap();
+        assert_eq!(summary.kline_count, 2);
+        assert_eq!(summary.trade_count, 0);
+
+        let restored = AppState::new();
+        restore_if_present(&restored, &path);
+        let symbol = Symbol::new("BTCUSDT");
+        assert_eq!(restored.market_store().klines(&symbol, "1m", 0, 120_000).len(), 2);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn restoring_a_missing_path_does_nothing() {
+        let state = AppState::new();
+        restore_if_present(&state, "/nonexistent/exchange-simulator-backup.json");
+        assert_eq!(state.market_store().all_klines().len(), 0);
+    }
+}
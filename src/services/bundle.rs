@@ -0,0 +1,121 @@
+//! Exports a dataset (symbols/interval/range plus its klines and trades)
+//! as a single gzip-compressed JSON bundle, and imports one back in,
+//! so a prepared dataset can be handed between simulator instances
+//! instead of every team re-downloading it from Binance. Sibling to
+//! `services::backup`, which snapshots this instance's *entire* dataset
+//! for its own restart; a bundle is scoped to one dataset and meant to
+//! travel.
+
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Kline, Symbol, Trade};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Bundle {
+    symbols: Vec<Symbol>,
+    interval: String,
+    start: i64,
+    end: i64,
+    klines: Vec<Kline>,
+    trades: Vec<Trade>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ImportSummary {
+    pub symbols: Vec<Symbol>,
+    pub kline_count: usize,
+    pub trade_count: usize,
+}
+
+/// Gathers `symbols`' klines/trades in `[start, end)` at `interval` and
+/// gzip-compresses them alongside that coverage as JSON. Returns the raw
+/// bytes for the caller to hand back as a download.
+pub fn export(state: &AppState, symbols: &[Symbol], interval: &str, start: i64, end: i64) -> Result<Vec<u8>, ApiError> {
+    let mut klines = Vec::new();
+    let mut trades = Vec::new();
+    for symbol in symbols {
+        klines.extend(state.market_store().klines(symbol, interval, start, end));
+        trades.extend(state.trade_store().trades(symbol, start, end));
+    }
+    let bundle = Bundle { symbols: symbols.to_vec(), interval: interval.to_string(), start, end, klines, trades };
+
+    let json = serde_json::to_vec(&bundle).map_err(|err| ApiError::BadRequest(format!("failed to serialize bundle: {err}")))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&json).map_err(|err| ApiError::BadRequest(format!("failed to compress bundle: {err}")))?;
+    encoder.finish().map_err(|err| ApiError::BadRequest(format!("failed to compress bundle: {err}")))
+}
+
+/// Decompresses and inserts a bundle written by [`export`] into `state`'s
+/// stores, and registers its coverage in `DatasetRegistry` (deduped with
+/// any overlapping coverage already registered there). Klines/trades that
+/// already exist at the same keys are simply overwritten, the same
+/// last-write-wins behavior `store::MarketStore::insert_klines` always
+/// has.
+pub fn import(state: &AppState, compressed: &[u8]) -> Result<ImportSummary, ApiError> {
+    let mut json = Vec::new();
+    GzDecoder::new(compressed)
+        .read_to_end(&mut json)
+        .map_err(|err| ApiError::BadRequest(format!("failed to decompress bundle: {err}")))?;
+    let bundle: Bundle = serde_json::from_slice(&json).map_err(|err| ApiError::BadRequest(format!("failed to parse bundle: {err}")))?;
+
+    let kline_count = bundle.klines.len();
+    let trade_count = bundle.trades.len();
+    state.market_store().insert_klines(bundle.klines);
+    state.trade_store().insert_trades(bundle.trades);
+    let _ = state.dataset_registry().register(bundle.symbols.clone(), bundle.interval, bundle.start, bundle.end, true);
+
+    Ok(ImportSummary { symbols: bundle.symbols, kline_count, trade_count })
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::Symbol;
+
+    fn kline(symbol: &Symbol, open_time: i64) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: dec!(1),
+            high: dec!(1),
+            low: dec!(1),
+            close: dec!(1),
+            volume: dec!(1),
+            quote_volume: dec!(1),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn a_round_tripped_bundle_restores_every_kline_into_a_fresh_instance() {
+        let symbol = Symbol::new("BTCUSDT");
+        let source = AppState::new();
+        source.market_store().insert_kline(kline(&symbol, 0));
+        source.market_store().insert_kline(kline(&symbol, 60_000));
+
+        let compressed = export(&source, std::slice::from_ref(&symbol), "1m", 0, 120_000).unwrap();
+
+        let destination = AppState::new();
+        let summary = import(&destination, &compressed).unwrap();
+        assert_eq!(summary.kline_count, 2);
+        assert_eq!(summary.trade_count, 0);
+        assert_eq!(destination.market_store().klines(&symbol, "1m", 0, 120_000).len(), 2);
+    }
+
+    #[test]
+    fn importing_garbage_bytes_fails_instead_of_panicking() {
+        let state = AppState::new();
+        assert!(import(&state, b"not a gzip stream").is_err());
+    }
+}
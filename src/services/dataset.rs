@@ -0,0 +1,714 @@
+//! On-demand dataset readiness for session creation (see
+//! `http::sessions::create_session`'s `dataset` requirement): checks every
+//! requested symbol has data for the requested range before letting a
+//! session start, instead of starting silently against an empty store.
+//!
+//! [`gaps`] can only tell whether a symbol has *any* data in the range,
+//! not whether every expected candle within it is present — a real
+//! gap-within-range report needs per-candle bookkeeping this tree doesn't
+//! have yet and is left for when ingestion lands for real. When a gap is
+//! found and the caller asked to auto-ingest, [`spawn_backfill`] fills it
+//! with the same synthetic, pipelined generator `simulator::seed_history`
+//! uses for the demo dataset, stepped by [`interval_ms`] for whatever
+//! interval the gap was found at (falling back to [`CANDLE_STEP_MS`] for an
+//! interval string that doesn't parse), since no external fetch source is
+//! wired into this tree either. Once backfilling finishes it fires
+//! `WebhookEvent::DatasetReady` for the session — the asynchronous
+//! readiness transition `webhook.rs` documented as having nothing to hook,
+//! until now.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use uuid::Uuid;
+
+use crate::dataset_progress::ESTIMATED_BYTES_PER_ROW;
+use crate::domain::{interval_ms, Decimal, DatasetEstimate, Kline, MissingSpan, Symbol, WebhookEvent};
+use crate::error::ApiError;
+use crate::services::rate_limit::RateLimiter;
+use crate::services::run_ingest_with_progress;
+use crate::state::AppState;
+
+pub(crate) const BACKFILL_CHUNK_CANDLES: i64 = 500;
+/// Binance's default 1-minute request-weight budget — see
+/// `services::rate_limit`'s module doc for why nothing in this tree ever
+/// actually pushes `used_weight` toward it.
+const WEIGHT_LIMIT: u32 = 1200;
+/// Candle step used when `interval` doesn't parse via [`interval_ms`] —
+/// preserves the fixed-step behavior this module had before interval-aware
+/// stepping landed, rather than failing a backfill outright over a bad
+/// interval string `gaps` already accepted.
+pub(crate) const CANDLE_STEP_MS: i64 = 60_000;
+
+/// Symbols in `symbols` with no klines at all for `interval` in `[start, end)`.
+/// A symbol registered as delisted (see
+/// `symbol_registry::SymbolLifecycleRegistry`) is only checked up to its
+/// delisting point — there's never going to be data past it, so a range
+/// entirely after delisting isn't reported as a gap at all rather than one
+/// `spawn_backfill` would retry forever without ever filling.
+pub fn gaps(state: &AppState, symbols: &[Symbol], interval: &str, start: i64, end: i64) -> Vec<Symbol> {
+    symbols
+        .iter()
+        .filter(|symbol| {
+            let end = state.symbol_lifecycle().delisted_at(symbol).map_or(end, |delisted_at| delisted_at.min(end));
+            start < end && state.market_store().klines(symbol, interval, start, end).is_empty()
+        })
+        .cloned()
+        .collect()
+}
+
+/// [`gaps`]'s counterpart for `store::TradeStore`, used when a session's
+/// [`MarketMode`](crate::domain::MarketMode) requires trade data. There's
+/// no trade backfill pipeline in this tree (unlike `spawn_backfill` for
+/// klines) — `ReplayService` only ever synthesizes trades at tick time for
+/// a running session, nothing populates the store ahead of one — so this
+/// exists purely to give `http::sessions::create_session` an honest gap
+/// list to reject a `dataset` requirement against rather than silently
+/// accepting a requirement it can never satisfy.
+pub fn trade_gaps(state: &AppState, symbols: &[Symbol], start: i64, end: i64) -> Vec<Symbol> {
+    symbols
+        .iter()
+        .filter(|symbol| {
+            let end = state.symbol_lifecycle().delisted_at(symbol).map_or(end, |delisted_at| delisted_at.min(end));
+            start < end && state.trade_store().trades(symbol, start, end).is_empty()
+        })
+        .cloned()
+        .collect()
+}
+
+/// Every active (not yet [`SessionStatus::Ended`](crate::domain::SessionStatus::Ended)
+/// or [`SessionStatus::Stopped`](crate::domain::SessionStatus::Stopped)) session
+/// reading `record`'s symbols at `record`'s interval, across every
+/// namespace — `DatasetRegistry` isn't namespace-scoped either (see
+/// `http::datasets::register_dataset`'s doc comment), so neither is this.
+/// Used by `http::admin::delete_dataset` to reject (or cascade-stop)
+/// deleting a dataset still in use, since a session doesn't hold any copy
+/// of its own — it reads `store::MarketStore` live on every tick.
+pub fn dependent_sessions(state: &AppState, record: &crate::domain::DatasetRecord) -> Vec<crate::domain::Session> {
+    use crate::domain::SessionStatus;
+    state
+        .all_sessions()
+        .into_iter()
+        .filter(|session| !matches!(session.status, SessionStatus::Ended | SessionStatus::Stopped))
+        .filter(|session| {
+            session.interval == record.interval
+                && session
+                    .symbols
+                    .iter()
+                    .chain(session.watchlist.iter())
+                    .any(|symbol| record.symbols.contains(symbol))
+        })
+        .collect()
+}
+
+/// Deterministic fingerprint of the klines rows backing `symbols`'
+/// `interval` data over `[start, end)`, so two sessions that replayed
+/// against the same rows can confirm it without diffing them row by row.
+/// Symbols are sorted before hashing so registering the same backfill with
+/// its symbols in a different order doesn't change the result. Not a
+/// cryptographic hash — `DefaultHasher` is good enough for "did this
+/// change", not for tamper-resistance.
+pub(crate) fn content_hash(state: &AppState, symbols: &[Symbol], interval: &str, start: i64, end: i64) -> String {
+    let mut sorted_symbols = symbols.to_vec();
+    sorted_symbols.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for symbol in &sorted_symbols {
+        for kline in state.market_store().klines(symbol, interval, start, end) {
+            kline.symbol.hash(&mut hasher);
+            kline.interval.hash(&mut hasher);
+            kline.open_time.hash(&mut hasher);
+            kline.close_time.hash(&mut hasher);
+            kline.open.to_string().hash(&mut hasher);
+            kline.high.to_string().hash(&mut hasher);
+            kline.low.to_string().hash(&mut hasher);
+            kline.close.to_string().hash(&mut hasher);
+            kline.volume.to_string().hash(&mut hasher);
+            kline.quote_volume.to_string().hash(&mut hasher);
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Every contiguous run of missing candle slots for `symbol`/`interval` in
+/// `[start, end)`, coalesced rather than reported one candle at a time —
+/// unlike [`gaps`], which can only say a symbol has *no* data somewhere in
+/// a range, this walks every expected `open_time` and reports exactly
+/// which spans are missing, for `http::market::availability`.
+pub fn missing_spans(state: &AppState, symbol: &Symbol, interval: &str, start: i64, end: i64) -> Result<Vec<MissingSpan>, ApiError> {
+    let step_ms = interval_ms(interval).ok_or_else(|| ApiError::BadRequest(format!("unrecognized interval: {interval}")))?;
+    let existing: std::collections::BTreeSet<i64> =
+        state.market_store().klines(symbol, interval, start, end).into_iter().map(|k| k.open_time).collect();
+
+    let mut spans = Vec::new();
+    let mut gap_start = None;
+    let mut open_time = start;
+    while open_time < end {
+        if existing.contains(&open_time) {
+            if let Some(gap_start) = gap_start.take() {
+                spans.push(MissingSpan { start: gap_start, end: open_time });
+            }
+        } else if gap_start.is_none() {
+            gap_start = Some(open_time);
+        }
+        open_time += step_ms;
+    }
+    if let Some(gap_start) = gap_start {
+        spans.push(MissingSpan { start: gap_start, end });
+    }
+    Ok(spans)
+}
+
+/// Up to `limit` klines straddling `at` for `symbol`/`interval` within
+/// `[start, end)` — `http::datasets::preview_dataset`'s "small window
+/// around a timestamp" for a UI to chart before committing to a session.
+/// Splits `limit` evenly between the candles just before `at` and the
+/// ones at or after it, so a preview near either edge of the range just
+/// gets fewer of the side that's run out rather than failing.
+pub fn preview(state: &AppState, symbol: &Symbol, interval: &str, start: i64, end: i64, at: i64, limit: usize) -> Vec<Kline> {
+    let at = at.clamp(start, end);
+    let half = limit / 2;
+
+    let mut before = state.market_store().klines(symbol, interval, start, at);
+    if before.len() > half {
+        before = before.split_off(before.len() - half);
+    }
+
+    let after = state.market_store().klines_page(symbol, interval, at.saturating_sub(1), end, limit - before.len());
+    before.extend(after);
+    before
+}
+
+/// Projects how big and how long an ingestion for `symbol_count` symbols'
+/// `interval` klines over `[start, end)` would be, without fetching or
+/// storing anything — a dry run for callers who routinely misjudge how
+/// long a multi-year, 1m-interval backfill takes. Chunked the same way
+/// [`spawn_backfill`] chunks a real one ([`BACKFILL_CHUNK_CANDLES`] candles
+/// per request), so `request_count` matches what an actual backfill would
+/// issue.
+pub fn estimate(state: &AppState, symbol_count: usize, interval: &str, start: i64, end: i64) -> Result<DatasetEstimate, ApiError> {
+    let interval_ms = interval_ms(interval).ok_or_else(|| ApiError::BadRequest(format!("unrecognized interval: {interval}")))?;
+    let symbol_count = symbol_count as i64;
+
+    let candles_per_symbol = (end - start).max(0) / interval_ms;
+    let candle_count = candles_per_symbol * symbol_count;
+    let requests_per_symbol = (candles_per_symbol + BACKFILL_CHUNK_CANDLES - 1) / BACKFILL_CHUNK_CANDLES.max(1);
+    let request_count = requests_per_symbol * symbol_count;
+
+    let estimated_duration_seconds = state.config().rate_limit_per_minute.and_then(|limit| {
+        if limit == 0 {
+            return None;
+        }
+        let limit = limit as i64;
+        let minutes_needed = (request_count + limit - 1) / limit;
+        Some(minutes_needed * 60)
+    });
+
+    Ok(DatasetEstimate {
+        candle_count,
+        request_count,
+        estimated_duration_seconds,
+        estimated_storage_bytes: candle_count.saturating_mul(ESTIMATED_BYTES_PER_ROW),
+    })
+}
+
+/// Backfills every symbol in `gaps` with synthetic candles covering
+/// `[start, end)`, then fires `WebhookEvent::DatasetReady` for
+/// `session_id`. Spawned so `POST /api/v1/sessions` can return the new
+/// session immediately instead of blocking the response on however long
+/// backfilling takes. Reports throughput and ETA to `session_id`'s
+/// `DatasetProgressTracker` as chunks land, so a UI polling the session can
+/// show a realistic ETA for a multi-month ingestion instead of a spinner
+/// with no numbers.
+pub fn spawn_backfill(state: AppState, session_id: Uuid, gaps: Vec<Symbol>, interval: String, start: i64, end: i64) {
+    let ledger_id = state.ingestion_ledger().start(session_id, gaps.clone(), interval.clone(), start, end);
+    spawn_backfill_job(state, ledger_id, session_id, gaps, interval, start, end);
+}
+
+/// Re-attempts every `Resumable` `IngestionLedger` record whose session
+/// still exists in `state` — see `ingestion_ledger`'s module doc for why
+/// that's never true after a real process restart today. Intended to be
+/// called once at startup, after `backup::restore_if_present` has loaded
+/// a snapshot and reconciled its stale records.
+pub fn resume_stale_ingestions(state: &AppState) {
+    for record in state.ingestion_ledger().all() {
+        if record.status != crate::domain::IngestStatus::Resumable {
+            continue;
+        }
+        if state.get_session(record.session_id).is_err() {
+            continue;
+        }
+        state.ingestion_ledger().resume(record.id);
+        spawn_backfill_job(state.clone(), record.id, record.session_id, record.symbols, record.interval, record.start, record.end);
+    }
+}
+
+fn spawn_backfill_job(state: AppState, ledger_id: Uuid, session_id: Uuid, gaps: Vec<Symbol>, interval: String, start: i64, end: i64) {
+    tokio::spawn(async move {
+        let step_ms = interval_ms(&interval).unwrap_or(CANDLE_STEP_MS);
+        let candles_per_symbol = ((end - start).max(0)) / step_ms;
+        let total_expected = candles_per_symbol * gaps.len() as i64;
+        let backfill_start = Instant::now();
+        let mut rows_inserted = 0i64;
+        let mut anomalies = 0u64;
+        // Shared across every symbol in this backfill, the way Binance's
+        // weight budget is shared across every request an API key makes,
+        // not reset per-symbol.
+        let limiter = Arc::new(Mutex::new(RateLimiter::new(WEIGHT_LIMIT)));
+
+        for symbol in &gaps {
+            let (inserted, rejected) = backfill_symbol(
+                &state,
+                session_id,
+                symbol,
+                &interval,
+                start,
+                end,
+                backfill_start,
+                rows_inserted,
+                anomalies,
+                total_expected,
+                &limiter,
+            )
+            .await;
+            rows_inserted += inserted;
+            anomalies += rejected;
+        }
+        let hash = content_hash(&state, &gaps, &interval, start, end);
+        state.ingestion_ledger().complete(ledger_id, rows_inserted, hash);
+        state.fire_webhook(
+            session_id,
+            WebhookEvent::DatasetReady,
+            serde_json::json!({ "session_id": session_id, "symbols": gaps }),
+        );
+    });
+}
+
+/// Backfills one symbol, validating every synthesized chunk against
+/// `[start, end)` with [`crate::services::validate_klines`] before it
+/// reaches the store. Returns `(rows_inserted, rows_rejected)` for this
+/// symbol alone; the caller folds both into its running totals the same
+/// way it already does for `rows_before`.
+#[allow(clippy::too_many_arguments)]
+async fn backfill_symbol(
+    state: &AppState,
+    session_id: Uuid,
+    symbol: &Symbol,
+    interval: &str,
+    start: i64,
+    end: i64,
+    backfill_start: Instant,
+    rows_before: i64,
+    anomalies_before: u64,
+    total_expected: i64,
+    limiter: &Arc<Mutex<RateLimiter>>,
+) -> (i64, u64) {
+    let step_ms = interval_ms(interval).unwrap_or(CANDLE_STEP_MS);
+    let symbol = symbol.clone();
+    let interval = interval.to_string();
+    let mut next_open_time = start;
+    let tracker = state.dataset_progress_tracker(session_id).ok();
+    let anomalies = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let anomalies_for_fetch = anomalies.clone();
+    let anomalies_for_result = anomalies.clone();
+    let fetch_limiter = limiter.clone();
+    let progress_limiter = limiter.clone();
+    let inserted = run_ingest_with_progress(
+        state.market_store(),
+        move || {
+            let symbol = symbol.clone();
+            let interval = interval.clone();
+            let chunk_start = next_open_time;
+            next_open_time += BACKFILL_CHUNK_CANDLES * step_ms;
+            let anomalies = anomalies_for_fetch.clone();
+            let limiter = fetch_limiter.clone();
+            async move {
+                if chunk_start >= end {
+                    return None;
+                }
+                let delay = limiter.lock().unwrap().delay();
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                let raw = synthetic_chunk(&symbol, &interval, chunk_start, end, step_ms);
+                let (valid, rejected) = crate::services::validate_klines(raw, start, end);
+                anomalies.fetch_add(rejected, std::sync::atomic::Ordering::Relaxed);
+                Some(valid)
+            }
+        },
+        move |inserted_this_symbol, _elapsed| {
+            if let Some(tracker) = &tracker {
+                let rows_ingested = rows_before + inserted_this_symbol as i64;
+                let remaining = (total_expected - rows_ingested).max(0);
+                let anomalies_so_far = anomalies_before + anomalies.load(std::sync::atomic::Ordering::Relaxed);
+                tracker.record(rows_ingested, backfill_start.elapsed(), Some(remaining), anomalies_so_far);
+                let throttle = progress_limiter.lock().unwrap().state();
+                tracker.record_throttle(throttle.used_weight, throttle.reason, throttle.retry_after.map(|d| d.as_millis() as u64));
+            }
+        },
+    )
+    .await;
+    (inserted as i64, anomalies_for_result.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+fn synthetic_chunk(symbol: &Symbol, interval: &str, chunk_start: i64, end: i64, step_ms: i64) -> Vec<Kline> {
+    (0..BACKFILL_CHUNK_CANDLES)
+        .map(|i| chunk_start + i * step_ms)
+        .take_while(|&open_time| open_time < end)
+        .map(|open_time| Kline {
+            symbol: symbol.clone(),
+            interval: interval.to_string(),
+            open_time,
+            close_time: open_time + (step_ms - 1),
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{InMemoryMarketStore, MarketStore};
+
+    #[test]
+    fn a_symbol_with_no_klines_in_range_is_a_gap() {
+        let store = InMemoryMarketStore::new();
+        let symbol = Symbol::new("BTCUSDT");
+        store.insert_kline(Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        });
+
+        let state = AppState::new();
+        state.market_store().insert_klines(store.klines(&symbol, "1m", 0, 60_000));
+
+        let missing = Symbol::new("ETHUSDT");
+        let found = gaps(&state, &[symbol, missing.clone()], "1m", 0, 60_000);
+        assert_eq!(found, vec![missing]);
+    }
+
+    fn session(state: &AppState, symbols: Vec<Symbol>, interval: &str, status: crate::domain::SessionStatus) -> crate::domain::Session {
+        let session = crate::domain::Session {
+            id: Uuid::new_v4(),
+            namespace_id: Uuid::new_v4(),
+            symbols,
+            watchlist: Vec::new(),
+            interval: interval.into(),
+            speed: 1.0,
+            status,
+            clock_drift: None,
+            commission: None,
+            margin: None,
+            allow_short: false,
+            chaos: None,
+            mark_price: None,
+            start_time: 0,
+            warmup_start: None,
+            current_time: 0,
+            order_limits: None,
+            clock_group: None,
+            live_source: None,
+            matching_engine: None,
+            pause_at: None,
+            breakpoints: Vec::new(),
+            candle_align_offset_ms: 0,
+            equity_snapshot_interval_ms: None,
+            seek_to: None,
+            ack_before_trade: false,
+            market_mode: None,
+            name: None,
+            tags: Vec::new(),
+            matching_enabled: false,
+        };
+        state.insert_session(session.clone());
+        session
+    }
+
+    #[tokio::test]
+    async fn dependent_sessions_finds_a_session_only_watching_a_symbol() {
+        let state = AppState::new();
+        let btc = Symbol::new("BTCUSDT");
+        let mut watching = session(&state, vec![Symbol::new("ETHUSDT")], "1m", crate::domain::SessionStatus::Running);
+        watching.watchlist = vec![btc.clone()];
+        state.insert_session(watching.clone());
+
+        let record = crate::domain::DatasetRecord { id: Uuid::new_v4(), symbols: vec![btc], interval: "1m".into(), start: 0, end: 1000 };
+        let found = dependent_sessions(&state, &record);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, watching.id);
+    }
+
+    #[tokio::test]
+    async fn dependent_sessions_finds_an_active_session_sharing_a_symbol_and_interval() {
+        let state = AppState::new();
+        let btc = Symbol::new("BTCUSDT");
+        let running = session(&state, vec![btc.clone()], "1m", crate::domain::SessionStatus::Running);
+
+        let record = crate::domain::DatasetRecord { id: Uuid::new_v4(), symbols: vec![btc], interval: "1m".into(), start: 0, end: 1000 };
+        let found = dependent_sessions(&state, &record);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, running.id);
+    }
+
+    #[tokio::test]
+    async fn dependent_sessions_ignores_ended_and_stopped_sessions() {
+        use crate::domain::SessionStatus;
+
+        let state = AppState::new();
+        let btc = Symbol::new("BTCUSDT");
+        session(&state, vec![btc.clone()], "1m", SessionStatus::Ended);
+        session(&state, vec![btc.clone()], "1m", SessionStatus::Stopped);
+
+        let record = crate::domain::DatasetRecord { id: Uuid::new_v4(), symbols: vec![btc], interval: "1m".into(), start: 0, end: 1000 };
+        assert!(dependent_sessions(&state, &record).is_empty());
+    }
+
+    #[tokio::test]
+    async fn dependent_sessions_ignores_a_different_interval() {
+        let state = AppState::new();
+        let btc = Symbol::new("BTCUSDT");
+        session(&state, vec![btc.clone()], "5m", crate::domain::SessionStatus::Running);
+
+        let record = crate::domain::DatasetRecord { id: Uuid::new_v4(), symbols: vec![btc], interval: "1m".into(), start: 0, end: 1000 };
+        assert!(dependent_sessions(&state, &record).is_empty());
+    }
+
+    #[test]
+    fn a_symbol_with_no_trades_in_range_is_a_trade_gap() {
+        use crate::domain::Trade;
+
+        let state = AppState::new();
+        let covered = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(Trade {
+            symbol: covered.clone(),
+            agg_trade_id: 1,
+            price: Decimal::new(1, 0),
+            quantity: Decimal::new(1, 0),
+            trade_time: 0,
+            is_buyer_maker: false,
+        });
+
+        let missing = Symbol::new("ETHUSDT");
+        let found = trade_gaps(&state, &[covered, missing.clone()], 0, 60_000);
+        assert_eq!(found, vec![missing]);
+    }
+
+    #[test]
+    fn a_range_entirely_after_delisting_is_never_reported_as_a_gap() {
+        let state = AppState::new();
+        let symbol = Symbol::new("OLDUSDT");
+        state
+            .symbol_lifecycle()
+            .register(crate::domain::SymbolLifecycleEvent::Delisted { symbol: symbol.clone(), effective_at: 60_000 });
+
+        let found = gaps(&state, &[symbol], "1m", 120_000, 180_000);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn missing_spans_reports_a_gap_sandwiched_between_present_candles() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        let kline = |open_time: i64| Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        };
+        state.market_store().insert_klines(vec![kline(0), kline(180_000)]);
+
+        let spans = missing_spans(&state, &symbol, "1m", 0, 240_000).unwrap();
+        assert_eq!(spans, vec![MissingSpan { start: 60_000, end: 180_000 }]);
+    }
+
+    #[test]
+    fn missing_spans_is_empty_for_fully_covered_range() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.market_store().insert_klines(vec![Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        }]);
+
+        let spans = missing_spans(&state, &symbol, "1m", 0, 60_000).unwrap();
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn preview_splits_the_limit_evenly_around_the_centered_timestamp() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        let kline = |open_time: i64| Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        };
+        let klines: Vec<Kline> = (0..10).map(|i| kline(i * 60_000)).collect();
+        state.market_store().insert_klines(klines);
+
+        let window = preview(&state, &symbol, "1m", 0, 600_000, 300_000, 4);
+        assert_eq!(window.len(), 4);
+        assert_eq!(window[0].open_time, 180_000);
+        assert_eq!(window.last().unwrap().open_time, 360_000);
+    }
+
+    #[test]
+    fn preview_returns_fewer_than_the_limit_near_the_start_of_the_range() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.market_store().insert_klines(vec![Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        }]);
+
+        let window = preview(&state, &symbol, "1m", 0, 60_000, 0, 100);
+        assert_eq!(window.len(), 1);
+    }
+
+    #[test]
+    fn missing_spans_rejects_an_unrecognized_interval() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        assert!(missing_spans(&state, &symbol, "bogus", 0, 60_000).is_err());
+    }
+
+    #[test]
+    fn content_hash_is_stable_regardless_of_symbol_order() {
+        let state = AppState::new();
+        let btc = Symbol::new("BTCUSDT");
+        let eth = Symbol::new("ETHUSDT");
+        state.market_store().insert_klines(vec![Kline {
+            symbol: btc.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        }]);
+
+        let forward = content_hash(&state, &[btc.clone(), eth.clone()], "1m", 0, 60_000);
+        let reversed = content_hash(&state, &[eth, btc], "1m", 0, 60_000);
+        assert_eq!(forward, reversed);
+    }
+
+    #[test]
+    fn content_hash_changes_when_the_underlying_rows_do() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        let empty = content_hash(&state, std::slice::from_ref(&symbol), "1m", 0, 60_000);
+
+        state.market_store().insert_klines(vec![Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: Decimal::new(1, 0),
+            high: Decimal::new(1, 0),
+            low: Decimal::new(1, 0),
+            close: Decimal::new(1, 0),
+            volume: Decimal::new(1, 0),
+            quote_volume: Decimal::new(1, 0),
+            is_closed: true,
+        }]);
+        let filled = content_hash(&state, &[symbol], "1m", 0, 60_000);
+
+        assert_ne!(empty, filled);
+    }
+
+    #[test]
+    fn estimate_counts_candles_and_chunked_requests_across_symbols() {
+        let state = AppState::new();
+        let result = estimate(&state, 2, "1m", 0, 1_000 * 60_000).unwrap();
+        assert_eq!(result.candle_count, 2_000);
+        assert_eq!(result.request_count, 4); // ceil(1000 / 500) per symbol, times 2 symbols
+        assert_eq!(result.estimated_duration_seconds, None);
+    }
+
+    #[test]
+    fn estimate_derives_duration_from_the_configured_rate_limit() {
+        let state = AppState::new();
+        state.set_config(crate::config::Config { rate_limit_per_minute: Some(2), ..Default::default() });
+
+        let result = estimate(&state, 1, "1m", 0, 1_000 * 60_000).unwrap();
+        assert_eq!(result.request_count, 2);
+        assert_eq!(result.estimated_duration_seconds, Some(60));
+    }
+
+    #[test]
+    fn estimate_rejects_an_unrecognized_interval() {
+        let state = AppState::new();
+        let err = estimate(&state, 1, "bogus", 0, 60_000).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn backfilling_a_gap_fills_the_whole_requested_range() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+
+        let limiter = Arc::new(Mutex::new(RateLimiter::new(1200)));
+        let (inserted, anomalies) = backfill_symbol(&state, Uuid::nil(), &symbol, "1m", 0, 120_000, Instant::now(), 0, 0, 2, &limiter).await;
+
+        assert_eq!(inserted, 2);
+        assert_eq!(anomalies, 0);
+        let klines = state.market_store().klines(&symbol, "1m", 0, 120_000);
+        assert_eq!(klines.len(), 2);
+        assert_eq!(klines[0].open_time, 0);
+        assert_eq!(klines[1].open_time, 60_000);
+    }
+}
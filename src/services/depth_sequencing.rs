@@ -0,0 +1,121 @@
+//! Update-id sequencing for a future Binance-compatible `<symbol>@depth`
+//! diff stream.
+//!
+//! There's no simulated order book anywhere in this tree — `domain` has no
+//! depth/book type, `ReplayService` only ever synthesizes klines and
+//! (implicitly, via `mark_price`) prices, never a bid/ask ladder — so there
+//! is no book to snapshot via `/api/v3/depth` or diff via `<symbol>@depth`
+//! yet, and this doesn't add either. What's here is the part of the request
+//! that doesn't depend on a book existing: Binance's documented book-sync
+//! algorithm requires each diff event to carry a `(U, u)` first/final
+//! update-id pair such that a client can tell it hasn't missed an update —
+//! `U <= lastUpdateId + 1 <= u` against the REST snapshot it started from,
+//! then `event.U == previous.u + 1` for every one after. [`DepthUpdateSequencer`]
+//! hands out exactly that pair, and [`follows`]/[`covers_snapshot`] implement
+//! the client-side checks, so whichever future depth feed needs this doesn't
+//! have to derive it from scratch.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::domain::Symbol;
+
+/// The `(U, u)` pair Binance's depth diff events carry, named after the
+/// fields the exchange actually emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthUpdateRange {
+    pub first_update_id: i64,
+    pub final_update_id: i64,
+}
+
+/// Hands out non-overlapping, gap-free `(U, u)` ranges per symbol, across
+/// the lifetime of the process — global like `store::MarketStore`, not
+/// per-session, since a depth feed (once it exists) would be market data
+/// shared across sessions the same way klines and trades already are.
+#[derive(Debug, Default)]
+pub struct DepthUpdateSequencer {
+    last_update_id: RwLock<HashMap<Symbol, i64>>,
+}
+
+impl DepthUpdateSequencer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns the next range for `symbol`, covering `event_count`
+    /// individual book changes folded into one diff event (at least 1).
+    pub fn next(&self, symbol: &Symbol, event_count: i64) -> DepthUpdateRange {
+        let event_count = event_count.max(1);
+        let mut last_update_id = self.last_update_id.write().unwrap();
+        let previous = *last_update_id.get(symbol).unwrap_or(&0);
+        let range = DepthUpdateRange {
+            first_update_id: previous + 1,
+            final_update_id: previous + event_count,
+        };
+        last_update_id.insert(symbol.clone(), range.final_update_id);
+        range
+    }
+}
+
+/// True if `event` could validly follow `previous` per Binance's documented
+/// book-sync algorithm: every event after the first must pick up exactly
+/// where the last one left off, with no gap and no overlap.
+pub fn follows(previous: &DepthUpdateRange, event: &DepthUpdateRange) -> bool {
+    event.first_update_id == previous.final_update_id + 1
+}
+
+/// True if `event` is a valid first event to apply against a REST snapshot
+/// whose `lastUpdateId` is `snapshot_last_update_id` — the event must
+/// straddle the snapshot's id rather than start strictly after or end
+/// strictly before it.
+pub fn covers_snapshot(event: &DepthUpdateRange, snapshot_last_update_id: i64) -> bool {
+    event.first_update_id <= snapshot_last_update_id + 1 && event.final_update_id >= snapshot_last_update_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successive_ranges_for_a_symbol_have_no_gap_or_overlap() {
+        let sequencer = DepthUpdateSequencer::new();
+        let symbol = Symbol::new("BTCUSDT");
+
+        let first = sequencer.next(&symbol, 3);
+        let second = sequencer.next(&symbol, 2);
+
+        assert_eq!(first, DepthUpdateRange { first_update_id: 1, final_update_id: 3 });
+        assert_eq!(second, DepthUpdateRange { first_update_id: 4, final_update_id: 5 });
+        assert!(follows(&first, &second));
+    }
+
+    #[test]
+    fn ranges_are_independent_per_symbol() {
+        let sequencer = DepthUpdateSequencer::new();
+        let btc = sequencer.next(&Symbol::new("BTCUSDT"), 1);
+        let eth = sequencer.next(&Symbol::new("ETHUSDT"), 1);
+        assert_eq!(btc.first_update_id, 1);
+        assert_eq!(eth.first_update_id, 1);
+    }
+
+    #[test]
+    fn a_range_with_a_gap_does_not_follow() {
+        let previous = DepthUpdateRange { first_update_id: 1, final_update_id: 5 };
+        let with_gap = DepthUpdateRange { first_update_id: 7, final_update_id: 9 };
+        assert!(!follows(&previous, &with_gap));
+    }
+
+    #[test]
+    fn an_event_straddling_the_snapshot_id_covers_it() {
+        let event = DepthUpdateRange { first_update_id: 100, final_update_id: 105 };
+        assert!(covers_snapshot(&event, 102));
+        assert!(covers_snapshot(&event, 100)); // U <= lastUpdateId + 1
+        assert!(covers_snapshot(&event, 105)); // u >= lastUpdateId
+    }
+
+    #[test]
+    fn an_event_entirely_after_the_snapshot_id_does_not_cover_it() {
+        let event = DepthUpdateRange { first_update_id: 100, final_update_id: 105 };
+        assert!(!covers_snapshot(&event, 50));
+    }
+}
@@ -0,0 +1,86 @@
+//! Binance-compatible CSV export of a session's execution history — see
+//! `http::sessions::export_session_trades`.
+//!
+//! There's no order/fill pipeline in this tree (see `orders`'s module
+//! doc) — `orders::OrderFillsLedger` exists but nothing ever calls
+//! `record_fill` on it, so there's no real per-order fill to export. The
+//! closest execution-shaped data a session actually produces is the
+//! `Trade` prints `ReplayService` synthesizes when
+//! [`MarketMode::includes_trades`](crate::domain::MarketMode::includes_trades)
+//! is set, so [`trade_history_csv`] exports those instead, laid out in
+//! the same column order Binance's own spot "Trade History" CSV export
+//! uses. `Fee` is always `0` (no commission is ever charged against a
+//! synthetic trade — see `domain::commission`) and `Executed`/`Amount`
+//! are bare decimal quantities rather than Binance's asset-suffixed
+//! strings (e.g. `0.01000000BTC`), since [`Symbol`](crate::domain::Symbol)
+//! has no base/quote split to suffix them with.
+
+use crate::domain::Trade;
+
+const HEADER: &str = "Date(UTC),Pair,Side,Price,Executed,Amount,Fee";
+
+/// Renders `trades` as Binance-style trade-history CSV, header row
+/// included. Rows are emitted in the order `trades` is given in — callers
+/// reading from `TradeStore` already get oldest-first ordering for free.
+pub fn trade_history_csv(trades: &[Trade]) -> String {
+    let mut csv = String::from(HEADER);
+    csv.push('\n');
+    for trade in trades {
+        let side = if trade.is_buyer_maker { "SELL" } else { "BUY" };
+        let date = chrono::DateTime::from_timestamp_millis(trade.trade_time)
+            .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        let amount = trade.price * trade.quantity;
+        csv.push_str(&format!(
+            "{date},{pair},{side},{price},{executed},{amount},0\n",
+            pair = trade.symbol,
+            price = trade.price,
+            executed = trade.quantity,
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::Symbol;
+
+    fn trade(is_buyer_maker: bool) -> Trade {
+        Trade {
+            symbol: Symbol::new("BTCUSDT"),
+            agg_trade_id: 1,
+            price: dec!(100),
+            quantity: dec!(2),
+            trade_time: 1_700_000_000_000,
+            is_buyer_maker,
+        }
+    }
+
+    #[test]
+    fn the_header_row_matches_binances_trade_history_column_order() {
+        let csv = trade_history_csv(&[]);
+        assert_eq!(csv, "Date(UTC),Pair,Side,Price,Executed,Amount,Fee\n");
+    }
+
+    #[test]
+    fn a_buyer_maker_trade_is_exported_as_a_sell() {
+        let csv = trade_history_csv(&[trade(true)]);
+        assert!(csv.lines().nth(1).unwrap().contains(",SELL,"));
+    }
+
+    #[test]
+    fn a_non_buyer_maker_trade_is_exported_as_a_buy() {
+        let csv = trade_history_csv(&[trade(false)]);
+        assert!(csv.lines().nth(1).unwrap().contains(",BUY,"));
+    }
+
+    #[test]
+    fn amount_is_price_times_quantity_and_fee_is_always_zero() {
+        let csv = trade_history_csv(&[trade(false)]);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.ends_with(",100,2,200,0"));
+    }
+}
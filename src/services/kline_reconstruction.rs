@@ -0,0 +1,112 @@
+//! Rebuilds kline tables from trade prints, for datasets that were only
+//! ever ingested as aggTrades — so a kline-mode session or a chart UI can
+//! still be served candles without a separate kline ingest pass.
+//!
+//! `store`'s module doc explains there's no DuckDB backing this tree yet,
+//! so this walks `TradeStore`'s trades in memory and folds them into
+//! interval-aligned buckets, the same way [`services::resample`](crate::services::resample)
+//! folds finer klines into coarser ones.
+
+use crate::domain::{interval_ms, Kline, Symbol};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Rebuilds `symbol`'s `interval` klines from trades in `[start, end)` and
+/// stores them via `MarketStore::insert_klines`. Returns the reconstructed
+/// klines. A bucket with no trades in it is simply absent, same as
+/// [`resample`](crate::services::resample::resample) leaves gaps in its
+/// source uncovered.
+pub fn reconstruct(state: &AppState, symbol: &Symbol, interval: &str, start: i64, end: i64) -> Result<Vec<Kline>, ApiError> {
+    let interval_ms = interval_ms(interval).ok_or_else(|| ApiError::BadRequest(format!("unrecognized interval: {interval}")))?;
+
+    let trades = state.trade_store().trades(symbol, start, end);
+    let mut klines: Vec<Kline> = Vec::new();
+    for trade in trades {
+        let bucket_open = trade.trade_time - trade.trade_time.rem_euclid(interval_ms);
+        match klines.last_mut() {
+            Some(kline) if kline.open_time == bucket_open => {
+                kline.high = kline.high.max(trade.price);
+                kline.low = kline.low.min(trade.price);
+                kline.close = trade.price;
+                kline.volume += trade.quantity;
+                kline.quote_volume += trade.price * trade.quantity;
+            }
+            _ => klines.push(Kline {
+                symbol: symbol.clone(),
+                interval: interval.to_string(),
+                open_time: bucket_open,
+                close_time: bucket_open + interval_ms - 1,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: trade.quantity,
+                quote_volume: trade.price * trade.quantity,
+                is_closed: bucket_open + interval_ms <= end,
+            }),
+        }
+    }
+
+    state.market_store().insert_klines(klines.clone());
+    Ok(klines)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::{Decimal, Trade};
+
+    fn trade(symbol: &Symbol, trade_time: i64, price: Decimal, quantity: Decimal) -> Trade {
+        Trade {
+            symbol: symbol.clone(),
+            agg_trade_id: trade_time,
+            price,
+            quantity,
+            trade_time,
+            is_buyer_maker: false,
+        }
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_interval() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        let err = reconstruct(&state, &symbol, "bogus", 0, 60_000).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(_)));
+    }
+
+    #[test]
+    fn folds_trades_into_ohlcv_and_quote_volume_buckets() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(trade(&symbol, 0, dec!(100.0), dec!(1.0)));
+        state.trade_store().insert_trade(trade(&symbol, 30_000, dec!(105.0), dec!(2.0)));
+        state.trade_store().insert_trade(trade(&symbol, 59_999, dec!(98.0), dec!(1.0)));
+
+        let klines = reconstruct(&state, &symbol, "1m", 0, 60_000).unwrap();
+        assert_eq!(klines.len(), 1);
+        let kline = &klines[0];
+        assert_eq!(kline.open_time, 0);
+        assert_eq!(kline.open, dec!(100.0));
+        assert_eq!(kline.high, dec!(105.0));
+        assert_eq!(kline.low, dec!(98.0));
+        assert_eq!(kline.close, dec!(98.0));
+        assert_eq!(kline.volume, dec!(4.0));
+        assert_eq!(kline.quote_volume, dec!(100.0) + dec!(210.0) + dec!(98.0));
+    }
+
+    #[test]
+    fn reconstructed_klines_are_stored_back_into_the_market_store() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(trade(&symbol, 0, dec!(100.0), dec!(1.0)));
+
+        reconstruct(&state, &symbol, "1m", 0, 60_000).unwrap();
+
+        let stored = state.market_store().klines(&symbol, "1m", 0, 60_000);
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].close, dec!(100.0));
+    }
+}
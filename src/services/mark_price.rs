@@ -0,0 +1,106 @@
+//! Derives a session's configured mark price for a symbol, instead of
+//! always marking at the raw last trade price. `ReplayService` publishes
+//! the result as a `mark_price` event alongside klines/trades (see
+//! `domain::MarketEventPayload::MarkPrice`), and
+//! `AppState::generate_summary` uses it to mark unrealized PnL on open
+//! shorts.
+
+use crate::domain::{Decimal, MarkPriceConfig, Symbol};
+use crate::state::AppState;
+
+/// Derives a mark price for `symbol` as of `as_of`, per `config`.
+/// `None` (unset on the session) behaves like
+/// [`MarkPriceConfig::LastClose`] — the same price an unmarked session
+/// would already be showing. Returns `None` if there isn't enough data
+/// yet to mark against (no klines, or no trades inside the VWAP window).
+pub fn compute(state: &AppState, symbol: &Symbol, interval: &str, as_of: i64, config: Option<&MarkPriceConfig>) -> Option<Decimal> {
+    match config {
+        Some(MarkPriceConfig::TradeVwap { window_ms }) => trade_vwap(state, symbol, as_of - window_ms, as_of),
+        Some(MarkPriceConfig::LastClose) | None => last_close(state, symbol, interval, as_of),
+    }
+}
+
+fn last_close(state: &AppState, symbol: &Symbol, interval: &str, as_of: i64) -> Option<Decimal> {
+    state.market_store().klines(symbol, interval, 0, as_of + 1).last().map(|k| k.close)
+}
+
+fn trade_vwap(state: &AppState, symbol: &Symbol, start: i64, end: i64) -> Option<Decimal> {
+    let trades = state.trade_store().trades(symbol, start, end + 1);
+    let (notional, quantity) = trades
+        .iter()
+        .fold((Decimal::ZERO, Decimal::ZERO), |(notional, quantity), trade| {
+            (notional + trade.price * trade.quantity, quantity + trade.quantity)
+        });
+    if quantity.is_zero() {
+        return None;
+    }
+    Some(notional / quantity)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::{Kline, Trade};
+
+    fn kline(symbol: &Symbol, open_time: i64, close: Decimal) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: dec!(1.0),
+            quote_volume: close,
+            is_closed: true,
+        }
+    }
+
+    fn trade(symbol: &Symbol, trade_time: i64, price: Decimal, quantity: Decimal) -> Trade {
+        Trade {
+            symbol: symbol.clone(),
+            agg_trade_id: trade_time,
+            price,
+            quantity,
+            trade_time,
+            is_buyer_maker: false,
+        }
+    }
+
+    #[test]
+    fn unset_config_marks_at_the_most_recent_kline_close() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.market_store().insert_kline(kline(&symbol, 0, dec!(100.0)));
+        state.market_store().insert_kline(kline(&symbol, 60_000, dec!(101.0)));
+
+        let mark = compute(&state, &symbol, "1m", 60_000, None);
+        assert_eq!(mark, Some(dec!(101.0)));
+    }
+
+    #[test]
+    fn trade_vwap_weights_by_quantity_over_the_window() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(trade(&symbol, 0, dec!(100.0), dec!(1.0)));
+        state.trade_store().insert_trade(trade(&symbol, 30_000, dec!(110.0), dec!(3.0)));
+
+        let config = MarkPriceConfig::TradeVwap { window_ms: 60_000 };
+        let mark = compute(&state, &symbol, "1m", 60_000, Some(&config));
+        assert_eq!(mark, Some(dec!(107.5)));
+    }
+
+    #[test]
+    fn trade_vwap_is_none_without_trades_in_the_window() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+
+        let config = MarkPriceConfig::TradeVwap { window_ms: 60_000 };
+        let mark = compute(&state, &symbol, "1m", 60_000, Some(&config));
+        assert_eq!(mark, None);
+    }
+}
@@ -0,0 +1,19 @@
+pub mod backup;
+pub mod bundle;
+pub mod dataset;
+pub mod depth_sequencing;
+pub mod execution_export;
+mod ingest;
+pub mod kline_reconstruction;
+pub mod mark_price;
+pub mod rate_limit;
+mod reaper;
+mod replay_service;
+pub mod resample;
+pub mod strategy_sandbox;
+pub mod symbol_history;
+pub mod tape;
+
+pub use ingest::{run_ingest, run_ingest_with_progress, validate_klines};
+pub use reaper::SessionReaper;
+pub use replay_service::ReplayService;
@@ -0,0 +1,150 @@
+//! Adaptive throttling modeled on Binance's weight-based rate limiting:
+//! `X-MBX-USED-WEIGHT-*` response headers report how much of the rolling
+//! weight budget has been consumed, and a 429 ("too many requests", back
+//! off) or 418 ("IP auto-banned", back off hard) both carry a
+//! `Retry-After` header naming how long to wait before trying again. This
+//! is a pure state machine, not tied to `reqwest` or any particular HTTP
+//! client, so it's unit-testable without a server and ready to reuse once
+//! a real fetch exists to feed it: `services::dataset::spawn_backfill`'s
+//! `fetch_chunk` is a synthetic in-process generator (see that module's
+//! doc), not an upstream HTTP call, so nothing in this tree ever actually
+//! observes a `429`/`418` or a real `X-MBX-USED-WEIGHT-*` value today —
+//! [`RateLimiter::delay`] is wired into the backfill loop and consulted
+//! before every chunk, but with nothing ever recording real pressure, it
+//! always returns [`Duration::ZERO`](std::time::Duration::ZERO).
+
+use std::time::{Duration, Instant};
+
+use crate::domain::ThrottleReason;
+
+/// A snapshot fit for [`crate::dataset_progress::DatasetProgressTracker::record_throttle`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThrottleState {
+    pub used_weight: u32,
+    pub reason: Option<ThrottleReason>,
+    pub retry_after: Option<Duration>,
+}
+
+/// Once used weight reaches this percentage of `weight_limit`, fetches
+/// pace down before an actual 429 is tripped.
+const SOFT_THRESHOLD_PERCENT: u32 = 80;
+const SOFT_PAUSE: Duration = Duration::from_millis(250);
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    weight_limit: u32,
+    used_weight: u32,
+    backoff_until: Option<Instant>,
+    backoff_reason: Option<ThrottleReason>,
+}
+
+impl RateLimiter {
+    pub fn new(weight_limit: u32) -> Self {
+        Self {
+            weight_limit,
+            used_weight: 0,
+            backoff_until: None,
+            backoff_reason: None,
+        }
+    }
+
+    /// Records the latest `X-MBX-USED-WEIGHT-*` value from a response.
+    pub fn record_used_weight(&mut self, used_weight: u32) {
+        self.used_weight = used_weight;
+    }
+
+    /// Records a 429 ("too many requests") with its `Retry-After`.
+    pub fn record_rate_limited(&mut self, retry_after: Duration) {
+        self.backoff_until = Some(Instant::now() + retry_after);
+        self.backoff_reason = Some(ThrottleReason::RateLimited);
+    }
+
+    /// Records a 418 ("IP auto-banned") with its `Retry-After`.
+    pub fn record_banned(&mut self, retry_after: Duration) {
+        self.backoff_until = Some(Instant::now() + retry_after);
+        self.backoff_reason = Some(ThrottleReason::Banned);
+    }
+
+    /// How long the caller should wait before its next fetch: whatever
+    /// remains of a 429/418 backoff, or [`SOFT_PAUSE`] once `used_weight`
+    /// has crossed [`SOFT_THRESHOLD_PERCENT`] of `weight_limit`, or
+    /// [`Duration::ZERO`] if neither applies.
+    pub fn delay(&self) -> Duration {
+        if let Some(until) = self.backoff_until {
+            let now = Instant::now();
+            if until > now {
+                return until - now;
+            }
+        }
+        if self.weight_limit > 0 && self.used_weight.saturating_mul(100) >= self.weight_limit.saturating_mul(SOFT_THRESHOLD_PERCENT) {
+            return SOFT_PAUSE;
+        }
+        Duration::ZERO
+    }
+
+    pub fn state(&self) -> ThrottleState {
+        let now = Instant::now();
+        let (reason, retry_after) = match self.backoff_until {
+            Some(until) if until > now => (self.backoff_reason, Some(until - now)),
+            _ => (None, None),
+        };
+        ThrottleState {
+            used_weight: self.used_weight,
+            reason,
+            retry_after,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_untouched_limiter_has_no_delay() {
+        let limiter = RateLimiter::new(1200);
+        assert_eq!(limiter.delay(), Duration::ZERO);
+        assert_eq!(limiter.state().reason, None);
+    }
+
+    #[test]
+    fn used_weight_below_the_soft_threshold_does_not_delay() {
+        let mut limiter = RateLimiter::new(1200);
+        limiter.record_used_weight(900);
+        assert_eq!(limiter.delay(), Duration::ZERO);
+    }
+
+    #[test]
+    fn used_weight_past_the_soft_threshold_pauses() {
+        let mut limiter = RateLimiter::new(1200);
+        limiter.record_used_weight(1000);
+        assert_eq!(limiter.delay(), SOFT_PAUSE);
+        assert_eq!(limiter.state().used_weight, 1000);
+    }
+
+    #[test]
+    fn a_429_backs_off_for_its_retry_after() {
+        let mut limiter = RateLimiter::new(1200);
+        limiter.record_rate_limited(Duration::from_secs(30));
+        let state = limiter.state();
+        assert_eq!(state.reason, Some(ThrottleReason::RateLimited));
+        assert!(state.retry_after.unwrap() <= Duration::from_secs(30));
+        assert!(limiter.delay() > Duration::ZERO);
+    }
+
+    #[test]
+    fn a_418_backs_off_as_a_ban() {
+        let mut limiter = RateLimiter::new(1200);
+        limiter.record_banned(Duration::from_secs(120));
+        assert_eq!(limiter.state().reason, Some(ThrottleReason::Banned));
+    }
+
+    #[test]
+    fn backoff_clears_once_retry_after_elapses() {
+        let mut limiter = RateLimiter::new(1200);
+        limiter.record_rate_limited(Duration::from_millis(1));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(limiter.delay(), Duration::ZERO);
+        assert_eq!(limiter.state().reason, None);
+    }
+}
@@ -0,0 +1,76 @@
+//! Background cleanup for sessions nobody is using. A `Created` session
+//! that's never started, or a `Paused` one nobody has resumed, still holds
+//! a broadcaster, ledgers and trackers in [`AppState`] — in a shared
+//! deployment those accumulate forever unless something reclaims them.
+//! [`SessionReaper::spawn`] polls for exactly that: sessions with neither
+//! an open websocket subscriber nor any API activity
+//! (`AppState::has_open_ws_connection`/`AppState::idle_duration`) for
+//! longer than `Config::idle_session_ttl_secs`.
+//!
+//! This is wall-clock idleness, not simulated time — unlike order/fill
+//! timestamps elsewhere in this tree, there's no session to ask "how long
+//! has it been" once the question is "has anyone touched this session's
+//! API or socket lately", so `AppState::touch_activity` and
+//! `Instant::elapsed` are the right tool here.
+
+use std::time::Duration;
+
+use crate::domain::SessionStatus;
+use crate::state::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct SessionReaper;
+
+impl SessionReaper {
+    /// Spawns the reaper loop in the background. A no-op loop (it still
+    /// polls, but never finds anything to do) when
+    /// `Config::idle_session_ttl_secs` is `None`, so callers can spawn this
+    /// unconditionally at startup rather than branching on whether the
+    /// feature is configured.
+    pub fn spawn(state: AppState) {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                reap_idle_sessions(&state);
+            }
+        });
+    }
+}
+
+fn reap_idle_sessions(state: &AppState) {
+    let config = state.config();
+    let Some(ttl_secs) = config.idle_session_ttl_secs else {
+        return;
+    };
+    let ttl = Duration::from_secs(ttl_secs);
+
+    let candidates = state
+        .sessions_with_status(SessionStatus::Created)
+        .into_iter()
+        .chain(state.sessions_with_status(SessionStatus::Paused));
+
+    for session in candidates {
+        if state.has_open_ws_connection(session.id) {
+            continue;
+        }
+        let Some(idle) = state.idle_duration(session.id) else {
+            continue;
+        };
+        if idle < ttl {
+            continue;
+        }
+
+        if session.status == SessionStatus::Paused {
+            // Setting it away from `Paused` lets the parked `ReplayService`
+            // loop observe the change on its next tick and exit on its own.
+            let _ = state.set_status(session.id, SessionStatus::Stopped);
+        }
+
+        if config.idle_session_delete {
+            state.remove_session(session.id);
+        } else {
+            state.clear_session_caches(session.id);
+        }
+    }
+}
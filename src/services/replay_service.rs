@@ -0,0 +1,456 @@
+//! Drives simulated time forward for a session: on `start` it advances
+//! through the dataset and publishes [`MarketEvent`]s onto the session's
+//! [`SessionEventBus`](crate::event_bus::SessionEventBus) until the session
+//! is stopped or ended. It never touches
+//! [`SessionBroadcaster`](crate::broadcaster::SessionBroadcaster) directly —
+//! `state::spawn_broadcaster_forwarder` is the bus subscriber that bridges
+//! events back to it, so adding another consumer (a journal, a metrics
+//! sink) means subscribing to the bus, not touching this loop. A paused
+//! session (see
+//! `http::admin::pause_all`) idles the loop in place rather than ending the
+//! task, so resuming it picks back up without a fresh `spawn`. Sessions
+//! sharing a [`Session::clock_group`](crate::domain::Session::clock_group)
+//! resync to each other's `open_time` every tick via
+//! `clock_group::ClockGroupRegistry`, so they advance in lockstep instead
+//! of on their own independent clocks.
+//!
+//! The dataset-backed candle/trade source is introduced by later ingestion
+//! work; for now each tick synthesizes the next candle so the streaming
+//! transports (websocket, SSE) have something real to carry end to end.
+//! Simulated time advances by [`candle_step_ms`] per tick, so a session
+//! configured with a sub-minute `interval` (e.g. `"1s"`) actually ticks at
+//! that cadence instead of silently advancing a full simulated minute.
+//!
+//! A symbol registered as delisted in
+//! `symbol_registry::SymbolLifecycleRegistry` stops being synthesized once
+//! `open_time` reaches its delisting point — the loop simply skips it for
+//! the rest of the session rather than erroring, the same way a session
+//! can already run past the end of an ingested dataset.
+//!
+//! [`Session::market_mode`](crate::domain::Session::market_mode) (falling
+//! back to `Config::default_market_mode` when unset) controls which of
+//! this loop's two independent per-tick pipelines actually publish: the
+//! kline pipeline always writes to `store::MarketStore` and feeds
+//! `ticker::TickerTracker`/`services::analytics` regardless of mode (they
+//! have no trade-based equivalent to read from instead), but its
+//! [`MarketEventPayload::Kline`] broadcast is skipped unless the mode
+//! includes klines; the synthetic-aggTrade pipeline only runs at all when
+//! the mode includes trades, writing to `store::TradeStore` and
+//! broadcasting [`MarketEventPayload::Trade`]. `MarketMode::Hybrid` runs
+//! both off the same per-tick price.
+//!
+//! When [`Session::equity_snapshot_interval_ms`](crate::domain::Session::equity_snapshot_interval_ms)
+//! is set, this loop also calls `AppState::record_equity_snapshot` every
+//! time `open_time` crosses a multiple of that interval, appending to the
+//! session's `equity_curve::EquityCurve` in addition to the single
+//! end-of-session [`SessionSummary`](crate::domain::SessionSummary).
+//!
+//! [`Session::seek_to`](crate::domain::Session::seek_to) (set via
+//! `http::sessions::seek_session`) jumps `open_time` straight to the
+//! requested point on the next tick rather than replaying everything in
+//! between. Only forward seeks are accepted — `AppState::seek` rejects a
+//! `seek_to` earlier than `current_time` before it ever reaches this loop
+//! — and [`crate::matching`]'s engine state is left untouched across the
+//! jump either way, since this tree has no order book for a jump to
+//! invalidate in the first place.
+//!
+//! Each symbol's per-tick synthesis ([`run_symbol_tick`]) runs in its own
+//! [`tokio::spawn`]ed task instead of back-to-back on this loop's own
+//! task, so a session tracking many symbols spreads that work across
+//! worker threads rather than serializing it — `open_time` itself is this
+//! loop's "shared clock" (or `clock_group::ClockGroupRegistry` for
+//! sessions sharing a `clock_group`; there's no separate `SimulatedClock`
+//! type to name). [`futures::future::join_all`] is the barrier: the main
+//! loop doesn't advance `open_time` until every symbol's task for this
+//! tick has finished, and then publishes each task's events in the fixed
+//! [`ordered_symbols`] order (not completion order), so the deterministic
+//! `(event_time, symbol, seq)` total order this module already documents
+//! holds regardless of which symbol's task happens to finish first. Since
+//! synthesis is still the trivial placeholder computation described above
+//! rather than a real dataset read, this buys structure more than
+//! measured throughput today — it's worth having once the dataset-backed
+//! source lands and per-symbol work stops being free.
+
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::domain::{
+    interval_ms, BreakpointCondition, BreakpointEvent, CrossDirection, DriftGenerator, Kline, MarkPrice,
+    MarkPriceConfig, MarketEvent, MarketEventPayload, MarketMode, SessionStatus, Symbol, Trade,
+};
+use crate::event_bus::BusEvent;
+use crate::services::mark_price;
+use crate::state::AppState;
+
+const TICK_INTERVAL: Duration = Duration::from_millis(200);
+/// Candle step used when a session's `interval` doesn't parse via
+/// [`interval_ms`] — preserves the fixed-step behavior this loop had before
+/// interval-aware stepping landed, rather than stalling a running session
+/// over a bad interval string.
+const FALLBACK_STEP_MS: i64 = 60_000;
+
+/// Simulated-time advance per tick, derived from `session.interval` (e.g.
+/// `"1s"` advances 1,000ms per tick instead of the fixed 60,000ms this loop
+/// used before every session was assumed to be 1m-interval).
+fn candle_step_ms(interval: &str) -> i64 {
+    interval_ms(interval).unwrap_or(FALLBACK_STEP_MS)
+}
+
+pub struct ReplayService;
+
+impl ReplayService {
+    /// Spawns the replay loop for `session_id` in the background. Returns
+    /// immediately; the loop exits on its own once the session is stopped,
+    /// ended, or removed from state.
+    pub fn spawn(state: AppState, session_id: uuid::Uuid) {
+        tokio::spawn(async move {
+            let initial = state.get_session(session_id).ok();
+            let mut open_time = initial.as_ref().map(|s| s.start_time).unwrap_or(0);
+            let mut price = dec!(100.00);
+            let mut last_price = price;
+            let mut drift = initial.as_ref().and_then(|s| s.clock_drift).map(DriftGenerator::new);
+            let mut next_snapshot_at = initial.as_ref().and_then(|s| s.equity_snapshot_interval_ms).map(|_| open_time);
+            let mut next_agg_trade_id: i64 = 1;
+
+            while let Ok(session) = state.get_session(session_id) {
+                match session.status {
+                    SessionStatus::Running => {}
+                    SessionStatus::Paused => {
+                        tokio::time::sleep(TICK_INTERVAL).await;
+                        continue;
+                    }
+                    _ => break,
+                }
+
+                if state.is_maintenance() {
+                    tokio::time::sleep(TICK_INTERVAL).await;
+                    continue;
+                }
+
+                if let Some(pause_at) = session.pause_at {
+                    if open_time >= pause_at {
+                        let _ = state.set_pause_at(session_id, None);
+                        let _ = state.set_status(session_id, SessionStatus::Paused);
+                        tokio::time::sleep(TICK_INTERVAL).await;
+                        continue;
+                    }
+                }
+
+                if let Some(seek_to) = session.seek_to {
+                    if seek_to > open_time {
+                        open_time = seek_to;
+                    }
+                    state.clear_seek(session_id);
+                }
+
+                let Ok(bus) = state.event_bus(session_id) else {
+                    break;
+                };
+
+                if let Some(group) = session.clock_group {
+                    open_time = state.clock_groups().advance(group, open_time);
+                }
+
+                let event_time = drift
+                    .as_mut()
+                    .map(|d| d.drift(open_time))
+                    .unwrap_or(open_time);
+
+                let step_ms = candle_step_ms(&session.interval);
+
+                if let Some(index) = find_triggered_breakpoint(&session.breakpoints, last_price, price) {
+                    let condition = session.breakpoints[index].clone();
+                    let _ = state.remove_breakpoint(session_id, index);
+                    let _ = state.set_status(session_id, SessionStatus::Paused);
+                    bus.publish(BusEvent::Market(MarketEvent {
+                        seq: 0, // assigned by the broadcaster
+                        event_time,
+                        payload: MarketEventPayload::Breakpoint(BreakpointEvent { condition }),
+                    }));
+                    tokio::time::sleep(TICK_INTERVAL).await;
+                    continue;
+                }
+
+                let market_mode = session.market_mode.unwrap_or(state.config().default_market_mode);
+
+                let active_symbols: Vec<Symbol> = ordered_symbols(&session.symbols, &session.watchlist)
+                    .into_iter()
+                    .filter(|symbol| {
+                        state
+                            .symbol_lifecycle()
+                            .delisted_at(symbol)
+                            .map(|delisted_at| open_time < delisted_at)
+                            .unwrap_or(true)
+                    })
+                    .collect();
+
+                // Agg-trade ids are assigned up front, before any symbol's
+                // task runs, so concurrent tasks never race over a shared
+                // counter and ids still come out in `active_symbols` order.
+                let first_agg_trade_id = next_agg_trade_id;
+                next_agg_trade_id += active_symbols.len() as i64;
+
+                let tasks: Vec<_> = active_symbols
+                    .iter()
+                    .enumerate()
+                    .map(|(index, symbol)| {
+                        tokio::spawn(run_symbol_tick(
+                            state.clone(),
+                            session_id,
+                            symbol.clone(),
+                            session.interval.clone(),
+                            open_time,
+                            event_time,
+                            price,
+                            step_ms,
+                            market_mode,
+                            session.mark_price,
+                            first_agg_trade_id + index as i64,
+                        ))
+                    })
+                    .collect();
+
+                for events in futures::future::join_all(tasks).await.into_iter().flatten() {
+                    for event in events {
+                        bus.publish(BusEvent::Market(event));
+                    }
+                }
+
+                open_time += step_ms;
+                last_price = price;
+                price += dec!(0.01);
+                let _ = state.advance_clock(session_id, open_time);
+
+                if let Some(interval_ms) = session.equity_snapshot_interval_ms {
+                    if let Some(due_at) = next_snapshot_at {
+                        if open_time >= due_at {
+                            let _ = state.record_equity_snapshot(session_id);
+                            next_snapshot_at = Some(due_at + interval_ms.max(1));
+                        }
+                    } else {
+                        next_snapshot_at = Some(open_time);
+                    }
+                } else {
+                    next_snapshot_at = None;
+                }
+
+                tokio::time::sleep(TICK_INTERVAL.div_f64(session.speed.max(0.01))).await;
+            }
+        });
+    }
+}
+
+/// Merges a session's tradable `symbols` with its read-only `watchlist`
+/// and sorts the result lexicographically, so every tick publishes its
+/// events in the same order regardless of the order either list was
+/// supplied in when the session was created. Combined with the
+/// broadcaster's strictly increasing `seq`, this gives every multi-symbol
+/// session a deterministic total order over `(event_time, symbol, seq)` —
+/// two runs of the same session always interleave symbols at a tied
+/// `event_time` identically. Watchlist symbols get the same synthetic
+/// kline treatment as tradable ones; there's no separate "tradable" data
+/// path to divert them away from since this tree has no matching engine.
+fn ordered_symbols(symbols: &[Symbol], watchlist: &[Symbol]) -> Vec<Symbol> {
+    let mut ordered: Vec<Symbol> = symbols.iter().chain(watchlist).cloned().collect();
+    ordered.sort();
+    ordered.dedup();
+    ordered
+}
+
+/// Finds the first of `breakpoints` whose `PriceCrosses` condition fired
+/// between `prev_price` and `price` — the shared synthetic price curve
+/// advanced once this tick, not a per-symbol price, so every
+/// `PriceCrosses` condition is checked against it regardless of which
+/// `symbol` it names. `OrderFilled`/`DrawdownExceeds` never match; see
+/// [`BreakpointCondition`] for why.
+fn find_triggered_breakpoint(breakpoints: &[BreakpointCondition], prev_price: Decimal, price: Decimal) -> Option<usize> {
+    breakpoints.iter().position(|condition| match condition {
+        BreakpointCondition::PriceCrosses { price: target, direction, .. } => {
+            crosses(prev_price, price, *target, *direction)
+        }
+        BreakpointCondition::OrderFilled | BreakpointCondition::DrawdownExceeds { .. } => false,
+    })
+}
+
+fn crosses(prev: Decimal, current: Decimal, target: Decimal, direction: CrossDirection) -> bool {
+    match direction {
+        CrossDirection::Above => prev < target && current >= target,
+        CrossDirection::Below => prev > target && current <= target,
+    }
+}
+
+/// One symbol's slice of a single tick's synthetic work, run inside its
+/// own spawned task — see this module's doc comment for the barrier
+/// `run`'s caller applies around a batch of these. Returns the events this
+/// symbol produced (with placeholder `seq: 0`, same as everywhere else in
+/// this loop) instead of publishing them directly, so the caller keeps
+/// control of publish order.
+#[allow(clippy::too_many_arguments)]
+async fn run_symbol_tick(
+    state: AppState,
+    session_id: uuid::Uuid,
+    symbol: Symbol,
+    interval: String,
+    open_time: i64,
+    event_time: i64,
+    price: Decimal,
+    step_ms: i64,
+    market_mode: MarketMode,
+    mark_price_config: Option<MarkPriceConfig>,
+    agg_trade_id: i64,
+) -> Vec<MarketEvent> {
+    let mut events = Vec::new();
+
+    let kline = synthetic_kline(&symbol, &interval, open_time, price, step_ms);
+    let close = kline.close;
+    state.market_store().insert_kline(kline.clone());
+    state.ticker_tracker().observe_kline(&kline);
+    if let Ok(tracker) = state.analytics_tracker(session_id) {
+        tracker.observe_kline(&kline);
+    }
+    if market_mode.includes_klines() {
+        events.push(MarketEvent { seq: 0, event_time, payload: MarketEventPayload::Kline(kline) });
+    }
+
+    if market_mode.includes_trades() {
+        let trade = synthetic_trade(&symbol, event_time, close, agg_trade_id);
+        state.trade_store().insert_trade(trade.clone());
+        events.push(MarketEvent { seq: 0, event_time, payload: MarketEventPayload::Trade(trade) });
+    }
+
+    if let Some(mark) = mark_price::compute(&state, &symbol, &interval, open_time, mark_price_config.as_ref()) {
+        events.push(MarketEvent {
+            seq: 0,
+            event_time,
+            payload: MarketEventPayload::MarkPrice(MarkPrice { symbol, price: mark }),
+        });
+    }
+
+    events
+}
+
+/// A single synthetic `aggTrade` tick at `price` (the symbol's kline
+/// close this tick), alternating `is_buyer_maker` by parity of
+/// `agg_trade_id` for the same reason `synthetic_kline` alternates nothing
+/// at all — there's no real order flow in this tree to derive it from, so
+/// alternating is just enough variation that a subscriber doesn't see a
+/// constant field.
+fn synthetic_trade(symbol: &Symbol, trade_time: i64, price: Decimal, agg_trade_id: i64) -> Trade {
+    Trade {
+        symbol: symbol.clone(),
+        agg_trade_id,
+        price,
+        quantity: dec!(1.0),
+        trade_time,
+        is_buyer_maker: agg_trade_id % 2 == 0,
+    }
+}
+
+fn synthetic_kline(symbol: &Symbol, interval: &str, open_time: i64, price: Decimal, step_ms: i64) -> Kline {
+    Kline {
+        symbol: symbol.clone(),
+        interval: interval.to_string(),
+        open_time,
+        close_time: open_time + (step_ms - 1),
+        open: price,
+        high: price + dec!(0.05),
+        low: price - dec!(0.05),
+        close: price + dec!(0.01),
+        volume: dec!(1.0),
+        quote_volume: price * dec!(1.0),
+        is_closed: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbols_are_ordered_lexicographically_regardless_of_input_order() {
+        let input = vec![Symbol::new("ETHUSDT"), Symbol::new("BTCUSDT"), Symbol::new("BNBUSDT")];
+        let ordered = ordered_symbols(&input, &[]);
+        assert_eq!(
+            ordered,
+            vec![Symbol::new("BNBUSDT"), Symbol::new("BTCUSDT"), Symbol::new("ETHUSDT")]
+        );
+    }
+
+    #[test]
+    fn watchlist_symbols_are_merged_in_and_deduplicated() {
+        let symbols = vec![Symbol::new("BTCUSDT")];
+        let watchlist = vec![Symbol::new("ETHUSDT"), Symbol::new("BTCUSDT")];
+        let ordered = ordered_symbols(&symbols, &watchlist);
+        assert_eq!(ordered, vec![Symbol::new("BTCUSDT"), Symbol::new("ETHUSDT")]);
+    }
+
+    #[test]
+    fn candle_step_is_derived_from_the_session_interval() {
+        assert_eq!(candle_step_ms("1s"), 1_000);
+        assert_eq!(candle_step_ms("1m"), 60_000);
+        assert_eq!(candle_step_ms("1h"), 3_600_000);
+    }
+
+    #[test]
+    fn an_unrecognized_interval_falls_back_to_the_one_minute_step() {
+        assert_eq!(candle_step_ms("bogus"), FALLBACK_STEP_MS);
+    }
+
+    #[test]
+    fn price_crosses_above_fires_when_the_target_is_straddled_upward() {
+        let breakpoints = vec![BreakpointCondition::PriceCrosses {
+            symbol: Symbol::new("BTCUSDT"),
+            price: dec!(100.00),
+            direction: CrossDirection::Above,
+        }];
+        assert_eq!(find_triggered_breakpoint(&breakpoints, dec!(99.99), dec!(100.01)), Some(0));
+    }
+
+    #[test]
+    fn price_crosses_below_fires_when_the_target_is_straddled_downward() {
+        let breakpoints = vec![BreakpointCondition::PriceCrosses {
+            symbol: Symbol::new("BTCUSDT"),
+            price: dec!(100.00),
+            direction: CrossDirection::Below,
+        }];
+        assert_eq!(find_triggered_breakpoint(&breakpoints, dec!(100.01), dec!(99.99)), Some(0));
+    }
+
+    #[test]
+    fn a_breakpoint_does_not_fire_when_the_price_never_reaches_it() {
+        let breakpoints = vec![BreakpointCondition::PriceCrosses {
+            symbol: Symbol::new("BTCUSDT"),
+            price: dec!(200.00),
+            direction: CrossDirection::Above,
+        }];
+        assert_eq!(find_triggered_breakpoint(&breakpoints, dec!(99.99), dec!(100.01)), None);
+    }
+
+    #[test]
+    fn order_filled_and_drawdown_conditions_never_fire() {
+        let breakpoints = vec![
+            BreakpointCondition::OrderFilled,
+            BreakpointCondition::DrawdownExceeds { percent: dec!(10.0) },
+        ];
+        assert_eq!(find_triggered_breakpoint(&breakpoints, dec!(99.99), dec!(100.01)), None);
+    }
+
+    #[test]
+    fn synthetic_trades_alternate_buyer_maker_by_id_parity() {
+        let symbol = Symbol::new("BTCUSDT");
+        assert!(!synthetic_trade(&symbol, 0, dec!(100.0), 1).is_buyer_maker);
+        assert!(synthetic_trade(&symbol, 0, dec!(100.0), 2).is_buyer_maker);
+    }
+
+    #[test]
+    fn a_synthetic_kline_closes_exactly_one_step_after_it_opens() {
+        let symbol = Symbol::new("BTCUSDT");
+        let one_second = synthetic_kline(&symbol, "1s", 0, dec!(100.00), candle_step_ms("1s"));
+        assert_eq!(one_second.close_time, 999);
+
+        let one_minute = synthetic_kline(&symbol, "1m", 0, dec!(100.00), candle_step_ms("1m"));
+        assert_eq!(one_minute.close_time, 59_999);
+    }
+}
@@ -0,0 +1,130 @@
+//! Aggregates stored klines from a finer interval into a coarser one
+//! (e.g. 1m candles into 15m ones) entirely server-side, so a dataset
+//! doesn't need to be ingested separately at every interval it might be
+//! viewed at.
+//!
+//! `store`'s module doc explains there's no DuckDB backing this tree yet,
+//! so there's no SQL aggregation to push this down to either — this walks
+//! `MarketStore`'s klines in memory and folds them into buckets instead.
+
+use crate::domain::{aligned_bucket_open, interval_ms, Kline, Symbol};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Resamples `symbol`'s klines in `[start, end)` from `from` into `to`.
+/// `to` must be an exact, strictly larger multiple of `from` — anything
+/// else would leave the first or last bucket covering a partial, and
+/// therefore misleading, span of time. `align_offset_ms` shifts the
+/// bucket boundaries (e.g. a day boundary pinned to UTC+8 rather than UTC
+/// midnight) — `0` reproduces the original UTC-aligned behavior. See
+/// [`aligned_bucket_open`](crate::domain::aligned_bucket_open).
+pub fn resample(
+    state: &AppState,
+    symbol: &Symbol,
+    from: &str,
+    to: &str,
+    start: i64,
+    end: i64,
+    align_offset_ms: i64,
+) -> Result<Vec<Kline>, ApiError> {
+    let from_ms = interval_ms(from).ok_or_else(|| ApiError::BadRequest(format!("unrecognized interval: {from}")))?;
+    let to_ms = interval_ms(to).ok_or_else(|| ApiError::BadRequest(format!("unrecognized interval: {to}")))?;
+    if to_ms <= from_ms || to_ms % from_ms != 0 {
+        return Err(ApiError::BadRequest(format!("{to} is not an exact multiple of {from}")));
+    }
+
+    let source = state.market_store().klines(symbol, from, start, end);
+    let mut buckets: Vec<Kline> = Vec::new();
+    for kline in source {
+        let bucket_open = aligned_bucket_open(kline.open_time, to_ms, align_offset_ms);
+        match buckets.last_mut() {
+            Some(bucket) if bucket.open_time == bucket_open => {
+                bucket.high = bucket.high.max(kline.high);
+                bucket.low = bucket.low.min(kline.low);
+                bucket.close = kline.close;
+                bucket.volume += kline.volume;
+                bucket.quote_volume += kline.quote_volume;
+                bucket.is_closed = bucket.is_closed && kline.is_closed;
+            }
+            _ => buckets.push(Kline {
+                symbol: symbol.clone(),
+                interval: to.to_string(),
+                open_time: bucket_open,
+                close_time: bucket_open + to_ms - 1,
+                open: kline.open,
+                high: kline.high,
+                low: kline.low,
+                close: kline.close,
+                volume: kline.volume,
+                quote_volume: kline.quote_volume,
+                is_closed: kline.is_closed,
+            }),
+        }
+    }
+    Ok(buckets)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn kline(symbol: &Symbol, open_time: i64, close: rust_decimal::Decimal) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: close,
+            high: close + dec!(1.0),
+            low: close - dec!(1.0),
+            close,
+            volume: dec!(2.0),
+            quote_volume: close * dec!(2.0),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn rejects_a_target_interval_that_is_not_a_multiple_of_the_source() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        let err = resample(&state, &symbol, "1m", "90s", 0, 60_000, 0).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(_)));
+    }
+
+    #[test]
+    fn aggregates_source_candles_into_the_coarser_bucket() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        for minute in 0..15 {
+            state.market_store().insert_kline(kline(&symbol, minute * 60_000, dec!(100.0) + rust_decimal::Decimal::from(minute)));
+        }
+
+        let resampled = resample(&state, &symbol, "1m", "15m", 0, 15 * 60_000, 0).unwrap();
+        assert_eq!(resampled.len(), 1);
+        let bucket = &resampled[0];
+        assert_eq!(bucket.open_time, 0);
+        assert_eq!(bucket.close_time, 15 * 60_000 - 1);
+        assert_eq!(bucket.open, dec!(100.0));
+        assert_eq!(bucket.close, dec!(114.0));
+        assert_eq!(bucket.high, dec!(115.0));
+        assert_eq!(bucket.low, dec!(99.0));
+        assert_eq!(bucket.volume, dec!(30.0));
+    }
+
+    #[test]
+    fn a_nonzero_offset_shifts_the_bucket_boundary() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        for minute in 0..30 {
+            state.market_store().insert_kline(kline(&symbol, minute * 60_000, dec!(100.0)));
+        }
+
+        // A 15m bucket offset by 5m opens at :05 instead of :00.
+        let resampled = resample(&state, &symbol, "1m", "15m", 0, 30 * 60_000, 5 * 60_000).unwrap();
+        let opens: Vec<i64> = resampled.iter().map(|k| k.open_time).collect();
+        assert_eq!(opens, vec![-10 * 60_000, 5 * 60_000, 20 * 60_000]);
+    }
+}
@@ -0,0 +1,186 @@
+//! In-process strategy callbacks driven against a session's replay.
+//!
+//! This tree has no embedded scripting runtime — no `wasmtime`, no `rhai`,
+//! not even in `dev-dependencies` — and this environment can't add one
+//! (`cargo add` has no registry access here), so "users upload a small
+//! strategy script" isn't implementable as asked. There's also no order
+//! placement surface anywhere in this tree for a strategy to call into: see
+//! `orders`' module doc on why `place_order` doesn't exist yet. So a
+//! strategy here can't actually trade, only decide that it *would* have.
+//!
+//! What's real: a [`Strategy`] is a plain Rust callback (`dyn` object,
+//! registered per session the same way every other per-session tracker is),
+//! driven by [`StrategyRunner::on_kline`]/[`on_trade`](StrategyRunner::on_trade)
+//! from `services::replay_service`'s tick loop. A strategy returns
+//! [`StrategyIntent`]s instead of placing orders directly, and the runner
+//! just records them — once `place_order` exists, wiring it in is a matter
+//! of feeding `drain_intents()`'s output into it rather than rearchitecting
+//! this module. Until then this is close enough to a backtest harness to
+//! validate strategy logic against a replay, short of fills and PnL.
+//!
+//! Registering a Rust `Strategy` means recompiling the simulator per
+//! strategy, which isn't what "upload a script" implies — but it's the
+//! honest version of this feature buildable with what's already vendored
+//! here.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::domain::{Decimal, Kline, Symbol, Trade};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategySide {
+    Buy,
+    Sell,
+}
+
+/// What a [`Strategy`] would have done, had this tree had somewhere to send
+/// it — see this module's doc comment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyIntent {
+    pub symbol: Symbol,
+    pub side: StrategySide,
+    pub quantity: Decimal,
+}
+
+/// A strategy's market-data callbacks. Implemented in Rust and registered
+/// with a [`StrategyRunner`] before a session starts, rather than uploaded
+/// as a script — see this module's doc comment for why.
+pub trait Strategy: Send {
+    fn on_kline(&mut self, kline: &Kline) -> Vec<StrategyIntent> {
+        let _ = kline;
+        Vec::new()
+    }
+
+    fn on_trade(&mut self, trade: &Trade) -> Vec<StrategyIntent> {
+        let _ = trade;
+        Vec::new()
+    }
+}
+
+/// Drives registered [`Strategy`] callbacks for one session and records
+/// every [`StrategyIntent`] they return, in order, for later retrieval by
+/// [`drain_intents`](StrategyRunner::drain_intents). Strategies run
+/// synchronously on the calling task (`services::replay_service`'s tick
+/// loop) — there's no separate sandboxed execution context to isolate a
+/// misbehaving strategy into, so a panicking strategy takes the replay loop
+/// down with it the same way a panicking tick would today.
+#[derive(Default)]
+pub struct StrategyRunner {
+    strategies: Mutex<Vec<Box<dyn Strategy>>>,
+    intents: Mutex<Vec<StrategyIntent>>,
+}
+
+impl StrategyRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, strategy: Box<dyn Strategy>) {
+        self.strategies.lock().unwrap().push(strategy);
+    }
+
+    pub fn on_kline(&self, kline: &Kline) {
+        let mut strategies = self.strategies.lock().unwrap();
+        let mut intents = self.intents.lock().unwrap();
+        for strategy in strategies.iter_mut() {
+            intents.extend(strategy.on_kline(kline));
+        }
+    }
+
+    pub fn on_trade(&self, trade: &Trade) {
+        let mut strategies = self.strategies.lock().unwrap();
+        let mut intents = self.intents.lock().unwrap();
+        for strategy in strategies.iter_mut() {
+            intents.extend(strategy.on_trade(trade));
+        }
+    }
+
+    /// Returns every intent recorded so far and clears the buffer, so a
+    /// poller sees each intent exactly once rather than re-reading history
+    /// on every call.
+    pub fn drain_intents(&self) -> Vec<StrategyIntent> {
+        std::mem::take(&mut self.intents.lock().unwrap())
+    }
+}
+
+/// Counts intents by symbol, a cheap way to sanity-check a strategy is
+/// actually reacting to the feed during development without reading back
+/// every intent.
+pub fn intent_counts_by_symbol(intents: &[StrategyIntent]) -> HashMap<Symbol, usize> {
+    let mut counts = HashMap::new();
+    for intent in intents {
+        *counts.entry(intent.symbol.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    struct BuyEveryKline;
+
+    impl Strategy for BuyEveryKline {
+        fn on_kline(&mut self, kline: &Kline) -> Vec<StrategyIntent> {
+            vec![StrategyIntent { symbol: kline.symbol.clone(), side: StrategySide::Buy, quantity: dec!(1) }]
+        }
+    }
+
+    fn kline(symbol: &Symbol) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time: 0,
+            close_time: 59_999,
+            open: dec!(100),
+            high: dec!(101),
+            low: dec!(99),
+            close: dec!(100.5),
+            volume: dec!(1),
+            quote_volume: dec!(100),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn a_registered_strategy_sees_every_kline_tick() {
+        let runner = StrategyRunner::new();
+        runner.register(Box::new(BuyEveryKline));
+        let symbol = Symbol::new("BTCUSDT");
+
+        runner.on_kline(&kline(&symbol));
+        runner.on_kline(&kline(&symbol));
+
+        let intents = runner.drain_intents();
+        assert_eq!(intents.len(), 2);
+        assert_eq!(intents[0].side, StrategySide::Buy);
+    }
+
+    #[test]
+    fn draining_clears_the_buffer() {
+        let runner = StrategyRunner::new();
+        runner.register(Box::new(BuyEveryKline));
+        runner.on_kline(&kline(&Symbol::new("BTCUSDT")));
+
+        assert_eq!(runner.drain_intents().len(), 1);
+        assert_eq!(runner.drain_intents().len(), 0);
+    }
+
+    #[test]
+    fn intent_counts_are_grouped_by_symbol() {
+        let btc = Symbol::new("BTCUSDT");
+        let eth = Symbol::new("ETHUSDT");
+        let intents = vec![
+            StrategyIntent { symbol: btc.clone(), side: StrategySide::Buy, quantity: dec!(1) },
+            StrategyIntent { symbol: btc.clone(), side: StrategySide::Sell, quantity: dec!(1) },
+            StrategyIntent { symbol: eth.clone(), side: StrategySide::Buy, quantity: dec!(1) },
+        ];
+
+        let counts = intent_counts_by_symbol(&intents);
+        assert_eq!(counts[&btc], 2);
+        assert_eq!(counts[&eth], 1);
+    }
+}
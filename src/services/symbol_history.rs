@@ -0,0 +1,107 @@
+//! Stitches a symbol's renamed predecessors back in for queries that span
+//! a rename, using `symbol_registry::SymbolLifecycleRegistry::history`. A
+//! plain `MarketStore` query stops dead at a rename boundary since the old
+//! and new symbol are different store partitions — this walks the chain of
+//! predecessors instead, relabeling their klines under the current name so
+//! a caller querying `NEWUSDT` across the rename date sees one continuous
+//! series rather than a gap where `OLDUSDT`'s history used to be.
+
+use crate::domain::Kline;
+use crate::domain::Symbol;
+use crate::state::AppState;
+
+/// Fetches up to `limit` klines for `symbol` after `cursor` and before
+/// `end`, the same paging contract as `MarketStore::klines_page`, but
+/// sourcing from `symbol`'s renamed predecessors for any portion of the
+/// range that predates the rename that produced `symbol`.
+pub fn klines_page(state: &AppState, symbol: &Symbol, interval: &str, cursor: i64, end: i64, limit: usize) -> Vec<Kline> {
+    let history = state.symbol_lifecycle().history(symbol);
+    let mut page: Vec<Kline> = Vec::new();
+    let mut segment_cursor = cursor;
+
+    for (old_symbol, effective_at) in &history {
+        if page.len() >= limit || segment_cursor >= end || segment_cursor >= *effective_at {
+            continue;
+        }
+        let segment_end = (*effective_at).min(end);
+        let mut segment = state.market_store().klines_page(old_symbol, interval, segment_cursor, segment_end, limit - page.len());
+        for kline in &mut segment {
+            kline.symbol = symbol.clone();
+        }
+        segment_cursor = segment.last().map(|k| k.open_time).unwrap_or(segment_end);
+        page.extend(segment);
+    }
+
+    if page.len() < limit && segment_cursor < end {
+        page.extend(state.market_store().klines_page(symbol, interval, segment_cursor, end, limit - page.len()));
+    }
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::SymbolLifecycleEvent;
+
+    fn kline(symbol: &Symbol, open_time: i64) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: dec!(1),
+            high: dec!(1),
+            low: dec!(1),
+            close: dec!(1),
+            volume: dec!(1),
+            quote_volume: dec!(1),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn without_a_rename_behaves_like_a_plain_page_query() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.market_store().insert_kline(kline(&symbol, 0));
+        state.market_store().insert_kline(kline(&symbol, 60_000));
+
+        let page = klines_page(&state, &symbol, "1m", -1, 120_000, 10);
+        assert_eq!(page.len(), 2);
+    }
+
+    #[test]
+    fn a_query_spanning_a_rename_stitches_the_old_symbols_klines_in_under_the_new_name() {
+        let state = AppState::new();
+        let old_symbol = Symbol::new("OLDUSDT");
+        let new_symbol = Symbol::new("NEWUSDT");
+        state.market_store().insert_kline(kline(&old_symbol, 0));
+        state.market_store().insert_kline(kline(&new_symbol, 60_000));
+        state
+            .symbol_lifecycle()
+            .register(SymbolLifecycleEvent::Renamed { from: old_symbol.clone(), to: new_symbol.clone(), effective_at: 60_000 });
+
+        let page = klines_page(&state, &new_symbol, "1m", -1, 120_000, 10);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].open_time, 0);
+        assert_eq!(page[0].symbol, new_symbol);
+        assert_eq!(page[1].open_time, 60_000);
+    }
+
+    #[test]
+    fn a_query_entirely_before_the_rename_only_reads_the_old_symbol() {
+        let state = AppState::new();
+        let old_symbol = Symbol::new("OLDUSDT");
+        let new_symbol = Symbol::new("NEWUSDT");
+        state.market_store().insert_kline(kline(&old_symbol, 0));
+        state
+            .symbol_lifecycle()
+            .register(SymbolLifecycleEvent::Renamed { from: old_symbol.clone(), to: new_symbol.clone(), effective_at: 60_000 });
+
+        let page = klines_page(&state, &new_symbol, "1m", -1, 60_000, 10);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].symbol, new_symbol);
+    }
+}
@@ -0,0 +1,116 @@
+//! Aggregates replayed trades into fixed-size time buckets (count, volume,
+//! VWAP) for "time & sales" style UIs — see
+//! `http::sessions::get_session_tape`. Pure post-processing over
+//! `TradeStore`, the same shape as `resample`'s kline aggregation.
+
+use rust_decimal::Decimal;
+
+use crate::domain::{aligned_bucket_open, interval_ms, Symbol, TapeBucket};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Aggregates `symbol`'s trades in `[start, end)` into `bucket`-sized
+/// windows (e.g. `"1s"`). `align_offset_ms` shifts the bucket boundaries —
+/// see [`Session::candle_align_offset_ms`](crate::domain::Session::candle_align_offset_ms),
+/// the usual source of a non-zero value.
+pub fn aggregate(
+    state: &AppState,
+    symbol: &Symbol,
+    bucket: &str,
+    start: i64,
+    end: i64,
+    align_offset_ms: i64,
+) -> Result<Vec<TapeBucket>, ApiError> {
+    let bucket_ms = interval_ms(bucket).ok_or_else(|| ApiError::BadRequest(format!("unrecognized bucket: {bucket}")))?;
+
+    let mut buckets: Vec<TapeBucket> = Vec::new();
+    let mut bucket_quote_volume = Decimal::ZERO;
+
+    for trade in state.trade_store().trades(symbol, start, end) {
+        let bucket_open = aligned_bucket_open(trade.trade_time, bucket_ms, align_offset_ms);
+        match buckets.last_mut() {
+            Some(current) if current.bucket_open == bucket_open => {
+                current.count += 1;
+                current.volume += trade.quantity;
+                bucket_quote_volume += trade.price * trade.quantity;
+                current.vwap = bucket_quote_volume / current.volume;
+            }
+            _ => {
+                bucket_quote_volume = trade.price * trade.quantity;
+                buckets.push(TapeBucket {
+                    symbol: symbol.clone(),
+                    bucket_open,
+                    count: 1,
+                    volume: trade.quantity,
+                    vwap: trade.price,
+                });
+            }
+        }
+    }
+    Ok(buckets)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::domain::Trade;
+
+    fn trade(symbol: &Symbol, trade_time: i64, price: Decimal, quantity: Decimal) -> Trade {
+        Trade {
+            symbol: symbol.clone(),
+            agg_trade_id: trade_time,
+            price,
+            quantity,
+            trade_time,
+            is_buyer_maker: false,
+        }
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_bucket() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        let err = aggregate(&state, &symbol, "bogus", 0, 1_000, 0).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(_)));
+    }
+
+    #[test]
+    fn trades_within_a_bucket_are_folded_into_one_with_a_volume_weighted_price() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(trade(&symbol, 100, dec!(100.0), dec!(1.0)));
+        state.trade_store().insert_trade(trade(&symbol, 500, dec!(102.0), dec!(3.0)));
+
+        let buckets = aggregate(&state, &symbol, "1s", 0, 1_000, 0).unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].bucket_open, 0);
+        assert_eq!(buckets[0].count, 2);
+        assert_eq!(buckets[0].volume, dec!(4.0));
+        assert_eq!(buckets[0].vwap, dec!(101.5));
+    }
+
+    #[test]
+    fn trades_in_different_buckets_stay_separate() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(trade(&symbol, 100, dec!(100.0), dec!(1.0)));
+        state.trade_store().insert_trade(trade(&symbol, 1_500, dec!(105.0), dec!(1.0)));
+
+        let buckets = aggregate(&state, &symbol, "1s", 0, 2_000, 0).unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].bucket_open, 0);
+        assert_eq!(buckets[1].bucket_open, 1_000);
+    }
+
+    #[test]
+    fn a_nonzero_offset_shifts_the_bucket_boundary() {
+        let state = AppState::new();
+        let symbol = Symbol::new("BTCUSDT");
+        state.trade_store().insert_trade(trade(&symbol, 100, dec!(100.0), dec!(1.0)));
+
+        let buckets = aggregate(&state, &symbol, "1s", 0, 1_000, 400).unwrap();
+        assert_eq!(buckets[0].bucket_open, -600);
+    }
+}
@@ -0,0 +1,113 @@
+//! Per-session short position bookkeeping for sessions with `allow_short`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::domain::{Decimal, ShortPosition, Symbol};
+
+/// Tracks open short positions per symbol for one session.
+#[derive(Debug, Default)]
+pub struct ShortLedger {
+    positions: Mutex<HashMap<Symbol, ShortPosition>>,
+}
+
+impl ShortLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads `positions` as this session's starting shorts, e.g. copied
+    /// from a prior session's `SessionSummary::open_shorts` when chaining
+    /// backtests (see `http::sessions::create_session`'s `from_session`).
+    /// Overwrites anything already open for a given symbol rather than
+    /// folding it in, since this is meant to run once, right after
+    /// `AppState::insert_session` creates an empty ledger.
+    pub fn seed(&self, positions: Vec<ShortPosition>) {
+        let mut current = self.positions.lock().unwrap();
+        for position in positions {
+            current.insert(position.symbol.clone(), position);
+        }
+    }
+
+    /// Opens a short, or adds to an existing one with the average entry
+    /// price weighted by quantity.
+    pub fn open(&self, symbol: &Symbol, quantity: Decimal, price: Decimal) {
+        let mut positions = self.positions.lock().unwrap();
+        positions
+            .entry(symbol.clone())
+            .and_modify(|position| {
+                let total_quantity = position.quantity + quantity;
+                position.avg_entry_price =
+                    (position.avg_entry_price * position.quantity + price * quantity) / total_quantity;
+                position.quantity = total_quantity;
+            })
+            .or_insert_with(|| ShortPosition {
+                symbol: symbol.clone(),
+                quantity,
+                avg_entry_price: price,
+            });
+    }
+
+    /// Buys back up to `quantity` at `price`, shrinking or closing the
+    /// position and returning the realized PnL. Returns zero if there is no
+    /// open position for `symbol`.
+    pub fn close(&self, symbol: &Symbol, quantity: Decimal, price: Decimal) -> Decimal {
+        let mut positions = self.positions.lock().unwrap();
+        let Some(position) = positions.get_mut(symbol) else {
+            return Decimal::ZERO;
+        };
+
+        let closed_quantity = quantity.min(position.quantity);
+        let pnl = position.realized_pnl(closed_quantity, price);
+        position.quantity -= closed_quantity;
+        if position.quantity.is_zero() {
+            positions.remove(symbol);
+        }
+        pnl
+    }
+
+    pub fn positions(&self) -> Vec<ShortPosition> {
+        self.positions.lock().unwrap().values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn closing_part_of_a_position_leaves_the_remainder_open() {
+        let ledger = ShortLedger::new();
+        let symbol = Symbol::new("BTCUSDT");
+        ledger.open(&symbol, dec!(2.0), dec!(100.0));
+
+        let pnl = ledger.close(&symbol, dec!(1.0), dec!(90.0));
+        assert_eq!(pnl, dec!(10.0));
+
+        let positions = ledger.positions();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].quantity, dec!(1.0));
+    }
+
+    #[test]
+    fn fully_closing_a_position_removes_it() {
+        let ledger = ShortLedger::new();
+        let symbol = Symbol::new("BTCUSDT");
+        ledger.open(&symbol, dec!(1.0), dec!(100.0));
+        ledger.close(&symbol, dec!(1.0), dec!(95.0));
+        assert!(ledger.positions().is_empty());
+    }
+
+    #[test]
+    fn seeding_carries_a_prior_sessions_shorts_into_a_new_ledger() {
+        let ledger = ShortLedger::new();
+        let symbol = Symbol::new("BTCUSDT");
+        ledger.seed(vec![ShortPosition { symbol: symbol.clone(), quantity: dec!(2.0), avg_entry_price: dec!(100.0) }]);
+
+        let positions = ledger.positions();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].quantity, dec!(2.0));
+    }
+}
@@ -0,0 +1,193 @@
+//! In-process embedding API: build a [`Simulator`] without the axum HTTP
+//! layer so integration tests and Rust strategy code can drive sessions and
+//! market data at full speed instead of going through the network. The HTTP
+//! binary (`main.rs`) is itself just the thinnest possible caller of this.
+//!
+//! There's no order pipeline in this tree yet (see [`crate::webhook`] and
+//! [`crate::margin`] for the same caveat), so there is deliberately no
+//! `OrderHandle` here — only [`SessionHandle`] and [`MarketHandle`].
+
+use uuid::Uuid;
+
+use crate::domain::{Decimal, Kline, Session, SessionStatus, Symbol, SymbolFilters, Trade};
+use crate::error::ApiError;
+use crate::services::{self, ReplayService};
+use crate::state::AppState;
+
+const SEED_CHUNKS: usize = 3;
+const SEED_CHUNK_SIZE: i64 = 60;
+const SEED_TRADE_COUNT: i64 = 200;
+
+/// Builds a [`Simulator`], optionally seeded with the same demo BTCUSDT
+/// symbol filters, klines and trades the HTTP binary seeds at startup.
+#[derive(Debug, Default)]
+pub struct SimulatorBuilder {
+    seed_demo_data: bool,
+}
+
+impl SimulatorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Off by default so embedders (tests, strategy harnesses) start from
+    /// an empty store unless they explicitly ask for the demo dataset.
+    pub fn with_demo_data(mut self, enabled: bool) -> Self {
+        self.seed_demo_data = enabled;
+        self
+    }
+
+    pub async fn build(self) -> Simulator {
+        let state = AppState::new();
+        if self.seed_demo_data {
+            seed_default_symbols(&state);
+            seed_history(&state).await;
+            seed_trades(&state);
+        }
+        Simulator { state }
+    }
+}
+
+/// An in-process handle onto a running simulator. Cheap to clone: every
+/// clone shares the same underlying [`AppState`].
+#[derive(Clone)]
+pub struct Simulator {
+    state: AppState,
+}
+
+impl Simulator {
+    pub fn sessions(&self) -> SessionHandle {
+        SessionHandle { state: self.state.clone() }
+    }
+
+    pub fn market(&self) -> MarketHandle {
+        MarketHandle { state: self.state.clone() }
+    }
+
+    /// The underlying shared state, for callers (like the HTTP binary)
+    /// that need to hand it to `http::router` directly.
+    pub fn state(&self) -> AppState {
+        self.state.clone()
+    }
+}
+
+/// Typed session lifecycle operations, mirroring what `http::sessions`
+/// exposes over REST but driven in-process.
+#[derive(Clone)]
+pub struct SessionHandle {
+    state: AppState,
+}
+
+impl SessionHandle {
+    pub fn create(&self, session: Session) {
+        self.state.insert_session(session);
+    }
+
+    pub fn get(&self, id: Uuid) -> Result<Session, ApiError> {
+        self.state.get_session(id)
+    }
+
+    /// Marks the session running and spawns its replay loop, same as
+    /// `POST /api/v1/sessions/:id/start`.
+    pub fn start(&self, id: Uuid) -> Result<(), ApiError> {
+        self.state.set_status(id, SessionStatus::Running)?;
+        ReplayService::spawn(self.state.clone(), id);
+        Ok(())
+    }
+}
+
+/// Typed read access to the shared kline/trade stores, mirroring
+/// `http::market` but driven in-process.
+#[derive(Clone)]
+pub struct MarketHandle {
+    state: AppState,
+}
+
+impl MarketHandle {
+    pub fn klines(&self, symbol: &Symbol, interval: &str, start: i64, end: i64) -> Vec<Kline> {
+        self.state.market_store().klines(symbol, interval, start, end)
+    }
+
+    pub fn trades(&self, symbol: &Symbol, start: i64, end: i64) -> Vec<Trade> {
+        self.state.trade_store().trades(symbol, start, end)
+    }
+}
+
+/// Symbol metadata normally comes from the ingested symbols table; until
+/// ingestion writes it, the handful of symbols the simulator ships demos
+/// for are registered here with their real Binance tick/step sizes.
+fn seed_default_symbols(state: &AppState) {
+    state.set_symbol_filters(
+        Symbol::new("BTCUSDT"),
+        SymbolFilters {
+            base_asset: "BTC".to_string(),
+            quote_asset: "USDT".to_string(),
+            tick_size: Decimal::new(1, 2),
+            step_size: Decimal::new(1, 5),
+        },
+    );
+}
+
+/// Seeds a small backlog of historical BTCUSDT candles so `GET
+/// /api/v1/market/klines` has something to return before any session has
+/// run. A real dataset ingest runner would fetch this over HTTP in chunks;
+/// here the chunks are synthesized, but they still flow through the same
+/// pipelined [`services::run_ingest`] fetch/insert overlap a live ingest
+/// would use.
+async fn seed_history(state: &AppState) {
+    let mut next_chunk = 0usize;
+    let inserted = services::run_ingest(state.market_store(), move || {
+        let chunk_index = next_chunk;
+        next_chunk += 1;
+        async move {
+            if chunk_index >= SEED_CHUNKS {
+                return None;
+            }
+            Some(seed_chunk(chunk_index))
+        }
+    })
+    .await;
+    tracing::info!(inserted, "seeded historical klines");
+}
+
+fn seed_chunk(chunk_index: usize) -> Vec<Kline> {
+    let symbol = Symbol::new("BTCUSDT");
+    let base_open_time = chunk_index as i64 * SEED_CHUNK_SIZE * 60_000;
+    (0..SEED_CHUNK_SIZE)
+        .map(|i| {
+            let open_time = base_open_time + i * 60_000;
+            let price = Decimal::new(1_000_000 + chunk_index as i64 * SEED_CHUNK_SIZE + i, 2);
+            Kline {
+                symbol: symbol.clone(),
+                interval: "1m".to_string(),
+                open_time,
+                close_time: open_time + 59_999,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: Decimal::new(1, 0),
+                quote_volume: price,
+                is_closed: true,
+            }
+        })
+        .collect()
+}
+
+/// Seeds a small backlog of synthetic BTCUSDT agg trades so `GET
+/// /api/v1/market/trades` has something to return before any session has
+/// run, mirroring [`seed_history`] for the trade side of the store.
+fn seed_trades(state: &AppState) {
+    let symbol = Symbol::new("BTCUSDT");
+    let batch: Vec<Trade> = (0..SEED_TRADE_COUNT)
+        .map(|i| Trade {
+            symbol: symbol.clone(),
+            agg_trade_id: i,
+            price: Decimal::new(1_000_000 + i, 2),
+            quantity: Decimal::new(1, 0),
+            trade_time: i * 1_000,
+            is_buyer_maker: i % 2 == 0,
+        })
+        .collect();
+    state.trade_store().insert_trades(batch);
+}
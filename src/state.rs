@@ -0,0 +1,773 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::analytics::AnalyticsTracker;
+use crate::broadcaster::SessionBroadcaster;
+use crate::clock_group::ClockGroupRegistry;
+use crate::config::Config;
+use crate::dataset_progress::DatasetProgressTracker;
+use crate::dataset_registry::DatasetRegistry;
+use crate::domain::{
+    BreakpointCondition, DatasetProgress, Decimal, EquitySnapshot, MarketEventPayload, Namespace, Position, Session,
+    SessionStatus, SessionSummary, Symbol, SymbolFilters, TokenRole, WebhookEvent,
+};
+use crate::equity_curve::EquityCurve;
+use crate::error::ApiError;
+use crate::event_bus::{BusEvent, SessionEventBus};
+use crate::ingestion_ledger::IngestionLedger;
+use crate::latency::LatencyInjector;
+use crate::listen_key::ListenKeyRegistry;
+use crate::logging::LogFilterHandle;
+use crate::margin::MarginLedger;
+use crate::orders::OrderRateTracker;
+use crate::services::mark_price;
+use crate::shorts::ShortLedger;
+use crate::store::{CachingMarketStore, InMemoryMarketStore, InMemoryTradeStore, MarketStore, TradeStore};
+use crate::symbol_registry::SymbolLifecycleRegistry;
+use crate::tenancy::NamespaceRegistry;
+use crate::ticker::TickerTracker;
+use crate::ttl_cache::TtlCache;
+use crate::webhook::WebhookRegistry;
+use crate::ws_stats::WsStatsTracker;
+
+/// How long `cached_symbol_filters` may serve a stale symbol list before
+/// recomputing — short enough that `admin::set_symbol_metadata` feels
+/// near-immediate, long enough to absorb a UI hammering
+/// `GET /api/v3/exchangeInfo` on every page load.
+const SYMBOL_FILTERS_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Shared application state handed to every axum handler.
+#[derive(Clone)]
+pub struct AppState {
+    inner: Arc<AppStateInner>,
+}
+
+struct AppStateInner {
+    sessions: RwLock<HashMap<Uuid, Session>>,
+    broadcasters: RwLock<HashMap<Uuid, Arc<SessionBroadcaster>>>,
+    event_buses: RwLock<HashMap<Uuid, Arc<SessionEventBus>>>,
+    margin_ledgers: RwLock<HashMap<Uuid, Arc<MarginLedger>>>,
+    short_ledgers: RwLock<HashMap<Uuid, Arc<ShortLedger>>>,
+    analytics_trackers: RwLock<HashMap<Uuid, Arc<AnalyticsTracker>>>,
+    dataset_progress_trackers: RwLock<HashMap<Uuid, Arc<DatasetProgressTracker>>>,
+    ws_stats_trackers: RwLock<HashMap<Uuid, Arc<WsStatsTracker>>>,
+    equity_curves: RwLock<HashMap<Uuid, Arc<EquityCurve>>>,
+    summaries: RwLock<HashMap<Uuid, SessionSummary>>,
+    last_activity: RwLock<HashMap<Uuid, Instant>>,
+    namespaces: NamespaceRegistry,
+    webhooks: WebhookRegistry,
+    config: RwLock<Config>,
+    log_filter: RwLock<Option<LogFilterHandle>>,
+    latency: RwLock<LatencyInjector>,
+    symbol_filters: RwLock<HashMap<Symbol, SymbolFilters>>,
+    maintenance: AtomicBool,
+    shutting_down: AtomicBool,
+    market_store: CachingMarketStore<InMemoryMarketStore>,
+    trade_store: InMemoryTradeStore,
+    ticker_tracker: TickerTracker,
+    clock_groups: ClockGroupRegistry,
+    dataset_registry: DatasetRegistry,
+    ingestion_ledger: IngestionLedger,
+    symbol_lifecycle: SymbolLifecycleRegistry,
+    symbol_filters_cache: TtlCache<Vec<(Symbol, SymbolFilters)>>,
+    order_rate_tracker: OrderRateTracker,
+    listen_keys: ListenKeyRegistry,
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(AppStateInner {
+                sessions: RwLock::new(HashMap::new()),
+                broadcasters: RwLock::new(HashMap::new()),
+                event_buses: RwLock::new(HashMap::new()),
+                margin_ledgers: RwLock::new(HashMap::new()),
+                short_ledgers: RwLock::new(HashMap::new()),
+                analytics_trackers: RwLock::new(HashMap::new()),
+                dataset_progress_trackers: RwLock::new(HashMap::new()),
+                ws_stats_trackers: RwLock::new(HashMap::new()),
+                equity_curves: RwLock::new(HashMap::new()),
+                summaries: RwLock::new(HashMap::new()),
+                last_activity: RwLock::new(HashMap::new()),
+                namespaces: NamespaceRegistry::new(),
+                webhooks: WebhookRegistry::new(),
+                config: RwLock::new(Config::default()),
+                log_filter: RwLock::new(None),
+                latency: RwLock::new(LatencyInjector::default()),
+                symbol_filters: RwLock::new(HashMap::new()),
+                maintenance: AtomicBool::new(false),
+                shutting_down: AtomicBool::new(false),
+                market_store: CachingMarketStore::new(InMemoryMarketStore::new()),
+                trade_store: InMemoryTradeStore::new(),
+                ticker_tracker: TickerTracker::new(),
+                clock_groups: ClockGroupRegistry::new(),
+                dataset_registry: DatasetRegistry::new(),
+                ingestion_ledger: IngestionLedger::new(),
+                symbol_lifecycle: SymbolLifecycleRegistry::new(),
+                symbol_filters_cache: TtlCache::new(SYMBOL_FILTERS_CACHE_TTL),
+                order_rate_tracker: OrderRateTracker::new(),
+                listen_keys: ListenKeyRegistry::new(),
+            }),
+        }
+    }
+
+    pub fn market_store(&self) -> &dyn MarketStore {
+        &self.inner.market_store
+    }
+
+    pub fn trade_store(&self) -> &dyn TradeStore {
+        &self.inner.trade_store
+    }
+
+    /// Global, not per-session — see `ticker::TickerTracker`.
+    pub fn ticker_tracker(&self) -> &TickerTracker {
+        &self.inner.ticker_tracker
+    }
+
+    /// Shared across every session regardless of namespace, keyed by
+    /// `Session::clock_group` — see `clock_group::ClockGroupRegistry`.
+    pub fn clock_groups(&self) -> &ClockGroupRegistry {
+        &self.inner.clock_groups
+    }
+
+    pub fn market_cache_hits(&self) -> u64 {
+        self.inner.market_store.hits()
+    }
+
+    pub fn market_cache_misses(&self) -> u64 {
+        self.inner.market_store.misses()
+    }
+
+    /// Replaces the config the process started with, e.g. after `main`
+    /// loads it from file + env. Handlers read it back through
+    /// [`AppState::config`] rather than via a second axum `State` type.
+    pub fn set_config(&self, config: Config) {
+        *self.inner.config.write().unwrap() = config;
+    }
+
+    pub fn config(&self) -> Config {
+        self.inner.config.read().unwrap().clone()
+    }
+
+    /// Hands `AppState` the reload handle `logging::init` returned, so
+    /// `set_log_filter` has something to act on. Left unset when the
+    /// simulator is embedded without `logging::init` (e.g. in tests).
+    pub fn set_log_filter_handle(&self, handle: LogFilterHandle) {
+        *self.inner.log_filter.write().unwrap() = Some(handle);
+    }
+
+    /// Applies `directives` to the live tracing filter. Fails with
+    /// `ApiError::BadRequest` if no handle was installed or the directive
+    /// string doesn't parse.
+    pub fn set_log_filter(&self, directives: &str) -> Result<(), ApiError> {
+        let guard = self.inner.log_filter.read().unwrap();
+        let handle = guard.as_ref().ok_or_else(|| ApiError::BadRequest("log filter reload is not configured".into()))?;
+        crate::logging::set_filter(handle, directives).map_err(ApiError::BadRequest)
+    }
+
+    /// Replaces the `/api/v3` latency profiles, e.g. from `Config` at
+    /// startup.
+    pub fn configure_latency(&self, profiles: Vec<crate::domain::LatencyProfile>, seed: u64) {
+        *self.inner.latency.write().unwrap() = LatencyInjector::new(profiles, seed);
+    }
+
+    pub fn latency_delay_for(&self, path: &str) -> Option<std::time::Duration> {
+        self.inner.latency.read().unwrap().delay_for(path)
+    }
+
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.inner.maintenance.store(enabled, Ordering::SeqCst);
+    }
+
+    pub fn is_maintenance(&self) -> bool {
+        self.inner.maintenance.load(Ordering::SeqCst)
+    }
+
+    /// Marks the process as shutting down so live websocket/SSE handlers
+    /// can tell subscribers apart from a plain network blip before the
+    /// listener stops accepting connections.
+    pub fn begin_shutdown(&self) {
+        self.inner.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.inner.shutting_down.load(Ordering::SeqCst)
+    }
+
+    pub fn set_symbol_filters(&self, symbol: Symbol, filters: SymbolFilters) {
+        self.inner.symbol_filters.write().unwrap().insert(symbol, filters);
+    }
+
+    pub fn insert_session(&self, session: Session) {
+        let id = session.id;
+        let journal_cap = self.config().max_session_journal_events;
+        self.inner.sessions.write().unwrap().insert(id, session);
+        let broadcaster = Arc::new(SessionBroadcaster::new(id, journal_cap));
+        self.inner.broadcasters.write().unwrap().insert(id, broadcaster.clone());
+        let bus = Arc::new(SessionEventBus::new(id));
+        self.inner.event_buses.write().unwrap().insert(id, bus.clone());
+        spawn_broadcaster_forwarder(bus, broadcaster);
+        self.inner
+            .margin_ledgers
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(MarginLedger::new()));
+        self.inner
+            .short_ledgers
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(ShortLedger::new()));
+        self.inner
+            .analytics_trackers
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(AnalyticsTracker::new()));
+        self.inner
+            .dataset_progress_trackers
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(DatasetProgressTracker::new()));
+        self.inner
+            .ws_stats_trackers
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(WsStatsTracker::new()));
+        self.inner
+            .equity_curves
+            .write()
+            .unwrap()
+            .insert(id, Arc::new(EquityCurve::new()));
+        self.touch_activity(id);
+    }
+
+    pub fn get_session(&self, id: Uuid) -> Result<Session, ApiError> {
+        self.inner
+            .sessions
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    /// Like [`AppState::get_session`], but also requires the session to
+    /// belong to `namespace_id`. Reports `SessionNotFound` rather than a
+    /// separate "forbidden" error on mismatch, so a token from one
+    /// namespace can't use this to probe whether a session id exists in
+    /// another.
+    pub fn get_session_scoped(&self, id: Uuid, namespace_id: Uuid) -> Result<Session, ApiError> {
+        let session = self.get_session(id)?;
+        if session.namespace_id != namespace_id {
+            return Err(ApiError::SessionNotFound(id));
+        }
+        self.touch_activity(id);
+        Ok(session)
+    }
+
+    /// Every session belonging to `namespace_id`, ordered by id for a
+    /// stable listing. Sessions live only in memory (see `sessions` above),
+    /// so this is a snapshot of the current process, not a persisted store
+    /// query — there's no creation timestamp to sort by instead.
+    pub fn list_sessions(&self, namespace_id: Uuid) -> Vec<Session> {
+        let mut sessions: Vec<Session> =
+            self.inner.sessions.read().unwrap().values().filter(|session| session.namespace_id == namespace_id).cloned().collect();
+        sessions.sort_by_key(|session| session.id);
+        sessions
+    }
+
+    /// Records `id` as having seen activity just now, so `services::reaper`
+    /// doesn't reclaim it. Called from every session-scoped v1 request via
+    /// [`AppState::get_session_scoped`], which `http::ws::ws_session` also
+    /// goes through on connect — a live subscriber beyond that point is
+    /// covered separately by [`AppState::has_open_ws_connection`].
+    pub fn touch_activity(&self, id: Uuid) {
+        self.inner.last_activity.write().unwrap().insert(id, Instant::now());
+    }
+
+    /// How long it's been since `id` last saw activity, or `None` if it has
+    /// never recorded any (including if it doesn't exist).
+    pub fn idle_duration(&self, id: Uuid) -> Option<Duration> {
+        let last_activity = self.inner.last_activity.read().unwrap();
+        last_activity.get(&id).map(|instant| instant.elapsed())
+    }
+
+    /// Whether `id` currently has a websocket subscriber attached —
+    /// `services::reaper` treats this the same as recent API activity, so
+    /// a long-idle-but-watched session is never reclaimed out from under
+    /// its subscriber.
+    pub fn has_open_ws_connection(&self, id: Uuid) -> bool {
+        self.inner
+            .ws_stats_trackers
+            .read()
+            .unwrap()
+            .get(&id)
+            .map(|tracker| tracker.has_open_connection())
+            .unwrap_or(false)
+    }
+
+    /// Drops every per-session tracker for `id` (broadcaster, ledgers,
+    /// analytics, dataset progress, ws-stats, equity curve, activity
+    /// timestamp) without
+    /// touching the session record itself, freeing the bulk of what an
+    /// idle session holds onto while leaving it inspectable by id. Used by
+    /// `services::reaper` when `Config::idle_session_delete` is off.
+    pub fn clear_session_caches(&self, id: Uuid) {
+        self.inner.broadcasters.write().unwrap().remove(&id);
+        self.inner.margin_ledgers.write().unwrap().remove(&id);
+        self.inner.short_ledgers.write().unwrap().remove(&id);
+        self.inner.analytics_trackers.write().unwrap().remove(&id);
+        self.inner.dataset_progress_trackers.write().unwrap().remove(&id);
+        self.inner.ws_stats_trackers.write().unwrap().remove(&id);
+        self.inner.equity_curves.write().unwrap().remove(&id);
+        self.inner.last_activity.write().unwrap().remove(&id);
+        self.inner.order_rate_tracker.clear(id);
+    }
+
+    /// [`AppState::clear_session_caches`] plus the session record and
+    /// summary themselves. Used by `services::reaper` when
+    /// `Config::idle_session_delete` is on. A websocket still attached
+    /// when this runs sees `id` disappear from `get_session` on its next
+    /// lifecycle poll and closes with `CloseReason::Deleted`
+    /// (`http::ws`).
+    pub fn remove_session(&self, id: Uuid) {
+        self.clear_session_caches(id);
+        self.inner.sessions.write().unwrap().remove(&id);
+        self.inner.summaries.write().unwrap().remove(&id);
+    }
+
+    /// Every session across every namespace, regardless of status. Used by
+    /// callers that aren't namespace-scoped themselves — see
+    /// `services::dataset::dependent_sessions`.
+    pub fn all_sessions(&self) -> Vec<Session> {
+        self.inner.sessions.read().unwrap().values().cloned().collect()
+    }
+
+    /// Every session currently in `status`, across all namespaces — used by
+    /// the admin pause-all/resume-all kill switch, which quiesces the whole
+    /// simulator rather than one namespace at a time.
+    pub fn sessions_with_status(&self, status: SessionStatus) -> Vec<Session> {
+        self.inner
+            .sessions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|session| session.status == status)
+            .cloned()
+            .collect()
+    }
+
+    pub fn create_namespace(&self, name: String) -> Namespace {
+        self.inner.namespaces.create_namespace(name)
+    }
+
+    pub fn issue_token(&self, namespace_id: Uuid, role: TokenRole) -> Option<String> {
+        self.inner.namespaces.issue_token(namespace_id, role)
+    }
+
+    pub fn resolve_token(&self, token: &str) -> Option<(Uuid, TokenRole)> {
+        self.inner.namespaces.resolve(token)
+    }
+
+    pub fn set_status(&self, id: Uuid, status: crate::domain::SessionStatus) -> Result<(), ApiError> {
+        let session = {
+            let mut sessions = self.inner.sessions.write().unwrap();
+            let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+            session.status = status;
+            session.clone()
+        };
+        if let Ok(bus) = self.event_bus(id) {
+            bus.publish(BusEvent::Status(status));
+        }
+        if status == crate::domain::SessionStatus::Ended {
+            self.generate_summary(&session);
+            self.fire_webhook(id, WebhookEvent::SessionEnded, serde_json::json!({ "session_id": id }));
+        }
+        Ok(())
+    }
+
+    /// Builds and persists `session`'s [`SessionSummary`], then fires
+    /// `WebhookEvent::SummaryReady`. Called once, from `set_status` when a
+    /// session ends.
+    fn generate_summary(&self, session: &Session) {
+        let events = self.broadcaster(session.id).map(|b| b.events_from(0)).unwrap_or_default();
+        let trades: Vec<_> = events
+            .iter()
+            .filter_map(|event| match &event.payload {
+                MarketEventPayload::Trade(trade) => Some(trade),
+                _ => None,
+            })
+            .collect();
+        let total_volume = trades.iter().fold(Decimal::ZERO, |acc, trade| acc + trade.quantity);
+
+        let (open_borrows, open_shorts, total_unrealized_pnl) = self.position_totals(session);
+
+        let summary = SessionSummary {
+            session_id: session.id,
+            generated_at: session.current_time,
+            event_count: events.len() as u64,
+            trade_count: trades.len() as u64,
+            total_volume,
+            total_fees: Decimal::ZERO,
+            open_borrows,
+            open_shorts,
+            total_unrealized_pnl,
+            order_rate: self.inner.order_rate_tracker.snapshot(session.id),
+        };
+        self.inner.summaries.write().unwrap().insert(session.id, summary);
+        self.fire_webhook(session.id, WebhookEvent::SummaryReady, serde_json::json!({ "session_id": session.id }));
+    }
+
+    /// The balance-shaped state `generate_summary` and
+    /// `record_equity_snapshot` both report: open borrows, open shorts, and
+    /// the shorts' total unrealized PnL marked at `session.current_time`.
+    /// Shared so a session's end-of-run summary and its in-flight equity
+    /// curve agree on what "balance" means.
+    fn position_totals(&self, session: &Session) -> (Vec<crate::domain::BorrowPosition>, Vec<crate::domain::ShortPosition>, Decimal) {
+        let open_borrows = match session.margin {
+            Some(config) => self.margin_ledger(session.id).map(|l| l.positions(session.current_time, &config)).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let open_shorts = self.short_ledger(session.id).map(|l| l.positions()).unwrap_or_default();
+        let total_unrealized_pnl = open_shorts.iter().fold(Decimal::ZERO, |acc, position| {
+            let mark = mark_price::compute(self, &position.symbol, &session.interval, session.current_time, session.mark_price.as_ref());
+            acc + mark.map(|mark| position.realized_pnl(position.quantity, mark)).unwrap_or(Decimal::ZERO)
+        });
+        (open_borrows, open_shorts, total_unrealized_pnl)
+    }
+
+    /// Per-symbol net positions for reporting, see [`Position`]'s doc
+    /// comment for why this is just the open shorts reshaped rather than a
+    /// true net position across longs and shorts.
+    pub fn positions(&self, id: Uuid) -> Result<Vec<Position>, ApiError> {
+        let session = self.get_session(id)?;
+        let open_shorts = self.short_ledger(id)?.positions();
+        Ok(open_shorts
+            .into_iter()
+            .map(|position| {
+                let mark = mark_price::compute(self, &position.symbol, &session.interval, session.current_time, session.mark_price.as_ref());
+                let unrealized_pnl = mark.map(|mark| position.realized_pnl(position.quantity, mark)).unwrap_or(Decimal::ZERO);
+                Position {
+                    symbol: position.symbol,
+                    quantity: -position.quantity,
+                    avg_entry_price: position.avg_entry_price,
+                    unrealized_pnl,
+                }
+            })
+            .collect())
+    }
+
+    /// Appends a point to `id`'s equity curve using its current balance
+    /// state, the same computation `generate_summary` uses for the
+    /// once-at-end [`SessionSummary`]. Called periodically from
+    /// `services::replay_service` when `Session::equity_snapshot_interval_ms`
+    /// is set.
+    pub fn record_equity_snapshot(&self, id: Uuid) -> Result<(), ApiError> {
+        let session = self.get_session(id)?;
+        let (open_borrows, open_shorts, total_unrealized_pnl) = self.position_totals(&session);
+        self.equity_curve(id)?.record(EquitySnapshot {
+            simulated_time: session.current_time,
+            open_borrows,
+            open_shorts,
+            total_unrealized_pnl,
+        });
+        Ok(())
+    }
+
+    /// The persisted summary for `id`, if its session has ended. Returns
+    /// `ApiError::SessionNotFound` if the session itself doesn't exist, or
+    /// `ApiError::BadRequest` if it exists but hasn't ended yet.
+    pub fn summary(&self, id: Uuid) -> Result<SessionSummary, ApiError> {
+        self.get_session(id)?;
+        self.inner
+            .summaries
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| ApiError::BadRequest("session has not ended yet".into()))
+    }
+
+    pub fn webhooks(&self) -> &WebhookRegistry {
+        &self.inner.webhooks
+    }
+
+    /// Global, not per-session — see `dataset_registry::DatasetRegistry`.
+    pub fn dataset_registry(&self) -> &DatasetRegistry {
+        &self.inner.dataset_registry
+    }
+
+    pub fn ingestion_ledger(&self) -> &IngestionLedger {
+        &self.inner.ingestion_ledger
+    }
+
+    /// Global, not per-session — see
+    /// `symbol_registry::SymbolLifecycleRegistry`.
+    pub fn symbol_lifecycle(&self) -> &SymbolLifecycleRegistry {
+        &self.inner.symbol_lifecycle
+    }
+
+    pub fn order_rate_tracker(&self) -> &OrderRateTracker {
+        &self.inner.order_rate_tracker
+    }
+
+    /// Global, not namespace- or session-scoped storage-wise (ownership is
+    /// still checked per call) — see `listen_key::ListenKeyRegistry`.
+    pub fn listen_keys(&self) -> &ListenKeyRegistry {
+        &self.inner.listen_keys
+    }
+
+    /// Fires `event` to every webhook subscribed to it for `id`, delivering
+    /// each on its own spawned task (with retry/backoff) so a slow or
+    /// unreachable endpoint never blocks the caller.
+    pub fn fire_webhook(&self, id: Uuid, event: WebhookEvent, payload: serde_json::Value) {
+        for (webhook_id, url, secret) in self.inner.webhooks.targets(id, event) {
+            let state = self.clone();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                let delivery = crate::webhook::deliver(&state.inner.webhooks.client(), &url, &secret, event, payload).await;
+                state.inner.webhooks.record(webhook_id, delivery);
+            });
+        }
+    }
+
+    /// Records how far the replay loop has advanced `id`'s simulated clock,
+    /// so session-scoped market data endpoints can clamp queries to it.
+    pub fn advance_clock(&self, id: Uuid, current_time: i64) -> Result<(), ApiError> {
+        let mut sessions = self.inner.sessions.write().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+        session.current_time = current_time;
+        Ok(())
+    }
+
+    /// Sets or clears `id`'s `Session::pause_at` breakpoint. See
+    /// `http::sessions::pause_at`.
+    pub fn set_pause_at(&self, id: Uuid, pause_at: Option<i64>) -> Result<(), ApiError> {
+        let mut sessions = self.inner.sessions.write().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+        session.pause_at = pause_at;
+        Ok(())
+    }
+
+    /// Requests that `id`'s replay loop jump forward to simulated time
+    /// `to` on its next tick. Rejects `to` earlier than the session's
+    /// current simulated time — see [`Session::seek_to`] for why
+    /// rewinding isn't supported.
+    pub fn seek(&self, id: Uuid, to: i64) -> Result<(), ApiError> {
+        let mut sessions = self.inner.sessions.write().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+        if to < session.current_time {
+            return Err(ApiError::BadRequest(format!(
+                "cannot seek backward: requested {to} is before the session's current simulated time {}",
+                session.current_time
+            )));
+        }
+        session.seek_to = Some(to);
+        Ok(())
+    }
+
+    /// Clears `id`'s pending [`Session::seek_to`], e.g. once
+    /// `ReplayService` has applied it. Silently a no-op if the session no
+    /// longer exists.
+    pub fn clear_seek(&self, id: Uuid) {
+        if let Some(session) = self.inner.sessions.write().unwrap().get_mut(&id) {
+            session.seek_to = None;
+        }
+    }
+
+    /// Toggles [`Session::matching_enabled`] and/or swaps
+    /// [`Session::matching_engine`], only while the session is
+    /// [`SessionStatus::Paused`] — changing the fill model mid-replay
+    /// would mean some of a run's events were judged under one model and
+    /// the rest under another, which `AppState::summary` has no way to
+    /// attribute back. Either argument left `None` leaves that field
+    /// untouched.
+    pub fn set_matching_config(&self, id: Uuid, matching_enabled: Option<bool>, matching_engine: Option<crate::domain::MatchingEngineKind>) -> Result<Session, ApiError> {
+        let mut sessions = self.inner.sessions.write().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+        if session.status != crate::domain::SessionStatus::Paused {
+            return Err(ApiError::BadRequest(format!(
+                "session {id} must be paused to change its matching configuration, is {:?}",
+                session.status
+            )));
+        }
+        if let Some(matching_enabled) = matching_enabled {
+            session.matching_enabled = matching_enabled;
+        }
+        if let Some(matching_engine) = matching_engine {
+            session.matching_engine = Some(matching_engine);
+        }
+        Ok(session.clone())
+    }
+
+    pub fn add_breakpoint(&self, id: Uuid, condition: BreakpointCondition) -> Result<Session, ApiError> {
+        let mut sessions = self.inner.sessions.write().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+        session.breakpoints.push(condition);
+        Ok(session.clone())
+    }
+
+    /// Removes the breakpoint at `index` if it's still there. Silently a
+    /// no-op otherwise — `ReplayService` calls this right after a
+    /// breakpoint fires, and the session may have been mutated concurrently
+    /// (e.g. another caller cleared it first) by the time the lock is
+    /// reacquired.
+    pub fn remove_breakpoint(&self, id: Uuid, index: usize) -> Result<(), ApiError> {
+        let mut sessions = self.inner.sessions.write().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::SessionNotFound(id))?;
+        if index < session.breakpoints.len() {
+            session.breakpoints.remove(index);
+        }
+        Ok(())
+    }
+
+    pub fn all_symbol_filters(&self) -> Vec<(Symbol, SymbolFilters)> {
+        self.inner
+            .symbol_filters
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(s, f)| (s.clone(), f.clone()))
+            .collect()
+    }
+
+    /// Same as [`AppState::all_symbol_filters`], but memoized for
+    /// [`SYMBOL_FILTERS_CACHE_TTL`] — what `v3::exchange_info` calls
+    /// instead, since its `GET /api/v3/exchangeInfo` is the one endpoint a
+    /// UI is expected to poll repeatedly just to refresh a symbol
+    /// dropdown.
+    pub fn cached_symbol_filters(&self) -> Vec<(Symbol, SymbolFilters)> {
+        self.inner.symbol_filters_cache.get_or_compute(|| self.all_symbol_filters())
+    }
+
+    pub fn symbol_filters(&self, symbol: &Symbol) -> Option<SymbolFilters> {
+        self.inner.symbol_filters.read().unwrap().get(symbol).cloned()
+    }
+
+    pub fn broadcaster(&self, id: Uuid) -> Result<Arc<SessionBroadcaster>, ApiError> {
+        self.inner
+            .broadcasters
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    /// The session's internal typed event bus — see [`crate::event_bus`].
+    /// `ReplayService` publishes onto this rather than calling
+    /// `broadcaster()` directly; [`spawn_broadcaster_forwarder`] is the one
+    /// subscriber that bridges `Market` events back onto the
+    /// [`SessionBroadcaster`] every ws/SSE/webhook consumer already relies
+    /// on.
+    pub fn event_bus(&self, id: Uuid) -> Result<Arc<SessionEventBus>, ApiError> {
+        self.inner
+            .event_buses
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    pub fn margin_ledger(&self, id: Uuid) -> Result<Arc<MarginLedger>, ApiError> {
+        self.inner
+            .margin_ledgers
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    pub fn short_ledger(&self, id: Uuid) -> Result<Arc<ShortLedger>, ApiError> {
+        self.inner
+            .short_ledgers
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    pub fn analytics_tracker(&self, id: Uuid) -> Result<Arc<AnalyticsTracker>, ApiError> {
+        self.inner
+            .analytics_trackers
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    pub fn dataset_progress_tracker(&self, id: Uuid) -> Result<Arc<DatasetProgressTracker>, ApiError> {
+        self.inner
+            .dataset_progress_trackers
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    /// Every session's dataset-progress snapshot, across all namespaces —
+    /// the fan-out `http::datasets::dataset_events` polls instead of
+    /// opening one `get_session_dataset_progress` stream per session.
+    pub fn dataset_progress_snapshots(&self) -> Vec<(Uuid, DatasetProgress)> {
+        self.inner
+            .dataset_progress_trackers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(&id, tracker)| (id, tracker.snapshot()))
+            .collect()
+    }
+
+    pub fn ws_stats_tracker(&self, id: Uuid) -> Result<Arc<WsStatsTracker>, ApiError> {
+        self.inner
+            .ws_stats_trackers
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+
+    pub fn equity_curve(&self, id: Uuid) -> Result<Arc<EquityCurve>, ApiError> {
+        self.inner
+            .equity_curves
+            .read()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or(ApiError::SessionNotFound(id))
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bridges a session's [`SessionEventBus`] to its [`SessionBroadcaster`] so
+/// every consumer built against the broadcaster (ws, SSE, webhooks) keeps
+/// working unchanged now that producers publish onto the bus instead of
+/// calling the broadcaster directly. Exits once the bus has no more
+/// senders, i.e. when the session is dropped from `AppStateInner`.
+fn spawn_broadcaster_forwarder(bus: Arc<SessionEventBus>, broadcaster: Arc<SessionBroadcaster>) {
+    let mut rx = bus.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(BusEvent::Market(event)) => broadcaster.publish(event),
+                Ok(BusEvent::Status(_)) | Ok(BusEvent::Order(_)) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
@@ -0,0 +1,194 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use lru::LruCache;
+
+use crate::domain::{Kline, Symbol};
+
+use super::MarketStore;
+
+const DEFAULT_CAPACITY: usize = 512;
+
+type CacheKey = (Symbol, String, i64, i64);
+
+/// Wraps a `MarketStore` with an LRU cache over recently served kline
+/// ranges, keyed by the exact `(symbol, interval, start, end)` of the
+/// query. Replay re-issuing the same `start`/`end` tick after tick hits
+/// the cache instead of round-tripping to the backing store; anything with
+/// a different `start` or `end` is a distinct key, so it's never served
+/// another query's range by mistake.
+///
+/// [`klines_page`](MarketStore::klines_page) is deliberately *not* served
+/// from this cache: it delegates straight to the inner store's own bounded
+/// range scan. Caching it under the trait default would mean every page of
+/// a multi-year range re-fetches (and re-caches, under a fresh key every
+/// time) the entire remaining range just to take a handful of rows off the
+/// front.
+pub struct CachingMarketStore<S> {
+    inner: S,
+    cache: Mutex<LruCache<CacheKey, Vec<Kline>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<S: MarketStore> CachingMarketStore<S> {
+    pub fn new(inner: S) -> Self {
+        Self::with_capacity(inner, DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(inner: S, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN),
+            )),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn key(symbol: &Symbol, interval: &str, start: i64, end: i64) -> CacheKey {
+        (symbol.clone(), interval.to_string(), start, end)
+    }
+}
+
+impl<S: MarketStore> MarketStore for CachingMarketStore<S> {
+    fn klines(&self, symbol: &Symbol, interval: &str, start: i64, end: i64) -> Vec<Kline> {
+        let key = Self::key(symbol, interval, start, end);
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return cached.clone();
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = self.inner.klines(symbol, interval, start, end);
+        self.cache.lock().unwrap().put(key, result.clone());
+        result
+    }
+
+    fn klines_page(&self, symbol: &Symbol, interval: &str, cursor: i64, end: i64, limit: usize) -> Vec<Kline> {
+        // The trait default would call `klines(cursor + 1, end)` — on this
+        // decorator that's the whole-range, cache-populating lookup above,
+        // so streaming a multi-year range page by page would refetch (and
+        // re-cache under a fresh key) the entire remaining range on every
+        // page. Pagination doesn't benefit from whole-range caching the way
+        // replay's fixed-range re-polling does, so go straight to the
+        // inner store's own bounded range scan instead.
+        self.inner.klines_page(symbol, interval, cursor, end, limit)
+    }
+
+    fn insert_kline(&self, kline: Kline) {
+        self.inner.insert_kline(kline);
+        // Precisely invalidating only affected buckets needs range
+        // bookkeeping the cache doesn't keep; ingestion writes are rare
+        // relative to reads, so a full clear is cheap enough.
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn insert_klines(&self, batch: Vec<Kline>) {
+        self.inner.insert_klines(batch);
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn all_klines(&self) -> Vec<Kline> {
+        self.inner.all_klines()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::store::InMemoryMarketStore;
+
+    fn kline(open_time: i64) -> Kline {
+        Kline {
+            symbol: Symbol::new("BTCUSDT"),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: dec!(1),
+            high: dec!(1),
+            low: dec!(1),
+            close: dec!(1),
+            volume: dec!(1),
+            quote_volume: dec!(1),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn repeated_query_hits_cache() {
+        let cache = CachingMarketStore::new(InMemoryMarketStore::new());
+        cache.insert_kline(kline(0));
+        let symbol = Symbol::new("BTCUSDT");
+
+        cache.klines(&symbol, "1m", 0, 60_000);
+        cache.klines(&symbol, "1m", 0, 60_000);
+
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn insert_invalidates_cache() {
+        let cache = CachingMarketStore::new(InMemoryMarketStore::new());
+        let symbol = Symbol::new("BTCUSDT");
+        cache.insert_kline(kline(0));
+        cache.klines(&symbol, "1m", 0, 60_000);
+        cache.insert_kline(kline(60_000));
+        cache.klines(&symbol, "1m", 0, 60_000);
+
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn two_queries_sharing_an_end_but_with_different_starts_dont_collide() {
+        let cache = CachingMarketStore::new(InMemoryMarketStore::new());
+        let symbol = Symbol::new("BTCUSDT");
+        cache.insert_kline(kline(0));
+        cache.insert_kline(kline(1_800_000));
+
+        let from_zero = cache.klines(&symbol, "1m", 0, 3_600_000);
+        let from_half_hour = cache.klines(&symbol, "1m", 1_800_000, 3_600_000);
+
+        assert_eq!(cache.misses(), 2);
+        assert!(from_zero.iter().any(|k| k.open_time == 0));
+        assert!(from_half_hour.iter().all(|k| k.open_time >= 1_800_000));
+    }
+
+    #[test]
+    fn paginating_bypasses_the_whole_range_cache() {
+        let cache = CachingMarketStore::new(InMemoryMarketStore::new());
+        let symbol = Symbol::new("BTCUSDT");
+        for open_time in [0, 60_000, 120_000, 180_000] {
+            cache.insert_kline(kline(open_time));
+        }
+
+        let first_page = cache.klines_page(&symbol, "1m", -1, 240_000, 2);
+        let second_page = cache.klines_page(&symbol, "1m", first_page.last().unwrap().open_time, 240_000, 2);
+
+        assert_eq!(
+            first_page.iter().map(|k| k.open_time).collect::<Vec<_>>(),
+            vec![0, 60_000]
+        );
+        assert_eq!(
+            second_page.iter().map(|k| k.open_time).collect::<Vec<_>>(),
+            vec![120_000, 180_000]
+        );
+        // Paging never touches the whole-range `klines()` cache at all.
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 0);
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use crate::domain::{Kline, Symbol};
+
+use super::MarketStore;
+
+/// Dev/default `MarketStore` backed by an in-process `BTreeMap`. Good
+/// enough until ingestion writes to a real embedded database; the trait
+/// boundary means callers don't need to change when that lands.
+#[derive(Default)]
+pub struct InMemoryMarketStore {
+    // (symbol, interval) -> open_time -> kline
+    klines: RwLock<BTreeMap<(Symbol, String), BTreeMap<i64, Kline>>>,
+}
+
+impl InMemoryMarketStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MarketStore for InMemoryMarketStore {
+    fn klines(&self, symbol: &Symbol, interval: &str, start: i64, end: i64) -> Vec<Kline> {
+        let klines = self.klines.read().unwrap();
+        let Some(series) = klines.get(&(symbol.clone(), interval.to_string())) else {
+            return Vec::new();
+        };
+        series.range(start..end).map(|(_, k)| k.clone()).collect()
+    }
+
+    fn klines_page(&self, symbol: &Symbol, interval: &str, cursor: i64, end: i64, limit: usize) -> Vec<Kline> {
+        let klines = self.klines.read().unwrap();
+        let Some(series) = klines.get(&(symbol.clone(), interval.to_string())) else {
+            return Vec::new();
+        };
+        series
+            .range(cursor.saturating_add(1)..end)
+            .take(limit)
+            .map(|(_, k)| k.clone())
+            .collect()
+    }
+
+    fn insert_kline(&self, kline: Kline) {
+        let mut klines = self.klines.write().unwrap();
+        klines
+            .entry((kline.symbol.clone(), kline.interval.clone()))
+            .or_default()
+            .insert(kline.open_time, kline);
+    }
+
+    fn insert_klines(&self, batch: Vec<Kline>) {
+        let mut klines = self.klines.write().unwrap();
+        for kline in batch {
+            klines
+                .entry((kline.symbol.clone(), kline.interval.clone()))
+                .or_default()
+                .insert(kline.open_time, kline);
+        }
+    }
+
+    fn all_klines(&self) -> Vec<Kline> {
+        self.klines.read().unwrap().values().flat_map(|series| series.values().cloned()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn kline(symbol: &str, open_time: i64) -> Kline {
+        Kline {
+            symbol: Symbol::new(symbol),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open: dec!(1),
+            high: dec!(1),
+            low: dec!(1),
+            close: dec!(1),
+            volume: dec!(1),
+            quote_volume: dec!(1),
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn range_query_respects_bounds() {
+        let store = InMemoryMarketStore::new();
+        for t in [0, 60_000, 120_000, 180_000] {
+            store.insert_kline(kline("BTCUSDT", t));
+        }
+        let symbol = Symbol::new("BTCUSDT");
+        let result = store.klines(&symbol, "1m", 60_000, 180_000);
+        let open_times: Vec<i64> = result.iter().map(|k| k.open_time).collect();
+        assert_eq!(open_times, vec![60_000, 120_000]);
+    }
+
+    #[test]
+    fn paginated_query_respects_cursor_and_limit() {
+        let store = InMemoryMarketStore::new();
+        for t in [0, 60_000, 120_000, 180_000] {
+            store.insert_kline(kline("BTCUSDT", t));
+        }
+        let symbol = Symbol::new("BTCUSDT");
+
+        let first_page = store.klines_page(&symbol, "1m", -1, 240_000, 2);
+        let open_times: Vec<i64> = first_page.iter().map(|k| k.open_time).collect();
+        assert_eq!(open_times, vec![0, 60_000]);
+
+        let cursor = first_page.last().unwrap().open_time;
+        let second_page = store.klines_page(&symbol, "1m", cursor, 240_000, 2);
+        let open_times: Vec<i64> = second_page.iter().map(|k| k.open_time).collect();
+        assert_eq!(open_times, vec![120_000, 180_000]);
+    }
+
+    #[test]
+    fn bulk_insert_stores_every_kline() {
+        let store = InMemoryMarketStore::new();
+        let batch = vec![kline("ETHUSDT", 0), kline("ETHUSDT", 60_000)];
+        store.insert_klines(batch);
+
+        let symbol = Symbol::new("ETHUSDT");
+        let result = store.klines(&symbol, "1m", 0, 120_000);
+        assert_eq!(result.len(), 2);
+    }
+}
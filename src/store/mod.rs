@@ -0,0 +1,107 @@
+//! Storage abstraction for historical market data. `MarketStore` is the
+//! port every backing implementation (in-memory for now, DuckDB once
+//! ingestion lands) and decorator (caching, metrics) implements, so the
+//! replay engine and v1 market endpoints never depend on a concrete store.
+
+mod cache;
+mod memory;
+mod trades;
+
+pub use cache::CachingMarketStore;
+pub use memory::InMemoryMarketStore;
+pub use trades::InMemoryTradeStore;
+
+use crate::domain::{Kline, Symbol, Trade};
+
+pub trait MarketStore: Send + Sync {
+    /// Klines for `symbol`/`interval` with `open_time` in `[start, end)`,
+    /// ordered oldest first.
+    fn klines(&self, symbol: &Symbol, interval: &str, start: i64, end: i64) -> Vec<Kline>;
+
+    fn insert_kline(&self, kline: Kline);
+
+    /// Bulk insert hook for ingestion pipelines. The default loops over
+    /// [`insert_kline`](MarketStore::insert_kline); backing stores with a
+    /// genuine bulk-write path (e.g. an appender-based implementation)
+    /// should override this instead of paying per-row overhead.
+    fn insert_klines(&self, klines: Vec<Kline>) {
+        for kline in klines {
+            self.insert_kline(kline);
+        }
+    }
+
+    /// Cursor-paginated variant of [`klines`](MarketStore::klines): at most
+    /// `limit` klines with `open_time` in `(cursor, end)`, ordered oldest
+    /// first. The default just truncates a full range query; a store that
+    /// can bound its own range scan (see `InMemoryMarketStore`) should
+    /// override this so a huge range doesn't get fully materialized before
+    /// the limit is applied.
+    fn klines_page(&self, symbol: &Symbol, interval: &str, cursor: i64, end: i64, limit: usize) -> Vec<Kline> {
+        self.klines(symbol, interval, cursor.saturating_add(1), end)
+            .into_iter()
+            .take(limit)
+            .collect()
+    }
+
+    /// Every kline across every symbol/interval, for a full-store dump
+    /// (see `services::backup`). The default returns empty — a store that
+    /// can't cheaply enumerate everything it holds (or doesn't want to,
+    /// e.g. a remote one) should just not override this rather than pay
+    /// for a full scan it can't otherwise avoid.
+    fn all_klines(&self) -> Vec<Kline> {
+        Vec::new()
+    }
+}
+
+/// Storage port for aggregated-trade prints, mirroring [`MarketStore`] but
+/// keyed by `agg_trade_id` rather than `open_time`.
+pub trait TradeStore: Send + Sync {
+    /// Trades for `symbol` with `trade_time` in `[start, end)`, ordered
+    /// oldest first.
+    fn trades(&self, symbol: &Symbol, start: i64, end: i64) -> Vec<Trade>;
+
+    fn insert_trade(&self, trade: Trade);
+
+    /// Bulk insert hook for ingestion pipelines. The default loops over
+    /// [`insert_trade`](TradeStore::insert_trade); a backing store with a
+    /// real bulk-write path (e.g. a DuckDB appender) should override this
+    /// instead of preparing and executing once per row.
+    fn insert_trades(&self, trades: Vec<Trade>) {
+        for trade in trades {
+            self.insert_trade(trade);
+        }
+    }
+
+    /// Every trade across every symbol, for a full-store dump (see
+    /// `services::backup`). See [`MarketStore::all_klines`] for why the
+    /// default is empty rather than a forced full scan.
+    fn all_trades(&self) -> Vec<Trade> {
+        Vec::new()
+    }
+
+    /// At most `limit` trades for `symbol` with `agg_trade_id > from_id`,
+    /// ordered oldest first — Binance's `historicalTrades`/`aggTrades`
+    /// `fromId` pagination. The default filters a full `all_trades` scan; a
+    /// store that can bound its own range scan should override this the
+    /// way [`InMemoryTradeStore`] does.
+    fn trades_by_id(&self, symbol: &Symbol, from_id: i64, limit: usize) -> Vec<Trade> {
+        let mut trades: Vec<Trade> = self
+            .all_trades()
+            .into_iter()
+            .filter(|t| &t.symbol == symbol && t.agg_trade_id > from_id)
+            .collect();
+        trades.sort_by_key(|t| t.agg_trade_id);
+        trades.truncate(limit);
+        trades
+    }
+
+    /// The most recent `limit` trades for `symbol`, ordered oldest first —
+    /// Binance's behavior when `historicalTrades`/`aggTrades` is called
+    /// with no `fromId`.
+    fn latest_trades(&self, symbol: &Symbol, limit: usize) -> Vec<Trade> {
+        let mut trades: Vec<Trade> = self.all_trades().into_iter().filter(|t| &t.symbol == symbol).collect();
+        trades.sort_by_key(|t| t.agg_trade_id);
+        let skip = trades.len().saturating_sub(limit);
+        trades.split_off(skip)
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+use crate::domain::{Symbol, Trade};
+
+use super::TradeStore;
+
+/// Dev/default `TradeStore` backed by an in-process `BTreeMap`, one per
+/// symbol keyed by `agg_trade_id`. Good enough until ingestion writes to a
+/// real embedded database; the trait boundary means callers don't need to
+/// change when that lands.
+#[derive(Default)]
+pub struct InMemoryTradeStore {
+    trades: RwLock<HashMap<Symbol, BTreeMap<i64, Trade>>>,
+}
+
+impl InMemoryTradeStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TradeStore for InMemoryTradeStore {
+    fn trades(&self, symbol: &Symbol, start: i64, end: i64) -> Vec<Trade> {
+        let trades = self.trades.read().unwrap();
+        let Some(series) = trades.get(symbol) else {
+            return Vec::new();
+        };
+        series
+            .values()
+            .filter(|t| t.trade_time >= start && t.trade_time < end)
+            .cloned()
+            .collect()
+    }
+
+    fn insert_trade(&self, trade: Trade) {
+        let mut trades = self.trades.write().unwrap();
+        trades
+            .entry(trade.symbol.clone())
+            .or_default()
+            .insert(trade.agg_trade_id, trade);
+    }
+
+    fn insert_trades(&self, batch: Vec<Trade>) {
+        let mut trades = self.trades.write().unwrap();
+        for trade in batch {
+            trades
+                .entry(trade.symbol.clone())
+                .or_default()
+                .insert(trade.agg_trade_id, trade);
+        }
+    }
+
+    fn all_trades(&self) -> Vec<Trade> {
+        self.trades.read().unwrap().values().flat_map(|series| series.values().cloned()).collect()
+    }
+
+    fn trades_by_id(&self, symbol: &Symbol, from_id: i64, limit: usize) -> Vec<Trade> {
+        let trades = self.trades.read().unwrap();
+        let Some(series) = trades.get(symbol) else {
+            return Vec::new();
+        };
+        series
+            .range(from_id.saturating_add(1)..)
+            .take(limit)
+            .map(|(_, t)| t.clone())
+            .collect()
+    }
+
+    fn latest_trades(&self, symbol: &Symbol, limit: usize) -> Vec<Trade> {
+        let trades = self.trades.read().unwrap();
+        let Some(series) = trades.get(symbol) else {
+            return Vec::new();
+        };
+        let mut trades: Vec<Trade> = series.values().rev().take(limit).cloned().collect();
+        trades.reverse();
+        trades
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn trade(symbol: &str, agg_trade_id: i64, trade_time: i64) -> Trade {
+        Trade {
+            symbol: Symbol::new(symbol),
+            agg_trade_id,
+            price: dec!(1),
+            quantity: dec!(1),
+            trade_time,
+            is_buyer_maker: false,
+        }
+    }
+
+    #[test]
+    fn bulk_insert_is_queryable_by_time_range() {
+        let store = InMemoryTradeStore::new();
+        store.insert_trades(vec![
+            trade("BTCUSDT", 1, 0),
+            trade("BTCUSDT", 2, 1_000),
+            trade("BTCUSDT", 3, 2_000),
+        ]);
+
+        let symbol = Symbol::new("BTCUSDT");
+        let result = store.trades(&symbol, 1_000, 3_000);
+        let ids: Vec<i64> = result.iter().map(|t| t.agg_trade_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn trades_by_id_returns_those_after_from_id_oldest_first() {
+        let store = InMemoryTradeStore::new();
+        store.insert_trades(vec![
+            trade("BTCUSDT", 1, 0),
+            trade("BTCUSDT", 2, 1_000),
+            trade("BTCUSDT", 3, 2_000),
+        ]);
+
+        let symbol = Symbol::new("BTCUSDT");
+        let result = store.trades_by_id(&symbol, 1, 10);
+        let ids: Vec<i64> = result.iter().map(|t| t.agg_trade_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn latest_trades_returns_the_most_recent_n_oldest_first() {
+        let store = InMemoryTradeStore::new();
+        store.insert_trades(vec![
+            trade("BTCUSDT", 1, 0),
+            trade("BTCUSDT", 2, 1_000),
+            trade("BTCUSDT", 3, 2_000),
+        ]);
+
+        let symbol = Symbol::new("BTCUSDT");
+        let result = store.latest_trades(&symbol, 2);
+        let ids: Vec<i64> = result.iter().map(|t| t.agg_trade_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+}
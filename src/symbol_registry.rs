@@ -0,0 +1,115 @@
+//! Global (not session-scoped) table of symbol renames and delistings over
+//! the replay window, so ingestion and session-scoped market queries can
+//! honor them instead of treating every symbol as permanent. Separate from
+//! `dataset_registry::DatasetRegistry`, which only tracks what coverage was
+//! registered, not what happened to a symbol's identity.
+
+use std::sync::Mutex;
+
+use crate::domain::{Symbol, SymbolLifecycleEvent};
+
+#[derive(Default)]
+pub struct SymbolLifecycleRegistry {
+    events: Mutex<Vec<SymbolLifecycleEvent>>,
+}
+
+impl SymbolLifecycleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, event: SymbolLifecycleEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    pub fn all(&self) -> Vec<SymbolLifecycleEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// The simulated `open_time` `symbol` was delisted at, if any
+    /// registered event says so.
+    pub fn delisted_at(&self, symbol: &Symbol) -> Option<i64> {
+        self.events.lock().unwrap().iter().find_map(|event| match event {
+            SymbolLifecycleEvent::Delisted { symbol: s, effective_at } if s == symbol => Some(*effective_at),
+            _ => None,
+        })
+    }
+
+    /// Walks `symbol`'s rename chain backward to whatever it used to be
+    /// called, oldest first: `(old_name, effective_at)` where `old_name`
+    /// was the symbol's identity for any `open_time` strictly before
+    /// `effective_at`. Stops at the first name with no recorded
+    /// predecessor, or if a cycle is detected (a malformed registration
+    /// shouldn't hang a query). Empty if `symbol` was never renamed.
+    pub fn history(&self, symbol: &Symbol) -> Vec<(Symbol, i64)> {
+        let events = self.events.lock().unwrap();
+        let mut chain = Vec::new();
+        let mut current = symbol.clone();
+        while let Some((previous, effective_at)) = events.iter().find_map(|event| match event {
+            SymbolLifecycleEvent::Renamed { from, to, effective_at } if *to == current => Some((from.clone(), *effective_at)),
+            _ => None,
+        }) {
+            if chain.iter().any(|(name, _)| *name == previous) {
+                break;
+            }
+            chain.push((previous.clone(), effective_at));
+            current = previous;
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(from: &str, to: &str, effective_at: i64) -> SymbolLifecycleEvent {
+        SymbolLifecycleEvent::Renamed { from: Symbol::new(from), to: Symbol::new(to), effective_at }
+    }
+
+    #[test]
+    fn a_symbol_with_no_lifecycle_events_is_unremarkable() {
+        let registry = SymbolLifecycleRegistry::new();
+        let symbol = Symbol::new("BTCUSDT");
+        assert_eq!(registry.delisted_at(&symbol), None);
+        assert!(registry.history(&symbol).is_empty());
+    }
+
+    #[test]
+    fn delisted_at_only_matches_the_registered_symbol() {
+        let registry = SymbolLifecycleRegistry::new();
+        registry.register(SymbolLifecycleEvent::Delisted { symbol: Symbol::new("OLDUSDT"), effective_at: 1_000 });
+        assert_eq!(registry.delisted_at(&Symbol::new("OLDUSDT")), Some(1_000));
+        assert_eq!(registry.delisted_at(&Symbol::new("BTCUSDT")), None);
+    }
+
+    #[test]
+    fn history_returns_a_single_rename_oldest_first() {
+        let registry = SymbolLifecycleRegistry::new();
+        registry.register(rename("OLDUSDT", "NEWUSDT", 5_000));
+        assert_eq!(registry.history(&Symbol::new("NEWUSDT")), vec![(Symbol::new("OLDUSDT"), 5_000)]);
+        assert!(registry.history(&Symbol::new("OLDUSDT")).is_empty());
+    }
+
+    #[test]
+    fn history_walks_a_chain_of_renames_oldest_first() {
+        let registry = SymbolLifecycleRegistry::new();
+        registry.register(rename("AUSDT", "BUSDT", 1_000));
+        registry.register(rename("BUSDT", "CUSDT", 2_000));
+        assert_eq!(
+            registry.history(&Symbol::new("CUSDT")),
+            vec![(Symbol::new("AUSDT"), 1_000), (Symbol::new("BUSDT"), 2_000)]
+        );
+    }
+
+    #[test]
+    fn a_cycle_does_not_hang_history() {
+        let registry = SymbolLifecycleRegistry::new();
+        registry.register(rename("AUSDT", "BUSDT", 1_000));
+        registry.register(rename("BUSDT", "AUSDT", 2_000));
+        // Malformed input (a rename loop); the guard against revisiting a
+        // name already in the chain is what matters here, not the result.
+        assert_eq!(registry.history(&Symbol::new("AUSDT")).len(), 2);
+    }
+}
@@ -0,0 +1,76 @@
+//! Namespace and API token bookkeeping. Lightweight by design: a namespace
+//! is just an id/name pair, and a token is an opaque string that resolves
+//! back to one. The dataset/market stores remain shared across namespaces
+//! (see `AppState`'s `market_store`/`trade_store`) — this only scopes
+//! sessions and webhooks, which is what `http::tenancy::require_token`
+//! actually enforces on the v1 surface.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use uuid::Uuid;
+
+use crate::domain::{Namespace, TokenRole};
+
+#[derive(Default)]
+pub struct NamespaceRegistry {
+    namespaces: RwLock<HashMap<Uuid, Namespace>>,
+    tokens: RwLock<HashMap<String, (Uuid, TokenRole)>>,
+}
+
+impl NamespaceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn create_namespace(&self, name: String) -> Namespace {
+        let namespace = Namespace { id: Uuid::new_v4(), name };
+        self.namespaces.write().unwrap().insert(namespace.id, namespace.clone());
+        namespace
+    }
+
+    pub fn namespace(&self, id: Uuid) -> Option<Namespace> {
+        self.namespaces.read().unwrap().get(&id).cloned()
+    }
+
+    /// Mints an opaque token for `namespace_id` with `role`. Returns `None`
+    /// if the namespace doesn't exist, so bootstrap callers can tell the
+    /// difference between "bad namespace id" and "token minting failed".
+    pub fn issue_token(&self, namespace_id: Uuid, role: TokenRole) -> Option<String> {
+        self.namespace(namespace_id)?;
+        let token = Uuid::new_v4().to_string();
+        self.tokens.write().unwrap().insert(token.clone(), (namespace_id, role));
+        Some(token)
+    }
+
+    /// The namespace and role a token was issued with, or `None` if the
+    /// token is unknown.
+    pub fn resolve(&self, token: &str) -> Option<(Uuid, TokenRole)> {
+        self.tokens.read().unwrap().get(token).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issued_tokens_resolve_back_to_their_namespace_and_role() {
+        let registry = NamespaceRegistry::new();
+        let namespace = registry.create_namespace("team-a".into());
+        let token = registry.issue_token(namespace.id, TokenRole::Operator).unwrap();
+        assert_eq!(registry.resolve(&token), Some((namespace.id, TokenRole::Operator)));
+    }
+
+    #[test]
+    fn unknown_namespace_cannot_be_issued_a_token() {
+        let registry = NamespaceRegistry::new();
+        assert!(registry.issue_token(Uuid::new_v4(), TokenRole::Viewer).is_none());
+    }
+
+    #[test]
+    fn unknown_token_does_not_resolve() {
+        let registry = NamespaceRegistry::new();
+        assert_eq!(registry.resolve("nope"), None);
+    }
+}
@@ -0,0 +1,189 @@
+//! Global (not session-scoped, like everything else under `http::v3`)
+//! rolling 24hr ticker per symbol. Fed from the same `insert_kline` call
+//! site `services::analytics::AnalyticsTracker` observes from
+//! (`services::replay_service`), so a symbol shows up here the moment any
+//! session produces a kline for it. Keeps a bounded window of recent
+//! klines per symbol instead of rescanning `store::MarketStore`'s full
+//! history on every request; aggregates are recomputed over the window at
+//! snapshot time rather than maintained as a running high/low, since
+//! evicting from a running min/max needs its own monotonic-deque
+//! bookkeeping that isn't worth it at 24h-of-1m-candle sizes (<=1440
+//! entries per symbol).
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use rust_decimal_macros::dec;
+
+use crate::domain::{Decimal, Kline, Symbol, Ticker24hr};
+
+/// 24 hours, in the same milliseconds `Kline::open_time` is measured in.
+const WINDOW_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Default)]
+pub struct TickerTracker {
+    symbols: Mutex<HashMap<Symbol, VecDeque<Kline>>>,
+}
+
+impl TickerTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds one more closed kline into its symbol's rolling window,
+    /// evicting any klines that have fallen outside the trailing 24h.
+    pub fn observe_kline(&self, kline: &Kline) {
+        let mut symbols = self.symbols.lock().unwrap();
+        let window = symbols.entry(kline.symbol.clone()).or_default();
+        window.push_back(kline.clone());
+        let cutoff = kline.open_time - WINDOW_MS;
+        while window.front().map(|k| k.open_time < cutoff).unwrap_or(false) {
+            window.pop_front();
+        }
+    }
+
+    /// A snapshot of `symbol`'s rolling ticker. `None` if no kline has been
+    /// observed for it yet.
+    pub fn snapshot(&self, symbol: &Symbol) -> Option<Ticker24hr> {
+        let symbols = self.symbols.lock().unwrap();
+        let window = symbols.get(symbol)?;
+        let first = window.front()?;
+        let last = window.back()?;
+
+        let open_price = first.open;
+        let last_price = last.close;
+        let price_change = last_price - open_price;
+        let price_change_percent = if open_price.is_zero() {
+            dec!(0)
+        } else {
+            price_change / open_price * dec!(100)
+        };
+
+        let mut high_price = first.high;
+        let mut low_price = first.low;
+        let mut notional = dec!(0);
+        let mut volume = dec!(0);
+        let mut quote_volume = dec!(0);
+        for kline in window {
+            high_price = high_price.max(kline.high);
+            low_price = low_price.min(kline.low);
+            notional += kline.close * kline.volume;
+            volume += kline.volume;
+            quote_volume += kline.quote_volume;
+        }
+        let weighted_avg_price = if volume.is_zero() { dec!(0) } else { notional / volume };
+
+        Some(Ticker24hr {
+            symbol: symbol.clone(),
+            price_change,
+            price_change_percent,
+            weighted_avg_price,
+            open_price,
+            high_price,
+            low_price,
+            last_price,
+            volume,
+            quote_volume,
+            open_time: first.open_time,
+            close_time: last.close_time,
+            count: window.len() as i64,
+        })
+    }
+
+    /// Every symbol with at least one observed kline still in its window.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.symbols.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Volume-weighted average price over the trailing `mins` minutes of
+    /// the rolling window, for `GET /api/v3/avgPrice`. Falls back to the
+    /// most recent close if every kline in that span had zero volume.
+    /// `None` if no kline has been observed for the symbol at all.
+    pub fn avg_price(&self, symbol: &Symbol, mins: i64) -> Option<Decimal> {
+        let symbols = self.symbols.lock().unwrap();
+        let window = symbols.get(symbol)?;
+        let last = window.back()?;
+        let cutoff = last.open_time - mins.max(0) * 60_000;
+
+        let mut notional = dec!(0);
+        let mut volume = dec!(0);
+        for kline in window.iter().rev().take_while(|k| k.open_time >= cutoff) {
+            notional += kline.close * kline.volume;
+            volume += kline.volume;
+        }
+
+        Some(if volume.is_zero() { last.close } else { notional / volume })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(symbol: &Symbol, open_time: i64, open: rust_decimal::Decimal, close: rust_decimal::Decimal) -> Kline {
+        Kline {
+            symbol: symbol.clone(),
+            interval: "1m".into(),
+            open_time,
+            close_time: open_time + 59_999,
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+            volume: dec!(1),
+            quote_volume: close,
+            is_closed: true,
+        }
+    }
+
+    #[test]
+    fn an_unobserved_symbol_has_no_snapshot() {
+        let tracker = TickerTracker::new();
+        assert!(tracker.snapshot(&Symbol::new("BTCUSDT")).is_none());
+    }
+
+    #[test]
+    fn price_change_is_measured_from_the_oldest_kline_still_in_the_window() {
+        let tracker = TickerTracker::new();
+        let symbol = Symbol::new("BTCUSDT");
+        tracker.observe_kline(&kline(&symbol, 0, dec!(100), dec!(100)));
+        tracker.observe_kline(&kline(&symbol, 60_000, dec!(100), dec!(110)));
+
+        let ticker = tracker.snapshot(&symbol).unwrap();
+        assert_eq!(ticker.open_price, dec!(100));
+        assert_eq!(ticker.last_price, dec!(110));
+        assert_eq!(ticker.price_change, dec!(10));
+        assert_eq!(ticker.price_change_percent, dec!(10));
+    }
+
+    #[test]
+    fn avg_price_is_none_for_an_unobserved_symbol() {
+        let tracker = TickerTracker::new();
+        assert!(tracker.avg_price(&Symbol::new("BTCUSDT"), 5).is_none());
+    }
+
+    #[test]
+    fn avg_price_weights_by_volume_within_the_trailing_window() {
+        let tracker = TickerTracker::new();
+        let symbol = Symbol::new("BTCUSDT");
+        tracker.observe_kline(&kline(&symbol, 0, dec!(100), dec!(100)));
+        tracker.observe_kline(&kline(&symbol, 60_000, dec!(100), dec!(110)));
+        tracker.observe_kline(&kline(&symbol, 120_000, dec!(110), dec!(120)));
+
+        // Only the last minute: klines at 60_000 and 120_000.
+        let avg = tracker.avg_price(&symbol, 1).unwrap();
+        assert_eq!(avg, dec!(115));
+    }
+
+    #[test]
+    fn klines_older_than_24h_are_evicted_from_the_window() {
+        let tracker = TickerTracker::new();
+        let symbol = Symbol::new("BTCUSDT");
+        tracker.observe_kline(&kline(&symbol, 0, dec!(100), dec!(100)));
+        tracker.observe_kline(&kline(&symbol, WINDOW_MS + 60_000, dec!(200), dec!(210)));
+
+        let ticker = tracker.snapshot(&symbol).unwrap();
+        assert_eq!(ticker.open_price, dec!(200));
+        assert_eq!(ticker.count, 1);
+    }
+}
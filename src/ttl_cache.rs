@@ -0,0 +1,63 @@
+//! A tiny memoization cache for values that are cheap to recompute but
+//! still worth not recomputing on every request — today just
+//! `v3::exchange_info`'s registered-symbol list (see
+//! `AppState::cached_symbol_filters`), the "symbol-list latency" a UI
+//! polling `GET /api/v3/exchangeInfo` actually cares about.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub struct TtlCache<T> {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> TtlCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, entry: Mutex::new(None) }
+    }
+
+    /// Returns the cached value if it's younger than `ttl`, otherwise
+    /// calls `compute`, caches the result, and returns that instead.
+    pub fn get_or_compute(&self, compute: impl FnOnce() -> T) -> T {
+        let mut entry = self.entry.lock().unwrap();
+        if let Some((cached_at, value)) = entry.as_ref() {
+            if cached_at.elapsed() < self.ttl {
+                return value.clone();
+            }
+        }
+        let value = compute();
+        *entry = Some((Instant::now(), value.clone()));
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn a_second_call_within_ttl_reuses_the_cached_value() {
+        let cache = TtlCache::new(Duration::from_secs(60));
+        let calls = AtomicU32::new(0);
+        let compute = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            42
+        };
+
+        assert_eq!(cache.get_or_compute(compute), 42);
+        assert_eq!(cache.get_or_compute(compute), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn a_call_after_ttl_expires_recomputes() {
+        let cache = TtlCache::new(Duration::from_millis(1));
+        cache.get_or_compute(|| 1);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cache.get_or_compute(|| 2), 2);
+    }
+}
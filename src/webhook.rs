@@ -0,0 +1,235 @@
+//! Outbound webhook delivery: per-session subscriptions POSTed to on
+//! session lifecycle events, with retry/backoff and a queryable delivery
+//! log. `WebhookEvent::OrderFill` and `DatasetReady` are registrable but
+//! never fired yet — this tree has no order pipeline, and market data is
+//! pre-seeded globally at startup rather than fetched on demand per
+//! session, so there's no asynchronous readiness transition to hook.
+//! `SessionEnded` and `SummaryReady` do fire, both from
+//! [`AppState::set_status`](crate::state::AppState::set_status).
+//!
+//! Firing lives on [`AppState`](crate::state::AppState) rather than here,
+//! since delivering asynchronously needs an owned, cloneable handle to
+//! spawn into a background task the same way [`ReplayService`]
+//! (crate::services::ReplayService) spawns its replay loop.
+//!
+//! [`WebhookRegistry::client`] already hands out a clone of one shared,
+//! pooled `reqwest::Client` built once in [`WebhookRegistry::new`] — this
+//! is the only outbound HTTP call in this tree, there's no `infra/binance`
+//! module or `fetch_available_range` anywhere to apply the same treatment
+//! to. See `ttl_cache::TtlCache` and `AppState::cached_symbol_filters` for
+//! where this tree's actual `exchangeInfo`-latency work landed instead.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::{DeliveryStatus, WebhookConfig, WebhookDelivery, WebhookEvent};
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(200);
+
+struct Webhook {
+    session_id: Uuid,
+    namespace_id: Uuid,
+    config: WebhookConfig,
+    deliveries: Mutex<Vec<WebhookDelivery>>,
+}
+
+pub struct WebhookRegistry {
+    webhooks: RwLock<HashMap<Uuid, Webhook>>,
+    client: reqwest::Client,
+}
+
+impl WebhookRegistry {
+    pub fn new() -> Self {
+        Self {
+            webhooks: RwLock::new(HashMap::new()),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn register(&self, session_id: Uuid, namespace_id: Uuid, config: WebhookConfig) -> Uuid {
+        let id = Uuid::new_v4();
+        self.webhooks.write().unwrap().insert(
+            id,
+            Webhook {
+                session_id,
+                namespace_id,
+                config,
+                deliveries: Mutex::new(Vec::new()),
+            },
+        );
+        id
+    }
+
+    /// Delivery log for `id`, scoped to `namespace_id`. Returns `None`
+    /// both when the webhook doesn't exist and when it belongs to a
+    /// different namespace, so a caller can't distinguish the two.
+    pub fn deliveries(&self, id: Uuid, namespace_id: Uuid) -> Option<Vec<WebhookDelivery>> {
+        self.webhooks
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|webhook| webhook.namespace_id == namespace_id)
+            .map(|webhook| webhook.deliveries.lock().unwrap().clone())
+    }
+
+    pub(crate) fn client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
+
+    /// Webhooks subscribed to `event` for `session_id`, as `(id, url, secret)`.
+    pub(crate) fn targets(&self, session_id: Uuid, event: WebhookEvent) -> Vec<(Uuid, String, String)> {
+        self.webhooks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, webhook)| webhook.session_id == session_id && webhook.config.events.contains(&event))
+            .map(|(id, webhook)| (*id, webhook.config.url.clone(), webhook.config.secret.clone()))
+            .collect()
+    }
+
+    pub(crate) fn record(&self, webhook_id: Uuid, delivery: WebhookDelivery) {
+        if let Some(webhook) = self.webhooks.read().unwrap().get(&webhook_id) {
+            webhook.deliveries.lock().unwrap().push(delivery);
+        }
+    }
+}
+
+impl Default for WebhookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// POSTs `payload` to `url` with `secret` as a shared-secret header,
+/// retrying with exponential backoff up to [`MAX_ATTEMPTS`] before giving
+/// up and recording the last error.
+pub(crate) async fn deliver(client: &reqwest::Client, url: &str, secret: &str, event: WebhookEvent, payload: Value) -> WebhookDelivery {
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        let result = client.post(url).header("X-Webhook-Secret", secret).json(&payload).send().await;
+
+        let error = match result {
+            Ok(response) if response.status().is_success() => {
+                return WebhookDelivery {
+                    id: Uuid::new_v4(),
+                    event,
+                    status: DeliveryStatus::Delivered,
+                    attempts,
+                    last_error: None,
+                };
+            }
+            Ok(response) => format!("http {}", response.status()),
+            Err(err) => err.to_string(),
+        };
+
+        if attempts >= MAX_ATTEMPTS {
+            return WebhookDelivery {
+                id: Uuid::new_v4(),
+                event,
+                status: DeliveryStatus::Failed,
+                attempts,
+                last_error: Some(error),
+            };
+        }
+
+        tokio::time::sleep(BASE_BACKOFF * 2u32.pow(attempts - 1)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_only_match_subscribed_session_and_event() {
+        let registry = WebhookRegistry::new();
+        let namespace_id = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
+        let other_session_id = Uuid::new_v4();
+
+        registry.register(
+            session_id,
+            namespace_id,
+            WebhookConfig {
+                url: "http://example.invalid/hook".into(),
+                secret: "shh".into(),
+                events: vec![WebhookEvent::SessionEnded],
+            },
+        );
+        registry.register(
+            session_id,
+            namespace_id,
+            WebhookConfig {
+                url: "http://example.invalid/other".into(),
+                secret: "shh".into(),
+                events: vec![WebhookEvent::DatasetReady],
+            },
+        );
+        registry.register(
+            other_session_id,
+            namespace_id,
+            WebhookConfig {
+                url: "http://example.invalid/elsewhere".into(),
+                secret: "shh".into(),
+                events: vec![WebhookEvent::SessionEnded],
+            },
+        );
+
+        let targets = registry.targets(session_id, WebhookEvent::SessionEnded);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].1, "http://example.invalid/hook");
+    }
+
+    #[test]
+    fn deliveries_are_recorded_against_the_right_webhook() {
+        let registry = WebhookRegistry::new();
+        let namespace_id = Uuid::new_v4();
+        let id = registry.register(
+            Uuid::new_v4(),
+            namespace_id,
+            WebhookConfig {
+                url: "http://example.invalid/hook".into(),
+                secret: "shh".into(),
+                events: vec![WebhookEvent::SessionEnded],
+            },
+        );
+
+        registry.record(
+            id,
+            WebhookDelivery {
+                id: Uuid::new_v4(),
+                event: WebhookEvent::SessionEnded,
+                status: DeliveryStatus::Delivered,
+                attempts: 1,
+                last_error: None,
+            },
+        );
+
+        let deliveries = registry.deliveries(id, namespace_id).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+    }
+
+    #[test]
+    fn deliveries_are_not_visible_from_another_namespace() {
+        let registry = WebhookRegistry::new();
+        let id = registry.register(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            WebhookConfig {
+                url: "http://example.invalid/hook".into(),
+                secret: "shh".into(),
+                events: vec![WebhookEvent::SessionEnded],
+            },
+        );
+
+        assert!(registry.deliveries(id, Uuid::new_v4()).is_none());
+    }
+}
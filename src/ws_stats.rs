@@ -0,0 +1,115 @@
+//! Per-session websocket connection history: one [`WsConnectionRecord`]
+//! per connect, tracking messages sent and broadcast-lag drops until it
+//! disconnects. Exposed at `GET /api/v1/sessions/:id/ws-stats` so flaky
+//! consumer behavior across a long replay can be diagnosed after the
+//! fact — see `http::ws` for where each record's lifecycle is driven.
+
+use std::sync::Mutex;
+
+use crate::domain::WsConnectionRecord;
+
+#[derive(Debug, Default)]
+pub struct WsStatsTracker {
+    connections: Mutex<Vec<WsConnectionRecord>>,
+}
+
+impl WsStatsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a new connection record at simulated time `connected_at` and
+    /// returns its index, to be passed back to the other methods here.
+    pub fn open(&self, connected_at: i64) -> usize {
+        let mut connections = self.connections.lock().unwrap();
+        connections.push(WsConnectionRecord {
+            connected_at,
+            disconnected_at: None,
+            messages_sent: 0,
+            lag_drops: 0,
+        });
+        connections.len() - 1
+    }
+
+    pub fn record_message(&self, index: usize) {
+        if let Some(record) = self.connections.lock().unwrap().get_mut(index) {
+            record.messages_sent += 1;
+        }
+    }
+
+    pub fn record_lag_drop(&self, index: usize, dropped: u64) {
+        if let Some(record) = self.connections.lock().unwrap().get_mut(index) {
+            record.lag_drops += dropped;
+        }
+    }
+
+    pub fn close(&self, index: usize, disconnected_at: i64) {
+        if let Some(record) = self.connections.lock().unwrap().get_mut(index) {
+            record.disconnected_at = Some(disconnected_at);
+        }
+    }
+
+    /// Every connection recorded so far, oldest first.
+    pub fn history(&self) -> Vec<WsConnectionRecord> {
+        self.connections.lock().unwrap().clone()
+    }
+
+    /// Whether at least one recorded connection hasn't disconnected yet —
+    /// used by `services::reaper` to treat a session with a live subscriber
+    /// as active regardless of how long it's been since the last API call.
+    pub fn has_open_connection(&self) -> bool {
+        self.connections.lock().unwrap().iter().any(|record| record.disconnected_at.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_a_connection_through_its_lifecycle() {
+        let tracker = WsStatsTracker::new();
+        let index = tracker.open(1_000);
+        tracker.record_message(index);
+        tracker.record_message(index);
+        tracker.record_lag_drop(index, 3);
+        tracker.close(index, 2_000);
+
+        let history = tracker.history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].connected_at, 1_000);
+        assert_eq!(history[0].disconnected_at, Some(2_000));
+        assert_eq!(history[0].messages_sent, 2);
+        assert_eq!(history[0].lag_drops, 3);
+    }
+
+    #[test]
+    fn concurrent_connections_get_independent_records() {
+        let tracker = WsStatsTracker::new();
+        let first = tracker.open(0);
+        let second = tracker.open(500);
+        tracker.record_message(first);
+        tracker.close(first, 100);
+
+        let history = tracker.history();
+        assert_eq!(history[0].messages_sent, 1);
+        assert_eq!(history[0].disconnected_at, Some(100));
+        assert_eq!(history[1].connected_at, 500);
+        assert_eq!(history[1].disconnected_at, None);
+        let _ = second;
+    }
+
+    #[test]
+    fn has_open_connection_is_true_until_every_connection_closes() {
+        let tracker = WsStatsTracker::new();
+        let first = tracker.open(0);
+        let second = tracker.open(0);
+        assert!(tracker.has_open_connection());
+
+        tracker.close(first, 100);
+        assert!(tracker.has_open_connection());
+
+        tracker.close(second, 200);
+        assert!(!tracker.has_open_connection());
+    }
+}